@@ -1,23 +1,39 @@
-use std::str::FromStr;
+use std::{collections::BTreeMap, str::FromStr, sync::Arc};
 
 use open_rpc_derive::document_rpc;
 use open_rpc_schema::document::OpenrpcDocument;
-use rwa_types::rapi::{get_rwa_accounts_by_mint, FullAccount};
+use rwa_types::events::DEFAULT_ACCOUNT_CHANGE_CHANNEL;
+use rwa_types::rapi::{
+    check_transfer_compliance, get_data_accounts_by_mint, get_identity_accounts_by_owner,
+    get_policy_accounts, get_rwa_account, get_rwa_accounts_by_mint, get_rwa_accounts_by_mints,
+    get_rwa_assets_by_authority, get_rwa_holders, get_rwa_mints, get_velocity_allowance,
+    ChildAccountOptions, DataAccount, DataRegistryKey, FullAccount, IdentityAccountWithRegistry,
+    Page, Pagination, PolicyAccountDetails, PolicyEngineKey, RwaAccountWithMint, RwaAssetRoles,
+    RwaHolders, RwaMint, RwaMintFilter, SortBy, SortDirection, TransferCompliance,
+    VelocityAllowance,
+};
 use sea_orm::{ConnectionTrait, DbBackend, Statement};
 use solana_sdk::pubkey::Pubkey;
 use sqlx::postgres::PgPoolOptions;
+use tokio::sync::broadcast;
 use {
     crate::api::*,
     crate::config::Config,
     crate::error::RwaApiError,
+    crate::subscriptions::{
+        account_change_channel, spawn_account_change_listener, SharedAccountChange,
+    },
     async_trait::async_trait,
     sea_orm::{DatabaseConnection, SqlxPostgresConnector},
 };
 
 pub struct RwaApi {
     db_connection: DatabaseConnection,
+    account_changes: broadcast::Sender<Arc<SharedAccountChange>>,
 }
 
+const MAX_BATCH_SIZE: usize = 1000;
+
 pub fn validate_pubkey(str_pubkey: String) -> Result<Pubkey, RwaApiError> {
     Pubkey::from_str(&str_pubkey).map_err(|_| RwaApiError::PubkeyValidationError(str_pubkey))
 }
@@ -29,9 +45,19 @@ impl RwaApi {
             .connect(&config.database_url)
             .await?;
 
+        let account_changes = account_change_channel();
+        spawn_account_change_listener(
+            pool.clone(),
+            config
+                .database_listener_channel
+                .unwrap_or_else(|| DEFAULT_ACCOUNT_CHANGE_CHANNEL.to_string()),
+            account_changes.clone(),
+        );
+
         let conn = SqlxPostgresConnector::from_sqlx_postgres_pool(pool);
         Ok(RwaApi {
             db_connection: conn,
+            account_changes,
         })
     }
 }
@@ -56,14 +82,309 @@ impl ApiContract for RwaApi {
         Ok(())
     }
 
+    fn account_changes(&self) -> broadcast::Receiver<Arc<SharedAccountChange>> {
+        self.account_changes.subscribe()
+    }
+
     async fn get_rwa_accounts_by_mint(
         self: &RwaApi,
         payload: GetRwaAccountsByMint,
     ) -> Result<FullAccount, RwaApiError> {
-        let GetRwaAccountsByMint { id } = payload;
+        let GetRwaAccountsByMint {
+            id,
+            include,
+            limit,
+            page,
+            before,
+            after,
+            sort_by,
+            sort_direction,
+            show_closed,
+            at_slot,
+        } = payload;
         let id_bytes = validate_pubkey(id.clone())?.to_bytes().to_vec();
+        let options = ChildAccountOptions {
+            include: include.unwrap_or_default(),
+            pagination: Pagination::new(limit, page, before, after, sort_by, sort_direction)?
+                .require_sort(&[SortBy::Id, SortBy::SlotUpdated])?,
+            show_closed: show_closed.unwrap_or(false),
+            at_slot: at_slot.map(|slot| slot as i64),
+        };
+
+        get_rwa_accounts_by_mint(&self.db_connection, id_bytes, &options)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_rwa_accounts_by_mints(
+        self: &RwaApi,
+        payload: GetRwaAccountsByMints,
+    ) -> Result<BTreeMap<String, FullAccount>, RwaApiError> {
+        let GetRwaAccountsByMints { ids, show_closed } = payload;
+        if ids.len() > MAX_BATCH_SIZE {
+            return Err(RwaApiError::BatchSizeExceededError);
+        }
+        let id_bytes = ids
+            .into_iter()
+            .map(|id| validate_pubkey(id).map(|pubkey| pubkey.to_bytes().to_vec()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        get_rwa_accounts_by_mints(&self.db_connection, id_bytes, show_closed.unwrap_or(false))
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_identity_accounts_by_owner(
+        self: &RwaApi,
+        payload: GetIdentityAccountsByOwner,
+    ) -> Result<Page<IdentityAccountWithRegistry>, RwaApiError> {
+        let GetIdentityAccountsByOwner {
+            owner,
+            limit,
+            page,
+            before,
+            after,
+            sort_by,
+            sort_direction,
+            show_closed,
+        } = payload;
+        let owner_bytes = validate_pubkey(owner.clone())?.to_bytes().to_vec();
+        let pagination = Pagination::new(limit, page, before, after, sort_by, sort_direction)?
+            .require_sort(&[SortBy::Id, SortBy::SlotUpdated])?;
+
+        get_identity_accounts_by_owner(
+            &self.db_connection,
+            owner_bytes,
+            &pagination,
+            show_closed.unwrap_or(false),
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn check_transfer_compliance(
+        self: &RwaApi,
+        payload: CheckTransferCompliance,
+    ) -> Result<TransferCompliance, RwaApiError> {
+        let CheckTransferCompliance {
+            mint,
+            from,
+            to,
+            amount,
+            timestamp,
+        } = payload;
+        let mint_bytes = validate_pubkey(mint)?.to_bytes().to_vec();
+        let from_bytes = validate_pubkey(from)?.to_bytes().to_vec();
+        let to_bytes = validate_pubkey(to)?.to_bytes().to_vec();
+
+        check_transfer_compliance(
+            &self.db_connection,
+            mint_bytes,
+            from_bytes,
+            to_bytes,
+            amount,
+            timestamp,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_rwa_holders(
+        self: &RwaApi,
+        payload: GetRwaHolders,
+    ) -> Result<RwaHolders, RwaApiError> {
+        let GetRwaHolders {
+            mint,
+            limit,
+            page,
+            before,
+            after,
+            sort_by,
+            sort_direction,
+        } = payload;
+        let mint_bytes = validate_pubkey(mint)?.to_bytes().to_vec();
+        // Largest holders first unless another sort is requested.
+        let (sort_by, sort_direction) = match sort_by {
+            Some(sort_by) => (Some(sort_by), sort_direction),
+            None => (
+                Some(SortBy::Amount),
+                sort_direction.or(Some(SortDirection::Desc)),
+            ),
+        };
+        let pagination = Pagination::new(limit, page, before, after, sort_by, sort_direction)?
+            .require_sort(&[SortBy::Id, SortBy::Amount])?;
+
+        get_rwa_holders(&self.db_connection, mint_bytes, &pagination)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_data_accounts_by_mint(
+        self: &RwaApi,
+        payload: GetDataAccountsByMint,
+    ) -> Result<Page<DataAccount>, RwaApiError> {
+        let GetDataAccountsByMint {
+            mint,
+            data_registry,
+            data_types,
+            limit,
+            page,
+            before,
+            after,
+            sort_by,
+            sort_direction,
+            show_closed,
+        } = payload;
+        let key = match (mint, data_registry) {
+            (Some(mint), None) => DataRegistryKey::Mint(validate_pubkey(mint)?.to_bytes().to_vec()),
+            (None, Some(registry)) => {
+                DataRegistryKey::Registry(validate_pubkey(registry)?.to_bytes().to_vec())
+            }
+            _ => {
+                return Err(RwaApiError::ValidationError(
+                    "Exactly one of mint or dataRegistry must be provided".to_string(),
+                ))
+            }
+        };
+        let pagination = Pagination::new(limit, page, before, after, sort_by, sort_direction)?
+            .require_sort(&[SortBy::Id, SortBy::SlotUpdated])?;
+
+        get_data_accounts_by_mint(
+            &self.db_connection,
+            key,
+            data_types,
+            &pagination,
+            show_closed.unwrap_or(false),
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_policy_accounts(
+        self: &RwaApi,
+        payload: GetPolicyAccounts,
+    ) -> Result<Page<PolicyAccountDetails>, RwaApiError> {
+        let GetPolicyAccounts {
+            mint,
+            policy_engine,
+            limit,
+            page,
+            before,
+            after,
+            sort_by,
+            sort_direction,
+            show_closed,
+        } = payload;
+        let key = match (mint, policy_engine) {
+            (Some(mint), None) => PolicyEngineKey::Mint(validate_pubkey(mint)?.to_bytes().to_vec()),
+            (None, Some(engine)) => {
+                PolicyEngineKey::PolicyEngine(validate_pubkey(engine)?.to_bytes().to_vec())
+            }
+            _ => {
+                return Err(RwaApiError::ValidationError(
+                    "Exactly one of mint or policyEngine must be provided".to_string(),
+                ))
+            }
+        };
+        let pagination = Pagination::new(limit, page, before, after, sort_by, sort_direction)?
+            .require_sort(&[SortBy::Id, SortBy::SlotUpdated])?;
+
+        get_policy_accounts(
+            &self.db_connection,
+            key,
+            &pagination,
+            show_closed.unwrap_or(false),
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_velocity_allowance(
+        self: &RwaApi,
+        payload: GetVelocityAllowance,
+    ) -> Result<VelocityAllowance, RwaApiError> {
+        let GetVelocityAllowance {
+            mint,
+            owner,
+            timestamp,
+        } = payload;
+        let mint_bytes = validate_pubkey(mint)?.to_bytes().to_vec();
+        let owner_bytes = validate_pubkey(owner)?.to_bytes().to_vec();
+
+        get_velocity_allowance(&self.db_connection, mint_bytes, owner_bytes, timestamp)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_rwa_assets_by_authority(
+        self: &RwaApi,
+        payload: GetRwaAssetsByAuthority,
+    ) -> Result<Page<RwaAssetRoles>, RwaApiError> {
+        let GetRwaAssetsByAuthority {
+            authority,
+            limit,
+            page,
+            before,
+            after,
+            sort_by,
+            sort_direction,
+            show_closed,
+        } = payload;
+        let authority_bytes = validate_pubkey(authority)?.to_bytes().to_vec();
+        let pagination = Pagination::new(limit, page, before, after, sort_by, sort_direction)?
+            .require_id_sort()?;
+
+        get_rwa_assets_by_authority(
+            &self.db_connection,
+            authority_bytes,
+            &pagination,
+            show_closed.unwrap_or(false),
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn get_rwa_account(
+        self: &RwaApi,
+        payload: GetRwaAccount,
+    ) -> Result<RwaAccountWithMint, RwaApiError> {
+        let GetRwaAccount { address } = payload;
+        let address_bytes = validate_pubkey(address)?.to_bytes().to_vec();
+
+        get_rwa_account(&self.db_connection, address_bytes)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_rwa_mints(
+        self: &RwaApi,
+        payload: GetRwaMints,
+    ) -> Result<Page<RwaMint>, RwaApiError> {
+        let GetRwaMints {
+            has_policy_engine,
+            authority,
+            updated_since,
+            limit,
+            page,
+            before,
+            after,
+            sort_by,
+            sort_direction,
+            show_closed,
+        } = payload;
+        let authority = authority
+            .map(|authority| validate_pubkey(authority).map(|pubkey| pubkey.to_bytes().to_vec()))
+            .transpose()?;
+        let filter = RwaMintFilter {
+            has_policy_engine,
+            authority,
+            updated_since: updated_since.map(|slot| slot as i64),
+            show_closed: show_closed.unwrap_or(false),
+        };
+        let pagination = Pagination::new(limit, page, before, after, sort_by, sort_direction)?
+            .require_sort(&[SortBy::Id, SortBy::SlotUpdated])?;
 
-        get_rwa_accounts_by_mint(&self.db_connection, id_bytes)
+        get_rwa_mints(&self.db_connection, &filter, &pagination)
             .await
             .map_err(Into::into)
     }
@@ -2,7 +2,12 @@ use crate::error::RwaApiError;
 use async_trait::async_trait;
 use open_rpc_derive::{document_rpc, rpc};
 use open_rpc_schema::schemars::JsonSchema;
+use rwa_types::rapi::{AccountInclude, DataAccountType, SortBy, SortDirection};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+use crate::subscriptions::SharedAccountChange;
 
 mod api_impl;
 pub use api_impl::*;
@@ -11,6 +16,140 @@ pub use api_impl::*;
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct GetRwaAccountsByMint {
     pub id: String,
+    pub include: Option<Vec<AccountInclude>>,
+    pub limit: Option<u64>,
+    pub page: Option<u64>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub sort_by: Option<SortBy>,
+    pub sort_direction: Option<SortDirection>,
+    pub show_closed: Option<bool>,
+    pub at_slot: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetRwaAccountsByMints {
+    pub ids: Vec<String>,
+    pub show_closed: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetIdentityAccountsByOwner {
+    pub owner: String,
+    pub limit: Option<u64>,
+    pub page: Option<u64>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub sort_by: Option<SortBy>,
+    pub sort_direction: Option<SortDirection>,
+    pub show_closed: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct CheckTransferCompliance {
+    pub mint: String,
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub timestamp: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetRwaHolders {
+    pub mint: String,
+    pub limit: Option<u64>,
+    pub page: Option<u64>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub sort_by: Option<SortBy>,
+    pub sort_direction: Option<SortDirection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetDataAccountsByMint {
+    pub mint: Option<String>,
+    pub data_registry: Option<String>,
+    pub data_types: Option<Vec<DataAccountType>>,
+    pub limit: Option<u64>,
+    pub page: Option<u64>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub sort_by: Option<SortBy>,
+    pub sort_direction: Option<SortDirection>,
+    pub show_closed: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetPolicyAccounts {
+    pub mint: Option<String>,
+    pub policy_engine: Option<String>,
+    pub limit: Option<u64>,
+    pub page: Option<u64>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub sort_by: Option<SortBy>,
+    pub sort_direction: Option<SortDirection>,
+    pub show_closed: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetVelocityAllowance {
+    pub mint: String,
+    pub owner: String,
+    pub timestamp: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetRwaAssetsByAuthority {
+    pub authority: String,
+    pub limit: Option<u64>,
+    pub page: Option<u64>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub sort_by: Option<SortBy>,
+    pub sort_direction: Option<SortDirection>,
+    pub show_closed: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetRwaAccount {
+    pub address: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetRwaMints {
+    pub has_policy_engine: Option<bool>,
+    pub authority: Option<String>,
+    pub updated_since: Option<u64>,
+    pub limit: Option<u64>,
+    pub page: Option<u64>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub sort_by: Option<SortBy>,
+    pub sort_direction: Option<SortDirection>,
+    pub show_closed: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct SubscribeRwaMint {
+    pub mint: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct SubscribeIdentity {
+    pub owner: String,
 }
 
 #[document_rpc]
@@ -18,6 +157,8 @@ pub struct GetRwaAccountsByMint {
 pub trait ApiContract: Send + Sync + 'static {
     async fn liveness(&self) -> Result<(), RwaApiError>;
     async fn readiness(&self) -> Result<(), RwaApiError>;
+    /// Account changes committed by the indexer, feeding the WebSocket subscriptions.
+    fn account_changes(&self) -> broadcast::Receiver<Arc<SharedAccountChange>>;
 
     #[rpc(
         name = "getRwaAccountsByMint",
@@ -28,4 +169,104 @@ pub trait ApiContract: Send + Sync + 'static {
         &self,
         payload: GetRwaAccountsByMint,
     ) -> Result<rwa_types::rapi::FullAccount, RwaApiError>;
+
+    #[rpc(
+        name = "getRwaAccountsByMints",
+        params = "named",
+        summary = "Get the RWA registries of up to 1000 mints, keyed by mint"
+    )]
+    async fn get_rwa_accounts_by_mints(
+        &self,
+        payload: GetRwaAccountsByMints,
+    ) -> Result<std::collections::BTreeMap<String, rwa_types::rapi::FullAccount>, RwaApiError>;
+
+    #[rpc(
+        name = "getIdentityAccountsByOwner",
+        params = "named",
+        summary = "Get all identity accounts of a wallet across identity registries"
+    )]
+    async fn get_identity_accounts_by_owner(
+        &self,
+        payload: GetIdentityAccountsByOwner,
+    ) -> Result<rwa_types::rapi::Page<rwa_types::rapi::IdentityAccountWithRegistry>, RwaApiError>;
+
+    #[rpc(
+        name = "checkTransferCompliance",
+        params = "named",
+        summary = "Evaluate a mint's transfer policies against a prospective transfer"
+    )]
+    async fn check_transfer_compliance(
+        &self,
+        payload: CheckTransferCompliance,
+    ) -> Result<rwa_types::rapi::TransferCompliance, RwaApiError>;
+
+    #[rpc(
+        name = "getRwaHolders",
+        params = "named",
+        summary = "Get the holders of an RWA mint with their balances and the mint supply"
+    )]
+    async fn get_rwa_holders(
+        &self,
+        payload: GetRwaHolders,
+    ) -> Result<rwa_types::rapi::RwaHolders, RwaApiError>;
+
+    #[rpc(
+        name = "getDataAccountsByMint",
+        params = "named",
+        summary = "Get the data accounts of an asset by mint or data registry, filterable by type"
+    )]
+    async fn get_data_accounts_by_mint(
+        &self,
+        payload: GetDataAccountsByMint,
+    ) -> Result<rwa_types::rapi::Page<rwa_types::rapi::DataAccount>, RwaApiError>;
+
+    #[rpc(
+        name = "getPolicyAccounts",
+        params = "named",
+        summary = "Get the decoded policy accounts of an asset by mint or policy engine"
+    )]
+    async fn get_policy_accounts(
+        &self,
+        payload: GetPolicyAccounts,
+    ) -> Result<rwa_types::rapi::Page<rwa_types::rapi::PolicyAccountDetails>, RwaApiError>;
+
+    #[rpc(
+        name = "getVelocityAllowance",
+        params = "named",
+        summary = "Get the amount and transfers an owner has left under a mint's velocity policies"
+    )]
+    async fn get_velocity_allowance(
+        &self,
+        payload: GetVelocityAllowance,
+    ) -> Result<rwa_types::rapi::VelocityAllowance, RwaApiError>;
+
+    #[rpc(
+        name = "getRwaAssetsByAuthority",
+        params = "named",
+        summary = "Get every mint where a pubkey is authority or delegate of an RWA program account"
+    )]
+    async fn get_rwa_assets_by_authority(
+        &self,
+        payload: GetRwaAssetsByAuthority,
+    ) -> Result<rwa_types::rapi::Page<rwa_types::rapi::RwaAssetRoles>, RwaApiError>;
+
+    #[rpc(
+        name = "getRwaAccount",
+        params = "named",
+        summary = "Get any RWA account by address with its type and the mint it belongs to"
+    )]
+    async fn get_rwa_account(
+        &self,
+        payload: GetRwaAccount,
+    ) -> Result<rwa_types::rapi::RwaAccountWithMint, RwaApiError>;
+
+    #[rpc(
+        name = "getRwaMints",
+        params = "named",
+        summary = "List indexed RWA mints with their configured programs and account counts"
+    )]
+    async fn get_rwa_mints(
+        &self,
+        payload: GetRwaMints,
+    ) -> Result<rwa_types::rapi::Page<rwa_types::rapi::RwaMint>, RwaApiError>;
 }
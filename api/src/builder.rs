@@ -1,8 +1,9 @@
-use jsonrpsee::RpcModule;
+use jsonrpsee::{types::error::CallError, RpcModule};
 use log::debug;
 
 use crate::api::*;
 use crate::error::RwaApiError;
+use crate::subscriptions::{stream_identity_updates, stream_mint_updates};
 
 pub struct RpcApiBuilder;
 
@@ -35,6 +36,183 @@ impl RpcApiBuilder {
         )?;
         module.register_alias("getRwaAccountsByMint", "get_rwa_accounts_by_mint")?;
 
+        // get_rwa_accounts_by_mints
+        module.register_async_method(
+            "get_rwa_accounts_by_mints",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetRwaAccountsByMints>()?;
+                rpc_context
+                    .get_rwa_accounts_by_mints(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias("getRwaAccountsByMints", "get_rwa_accounts_by_mints")?;
+
+        // get_identity_accounts_by_owner
+        module.register_async_method(
+            "get_identity_accounts_by_owner",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetIdentityAccountsByOwner>()?;
+                rpc_context
+                    .get_identity_accounts_by_owner(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias(
+            "getIdentityAccountsByOwner",
+            "get_identity_accounts_by_owner",
+        )?;
+
+        // check_transfer_compliance
+        module.register_async_method(
+            "check_transfer_compliance",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<CheckTransferCompliance>()?;
+                rpc_context
+                    .check_transfer_compliance(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias("checkTransferCompliance", "check_transfer_compliance")?;
+
+        // get_rwa_holders
+        module.register_async_method("get_rwa_holders", |rpc_params, rpc_context| async move {
+            let payload = rpc_params.parse::<GetRwaHolders>()?;
+            rpc_context
+                .get_rwa_holders(payload)
+                .await
+                .map_err(Into::into)
+        })?;
+        module.register_alias("getRwaHolders", "get_rwa_holders")?;
+
+        // get_data_accounts_by_mint
+        module.register_async_method(
+            "get_data_accounts_by_mint",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetDataAccountsByMint>()?;
+                rpc_context
+                    .get_data_accounts_by_mint(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias("getDataAccountsByMint", "get_data_accounts_by_mint")?;
+
+        // get_policy_accounts
+        module.register_async_method(
+            "get_policy_accounts",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetPolicyAccounts>()?;
+                rpc_context
+                    .get_policy_accounts(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias("getPolicyAccounts", "get_policy_accounts")?;
+
+        // get_velocity_allowance
+        module.register_async_method(
+            "get_velocity_allowance",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetVelocityAllowance>()?;
+                rpc_context
+                    .get_velocity_allowance(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias("getVelocityAllowance", "get_velocity_allowance")?;
+
+        // get_rwa_assets_by_authority
+        module.register_async_method(
+            "get_rwa_assets_by_authority",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetRwaAssetsByAuthority>()?;
+                rpc_context
+                    .get_rwa_assets_by_authority(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias("getRwaAssetsByAuthority", "get_rwa_assets_by_authority")?;
+
+        // get_rwa_account
+        module.register_async_method("get_rwa_account", |rpc_params, rpc_context| async move {
+            let payload = rpc_params.parse::<GetRwaAccount>()?;
+            rpc_context
+                .get_rwa_account(payload)
+                .await
+                .map_err(Into::into)
+        })?;
+        module.register_alias("getRwaAccount", "get_rwa_account")?;
+
+        // get_rwa_mints
+        module.register_async_method("get_rwa_mints", |rpc_params, rpc_context| async move {
+            let payload = rpc_params.parse::<GetRwaMints>()?;
+            rpc_context.get_rwa_mints(payload).await.map_err(Into::into)
+        })?;
+        module.register_alias("getRwaMints", "get_rwa_mints")?;
+
+        // subscribe_rwa_mint
+        module.register_subscription(
+            "subscribe_rwa_mint",
+            "rwaMintNotification",
+            "unsubscribe_rwa_mint",
+            |rpc_params, mut sink, rpc_context| {
+                let payload = match rpc_params.parse::<SubscribeRwaMint>() {
+                    Ok(payload) => payload,
+                    Err(e) => {
+                        let _ = sink.reject(e);
+                        return Ok(());
+                    }
+                };
+                let mint = match validate_pubkey(payload.mint) {
+                    Ok(mint) => mint.to_string(),
+                    Err(e) => {
+                        let _ = sink.reject(CallError::from_std_error(e));
+                        return Ok(());
+                    }
+                };
+                sink.accept()?;
+                tokio::spawn(stream_mint_updates(rpc_context, sink, mint));
+                Ok(())
+            },
+        )?;
+        module.register_alias("subscribeRwaMint", "subscribe_rwa_mint")?;
+        module.register_alias("unsubscribeRwaMint", "unsubscribe_rwa_mint")?;
+
+        // subscribe_identity
+        module.register_subscription(
+            "subscribe_identity",
+            "identityNotification",
+            "unsubscribe_identity",
+            |rpc_params, mut sink, rpc_context| {
+                let payload = match rpc_params.parse::<SubscribeIdentity>() {
+                    Ok(payload) => payload,
+                    Err(e) => {
+                        let _ = sink.reject(e);
+                        return Ok(());
+                    }
+                };
+                let owner = match validate_pubkey(payload.owner) {
+                    Ok(owner) => owner.to_string(),
+                    Err(e) => {
+                        let _ = sink.reject(CallError::from_std_error(e));
+                        return Ok(());
+                    }
+                };
+                sink.accept()?;
+                tokio::spawn(stream_identity_updates(rpc_context, sink, owner));
+                Ok(())
+            },
+        )?;
+        module.register_alias("subscribeIdentity", "subscribe_identity")?;
+        module.register_alias("unsubscribeIdentity", "unsubscribe_identity")?;
+
         module.register_async_method("schema", |_, rpc_context| async move {
             Ok(rpc_context.schema())
         })?;
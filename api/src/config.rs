@@ -10,6 +10,8 @@ pub struct Config {
     pub server_port: u16,
     pub env: Option<String>,
     pub db_max_conn: Option<u32>,
+    /// Postgres channel the indexer sends account change notifications on.
+    pub database_listener_channel: Option<String>,
 }
 
 pub fn load_config() -> Config {
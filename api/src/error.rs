@@ -1,4 +1,5 @@
 use log::{debug, error, info};
+use rwa_types::rapi::PaginationValidationError;
 use sea_orm::DbErr;
 
 use {jsonrpsee::core::Error as RpcError, jsonrpsee::types::error::CallError, thiserror::Error};
@@ -31,6 +32,20 @@ pub enum RwaApiError {
     PaginationSortingValidationError,
 }
 
+impl From<PaginationValidationError> for RwaApiError {
+    fn from(err: PaginationValidationError) -> Self {
+        match err {
+            PaginationValidationError::LimitExceeded => RwaApiError::PaginationExceededError,
+            PaginationValidationError::ConflictingMethods => RwaApiError::PaginationError,
+            PaginationValidationError::InvalidPage
+            | PaginationValidationError::UnsupportedSort(_)
+            | PaginationValidationError::InvalidCursor(_) => {
+                RwaApiError::ValidationError(err.to_string())
+            }
+        }
+    }
+}
+
 impl From<RwaApiError> for RpcError {
     fn from(val: RwaApiError) -> Self {
         match &val {
@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+use jsonrpsee::SubscriptionSink;
+use log::{debug, error, warn};
+use rwa_types::{
+    events::AccountChange,
+    rapi::{FullAccount, RwaAccountWithMint, RwaMintUpdate},
+};
+use sqlx::{postgres::PgListener, PgPool};
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    OnceCell,
+};
+
+use crate::api::{ApiContract, GetRwaAccount, GetRwaAccountsByMint};
+
+// Subscribers that fall this far behind skip the oldest changes.
+const ACCOUNT_CHANGE_BUFFER_SIZE: usize = 1024;
+
+const REGISTRY_TABLES: [&str; 4] = [
+    "asset_controller",
+    "data_registry",
+    "identity_registry",
+    "policy_engine",
+];
+
+/// An account change as broadcast to the subscriptions. The registries of the changed mint are
+/// loaded by the first subscription that needs them and shared with the others.
+pub struct SharedAccountChange {
+    pub change: AccountChange,
+    registries: OnceCell<Option<FullAccount>>,
+}
+
+impl SharedAccountChange {
+    pub fn new(change: AccountChange) -> Self {
+        SharedAccountChange {
+            change,
+            registries: OnceCell::new(),
+        }
+    }
+}
+
+pub fn account_change_channel() -> broadcast::Sender<Arc<SharedAccountChange>> {
+    broadcast::channel(ACCOUNT_CHANGE_BUFFER_SIZE).0
+}
+
+/// Forwards the indexer's account change notifications on `channel` to `sender`.
+pub fn spawn_account_change_listener(
+    pool: PgPool,
+    channel: String,
+    sender: broadcast::Sender<Arc<SharedAccountChange>>,
+) {
+    tokio::spawn(async move {
+        let mut listener = match PgListener::connect_with(&pool).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                error!("Failed to connect account change listener: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = listener.listen(&channel).await {
+            error!("Failed to listen on {}: {}", channel, e);
+            return;
+        }
+        loop {
+            // recv reconnects on its own, errors are only logged.
+            match listener.recv().await {
+                Ok(notification) => {
+                    match serde_json::from_str::<AccountChange>(notification.payload()) {
+                        Ok(change) => {
+                            // No receivers just means nobody is subscribed.
+                            let _ = sender.send(Arc::new(SharedAccountChange::new(change)));
+                        }
+                        Err(e) => warn!("Invalid account change notification: {}", e),
+                    }
+                }
+                Err(e) => error!("Account change listener error: {}", e),
+            }
+        }
+    });
+}
+
+/// Waits for the next change accepted by `filter`, or None once the subscription is over.
+async fn next_change(
+    sink: &SubscriptionSink,
+    changes: &mut broadcast::Receiver<Arc<SharedAccountChange>>,
+    filter: impl Fn(&AccountChange) -> bool,
+) -> Option<Arc<SharedAccountChange>> {
+    loop {
+        tokio::select! {
+            _ = sink.closed() => return None,
+            change = changes.recv() => match change {
+                Ok(shared) if filter(&shared.change) => return Some(shared),
+                Ok(_) => {}
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("Subscription lagged, skipped {} account changes", skipped);
+                }
+                Err(RecvError::Closed) => return None,
+            },
+        }
+    }
+}
+
+async fn lookup_account(
+    api: &Arc<Box<dyn ApiContract>>,
+    address: String,
+) -> Option<RwaAccountWithMint> {
+    api.get_rwa_account(GetRwaAccount { address })
+        .await
+        .map_err(|e| debug!("Failed to load changed account: {}", e))
+        .ok()
+}
+
+/// Pushes an update for every change the indexer commits for `mint`.
+pub async fn stream_mint_updates(
+    api: Arc<Box<dyn ApiContract>>,
+    mut sink: SubscriptionSink,
+    mint: String,
+) {
+    let mut changes = api.account_changes();
+    while let Some(shared) = next_change(&sink, &mut changes, |change| {
+        change.mint.as_deref() == Some(mint.as_str())
+    })
+    .await
+    {
+        let change = &shared.change;
+        let update = if REGISTRY_TABLES.contains(&change.table.as_str()) {
+            let accounts = shared
+                .registries
+                .get_or_init(|| async {
+                    let request = GetRwaAccountsByMint {
+                        id: mint.clone(),
+                        ..Default::default()
+                    };
+                    api.get_rwa_accounts_by_mint(request)
+                        .await
+                        .map_err(|e| debug!("Failed to load accounts of {}: {}", mint, e))
+                        .ok()
+                })
+                .await;
+            match accounts {
+                Some(accounts) => RwaMintUpdate::Registries {
+                    slot: change.slot,
+                    accounts: accounts.clone(),
+                },
+                None => continue,
+            }
+        } else {
+            match lookup_account(&api, change.pubkey.clone()).await {
+                Some(account) => RwaMintUpdate::Account {
+                    slot: change.slot,
+                    account,
+                },
+                None => continue,
+            }
+        };
+        if !matches!(sink.send(&update), Ok(true)) {
+            break;
+        }
+    }
+}
+
+/// Pushes the identity account whenever one of `owner`'s identity accounts changes.
+pub async fn stream_identity_updates(
+    api: Arc<Box<dyn ApiContract>>,
+    mut sink: SubscriptionSink,
+    owner: String,
+) {
+    let mut changes = api.account_changes();
+    while let Some(shared) = next_change(&sink, &mut changes, |change| {
+        change.table == "identity_account" && change.owner.as_deref() == Some(owner.as_str())
+    })
+    .await
+    {
+        let account = match lookup_account(&api, shared.change.pubkey.clone()).await {
+            Some(account) => account,
+            None => continue,
+        };
+        if !matches!(sink.send(&account), Ok(true)) {
+            break;
+        }
+    }
+}
@@ -1,6 +1,7 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, str::FromStr};
 
 use git2::Repository;
+use serde::{de::Error, Deserialize, Deserializer};
 use solana_program::{pubkey, pubkey::Pubkey};
 
 pub const APPROVAL_ACCOUNT_LEN: u64 = 136;
@@ -29,30 +30,90 @@ pub const IDENTIFIER_REGISTRY_PROGRAM_ID: Pubkey =
 
 pub const POLICY_ENGINE_PROGRAM_ID: Pubkey = pubkey!("po1cPf1eyUJJPqULw4so3T4JU9pdFn83CDyuLEKFAau");
 
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Addresses of the RWA programs, the mainnet deployments unless configured otherwise, e.g.
+/// `{asset_controller="<PUBKEY>", policy_engine="<PUBKEY>"}` for a devnet build.
+#[derive(Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(default)]
+pub struct ProgramIds {
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub asset_controller: Pubkey,
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub data_registry: Pubkey,
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub identity_registry: Pubkey,
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub policy_engine: Pubkey,
+}
+
+impl Default for ProgramIds {
+    fn default() -> Self {
+        Self {
+            asset_controller: ASSET_CONTROLLER_PROGRAM_ID,
+            data_registry: DATA_REGISTRY_PROGRAM_ID,
+            identity_registry: IDENTIFIER_REGISTRY_PROGRAM_ID,
+            policy_engine: POLICY_ENGINE_PROGRAM_ID,
+        }
+    }
+}
+
+impl ProgramIds {
+    pub fn all(&self) -> [Pubkey; 4] {
+        [
+            self.asset_controller,
+            self.data_registry,
+            self.identity_registry,
+            self.policy_engine,
+        ]
+    }
+
+    /// The program named like its field, e.g. `policy_engine`.
+    pub fn by_name(&self, name: &str) -> Option<Pubkey> {
+        match name {
+            "asset_controller" => Some(self.asset_controller),
+            "data_registry" => Some(self.data_registry),
+            "identity_registry" => Some(self.identity_registry),
+            "policy_engine" => Some(self.policy_engine),
+            _ => None,
+        }
+    }
+
+    pub fn find_asset_controller_pda(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[mint.as_ref()], &self.asset_controller)
+    }
+
+    pub fn find_data_registry_pda(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[mint.as_ref()], &self.data_registry)
+    }
+
+    pub fn find_identifier_registry_pda(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[mint.as_ref()], &self.identity_registry)
+    }
+
+    pub fn find_policy_engine_pda(&self, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[mint.as_ref()], &self.policy_engine)
+    }
+}
+
+fn deserialize_pubkey<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+    let key = String::deserialize(deserializer)?;
+    Pubkey::from_str(&key).map_err(|e| D::Error::custom(format!("invalid pubkey {key}: {e}")))
+}
+
+// PDAs of the mainnet deployments.
 pub fn find_asset_controller_pda(mint: &Pubkey) -> (solana_program::pubkey::Pubkey, u8) {
-    solana_program::pubkey::Pubkey::find_program_address(
-        &[mint.as_ref()],
-        &ASSET_CONTROLLER_PROGRAM_ID,
-    )
+    ProgramIds::default().find_asset_controller_pda(mint)
 }
 
 pub fn find_data_registry_pda(data_type: &Pubkey) -> (solana_program::pubkey::Pubkey, u8) {
-    solana_program::pubkey::Pubkey::find_program_address(
-        &[data_type.as_ref()],
-        &DATA_REGISTRY_PROGRAM_ID,
-    )
+    ProgramIds::default().find_data_registry_pda(data_type)
 }
 
 pub fn find_identifier_registry_pda(identifier: &Pubkey) -> (solana_program::pubkey::Pubkey, u8) {
-    solana_program::pubkey::Pubkey::find_program_address(
-        &[identifier.as_ref()],
-        &IDENTIFIER_REGISTRY_PROGRAM_ID,
-    )
+    ProgramIds::default().find_identifier_registry_pda(identifier)
 }
 
 pub fn find_policy_engine_pda(policy: &Pubkey) -> (solana_program::pubkey::Pubkey, u8) {
-    solana_program::pubkey::Pubkey::find_program_address(
-        &[policy.as_ref()],
-        &POLICY_ENGINE_PROGRAM_ID,
-    )
+    ProgramIds::default().find_policy_engine_pda(policy)
 }
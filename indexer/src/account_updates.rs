@@ -1,16 +1,19 @@
 use std::sync::Arc;
 
 use crate::{
+    change_stream::ChangeEventPublisher,
     config::{IndexerConfig, PodType},
+    dead_letter::store_failed_account_update,
     metric,
-    metrics::capture_result,
-    program_handlers::ProgramHandler,
+    metrics::{capture_result, CapturedMessage, MessageOutcome},
+    program_handlers::{ProgramHandler, ProgramRegistry},
 };
 use cadence_macros::{is_global_default_set, statsd_count, statsd_time};
 use chrono::Utc;
 use log::{debug, error};
 use plerkle_messenger::{ConsumptionType, Messenger, RecvData, ACCOUNT_STREAM, ACC_BACKFILL};
-use plerkle_serialization::root_as_account_info;
+use plerkle_serialization::{root_as_account_info, AccountInfo};
+use sea_orm::{DatabaseConnection, SqlxPostgresConnector};
 use sqlx::{Pool, Postgres};
 use tokio::{
     sync::mpsc::UnboundedSender,
@@ -21,6 +24,7 @@ use tokio::{
 pub fn account_worker<T: Messenger>(
     pool: Pool<Postgres>,
     config: IndexerConfig,
+    programs: Arc<ProgramRegistry>,
     ack_channel: UnboundedSender<(&'static str, String)>,
     consumption_type: ConsumptionType,
     pod_type: &PodType,
@@ -34,7 +38,9 @@ pub fn account_worker<T: Messenger>(
         let config_clone = config.clone();
         let source = T::new(config_clone.get_messenger_client_config()).await;
         if let Ok(mut msg) = source {
-            let manager = Arc::new(ProgramHandler::new(pool, config));
+            let db = SqlxPostgresConnector::from_sqlx_postgres_pool(pool.clone());
+            let manager = Arc::new(ProgramHandler::new(pool, config).with_programs(programs));
+            let change_events = ChangeEventPublisher::new(&config_clone, &mut msg).await;
             loop {
                 let e: Result<Vec<RecvData>, plerkle_messenger::MessengerError> =
                     msg.recv(stream_key, consumption_type.clone()).await;
@@ -45,6 +51,7 @@ pub fn account_worker<T: Messenger>(
                         for item in data {
                             tasks.spawn(handle_account(
                                 Arc::clone(&manager),
+                                db.clone(),
                                 item,
                                 stream_key,
                                 config_clone.clone(),
@@ -72,6 +79,12 @@ pub fn account_worker<T: Messenger>(
                         }
                     }
                 }
+                // Publishes the events this batch queued, and retries ones that failed before.
+                if let Some(publisher) = &change_events {
+                    if let Err(e) = publisher.publish(&db, &mut msg).await {
+                        error!("Failed to publish change events: {}", e);
+                    }
+                }
             }
         }
     })
@@ -79,12 +92,12 @@ pub fn account_worker<T: Messenger>(
 
 async fn handle_account(
     manager: Arc<ProgramHandler>,
+    db: DatabaseConnection,
     item: RecvData,
     stream_key: &str,
     config: IndexerConfig,
 ) -> Option<String> {
     let id = item.id;
-    let mut ret_id = None;
     let data = item.data;
     if item.tries > 0 {
         metric! {
@@ -92,39 +105,77 @@ async fn handle_account(
         }
     }
     // Get root of account info flatbuffers object.
-    if let Ok(account_update) = root_as_account_info(&data) {
-        let str_program_id =
-            bs58::encode(account_update.owner().unwrap().0.as_slice()).into_string();
-
-        metric! {
-            statsd_count!("indexer.seen", 1, "owner" => &str_program_id, "stream" => stream_key);
-            let seen_at = Utc::now();
-            statsd_time!(
-                "indexer.bus_ingest_time",
-                std::cmp::max(seen_at.timestamp_millis() - account_update.seen_at(), 0) as u64,
-                "owner" => &str_program_id,
-                "stream" => stream_key
-            );
-        }
-        let mut account = None;
-        if let Some(pubkey) = account_update.pubkey() {
-            account = Some(bs58::encode(pubkey.0.as_slice()).into_string());
+    let account_update = match root_as_account_info(&data) {
+        Ok(account_update) => account_update,
+        Err(e) => {
+            error!("Error reading account update: {}", e);
+            return dead_letter(&db, id, stream_key, &data, None, &e.to_string(), item.tries).await;
         }
-        let begin_processing = Instant::now();
-        let res = manager.handle_account_update(account_update, &config).await;
-        let should_ack = capture_result(
-            id.clone(),
-            stream_key,
-            ("owner", &str_program_id),
-            item.tries,
-            res,
-            begin_processing,
-            None,
-            account,
+    };
+
+    let str_program_id = bs58::encode(account_update.owner().unwrap().0.as_slice()).into_string();
+
+    metric! {
+        statsd_count!("indexer.seen", 1, "owner" => &str_program_id, "stream" => stream_key);
+        let seen_at = Utc::now();
+        statsd_time!(
+            "indexer.bus_ingest_time",
+            std::cmp::max(seen_at.timestamp_millis() - account_update.seen_at(), 0) as u64,
+            "owner" => &str_program_id,
+            "stream" => stream_key
         );
-        if should_ack {
-            ret_id = Some(id);
+    }
+    let mut account = None;
+    if let Some(pubkey) = account_update.pubkey() {
+        account = Some(bs58::encode(pubkey.0.as_slice()).into_string());
+    }
+    let begin_processing = Instant::now();
+    let res = manager.handle_account_update(account_update, &config).await;
+    let outcome = capture_result(
+        CapturedMessage {
+            stream: stream_key,
+            label: ("owner", &str_program_id),
+            tries: item.tries,
+            max_retries: config.get_max_retries(),
+            proc: begin_processing,
+            txn_sig: None,
+            account,
+        },
+        res,
+    );
+    match outcome {
+        MessageOutcome::Ack => Some(id),
+        MessageOutcome::Retry => None,
+        MessageOutcome::DeadLetter(error) => {
+            dead_letter(
+                &db,
+                id,
+                stream_key,
+                &data,
+                Some(&account_update),
+                &error,
+                item.tries,
+            )
+            .await
+        }
+    }
+}
+
+// Only acks the message once it is safely stored, otherwise it is left for redelivery.
+async fn dead_letter(
+    db: &DatabaseConnection,
+    id: String,
+    stream_key: &str,
+    payload: &[u8],
+    account_update: Option<&AccountInfo<'_>>,
+    error: &str,
+    tries: usize,
+) -> Option<String> {
+    match store_failed_account_update(db, stream_key, payload, account_update, error, tries).await {
+        Ok(()) => Some(id),
+        Err(e) => {
+            error!("Failed to dead-letter message {}: {}", id, e);
+            None
         }
     }
-    ret_id
 }
@@ -0,0 +1,135 @@
+use crate::{config::IndexerConfig, error::IndexerError, metric};
+use acc_forwarder::{fetch_and_send_mint, fetch_asset_mints, AccountStream};
+use cadence_macros::{is_global_default_set, statsd_count};
+use common::utils::ProgramIds;
+use log::{error, info};
+use plerkle_messenger::ACC_BACKFILL;
+use rwa_types::dao::backfill_progress;
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue::Set, DatabaseConnection, EntityTrait, SqlxPostgresConnector,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use sqlx::{Pool, Postgres};
+use tokio::task::JoinHandle;
+
+const BACKFILL_BUFFER_SIZE: usize = 10_000_000;
+
+/// Loads every RWA asset over RPC and pushes its accounts onto `ACC_BACKFILL`, where the
+/// backfill pod's account workers pick them up. Assets are found through their asset controllers
+/// and sent the way `acc_forwarder mint` sends them. Progress is checkpointed after every asset
+/// in the asset controller program's `backfill_progress` row, so a restart resumes after the
+/// last completed asset; delete the row to backfill again.
+pub fn backfiller(pool: Pool<Postgres>, config: IndexerConfig) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let db = SqlxPostgresConnector::from_sqlx_postgres_pool(pool);
+        let client =
+            RpcClient::new_with_commitment(config.get_rpc_url(), config.get_rpc_commitment());
+        let messenger = match AccountStream::connect(
+            config.get_messenger_client_config(),
+            ACC_BACKFILL,
+            BACKFILL_BUFFER_SIZE,
+        )
+        .await
+        {
+            Ok(messenger) => messenger,
+            Err(e) => {
+                error!("Backfiller failed to connect to messenger: {}", e);
+                return;
+            }
+        };
+
+        match backfill(&db, &client, &messenger, &config.get_program_ids()).await {
+            Ok(()) => info!("Backfill finished"),
+            Err(e) => {
+                error!("Failed to backfill: {}", e);
+                metric! {
+                    statsd_count!("indexer.backfill.error", 1);
+                }
+            }
+        }
+    })
+}
+
+async fn backfill(
+    db: &DatabaseConnection,
+    client: &RpcClient,
+    messenger: &AccountStream,
+    program_ids: &ProgramIds,
+) -> Result<(), IndexerError> {
+    let program_id = program_ids.asset_controller.to_bytes().to_vec();
+    let progress = backfill_progress::Entity::find_by_id(program_id.clone())
+        .one(db)
+        .await?;
+    if progress.as_ref().map_or(false, |p| p.completed) {
+        info!("Assets already backfilled, skipping");
+        return Ok(());
+    }
+    let last_mint = progress.as_ref().and_then(|p| p.last_pubkey.clone());
+    let mut accounts_sent = progress.map_or(0, |p| p.accounts_sent);
+
+    let slot = client
+        .get_slot()
+        .await
+        .map_err(|e| IndexerError::BatchInitNetworkingError(e.to_string()))?;
+    let mut mints = fetch_asset_mints(program_ids, client)
+        .await
+        .map_err(|e| IndexerError::BatchInitNetworkingError(e.to_string()))?;
+    mints.sort();
+    if let Some(last_mint) = last_mint {
+        mints.retain(|mint| mint.as_ref() > last_mint.as_slice());
+    }
+    info!("Backfilling {} assets at slot {}", mints.len(), slot);
+
+    for mint in mints {
+        let sent = fetch_and_send_mint(mint, program_ids, client, messenger)
+            .await
+            .map_err(|e| {
+                IndexerError::BatchInitNetworkingError(format!("Asset {}: {}", mint, e))
+            })?;
+        accounts_sent += sent as i64;
+        save_progress(db, &program_id, Some(&mint), accounts_sent, slot, false).await?;
+        metric! {
+            statsd_count!("indexer.backfill.accounts", sent as i64);
+        }
+    }
+    save_progress(db, &program_id, None, accounts_sent, slot, true).await
+}
+
+async fn save_progress(
+    db: &DatabaseConnection,
+    program_id: &[u8],
+    last_pubkey: Option<&Pubkey>,
+    accounts_sent: i64,
+    slot: u64,
+    completed: bool,
+) -> Result<(), IndexerError> {
+    let mut active_model = backfill_progress::ActiveModel {
+        program_id: Set(program_id.to_vec()),
+        accounts_sent: Set(accounts_sent),
+        slot: Set(Some(slot as i64)),
+        completed: Set(completed),
+        last_updated_at: Set(chrono::Utc::now().naive_utc()),
+        ..Default::default()
+    };
+    let mut update_columns = vec![
+        backfill_progress::Column::AccountsSent,
+        backfill_progress::Column::Slot,
+        backfill_progress::Column::Completed,
+        backfill_progress::Column::LastUpdatedAt,
+    ];
+    if let Some(last_pubkey) = last_pubkey {
+        active_model.last_pubkey = Set(Some(last_pubkey.to_bytes().to_vec()));
+        update_columns.push(backfill_progress::Column::LastPubkey);
+    }
+
+    backfill_progress::Entity::insert(active_model)
+        .on_conflict(
+            OnConflict::columns([backfill_progress::Column::ProgramId])
+                .update_columns(update_columns)
+                .to_owned(),
+        )
+        .exec(db)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use cadence_macros::{is_global_default_set, statsd_count};
+use log::error;
+use plerkle_messenger::Messenger;
+use rwa_types::events::ChangeEvent;
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbBackend, FromQueryResult, Statement, TransactionTrait,
+};
+
+use crate::{config::IndexerConfig, error::IndexerError, metric};
+
+const CHANGE_EVENT_BUFFER_SIZE: usize = 1_000_000;
+const PUBLISH_BATCH_SIZE: i64 = 1_000;
+
+/// Queues change events in `change_event_outbox`, in the transaction of the write that produced
+/// them, for a [`ChangeEventPublisher`] to publish. Queuing is a no-op when no change event
+/// stream is configured.
+#[derive(Clone, Copy, Default)]
+pub struct ChangeEventOutbox(bool);
+
+impl ChangeEventOutbox {
+    pub fn new(config: &IndexerConfig) -> Self {
+        ChangeEventOutbox(config.change_event_stream.is_some())
+    }
+
+    pub async fn queue<C: ConnectionTrait>(
+        &self,
+        conn: &C,
+        events: impl IntoIterator<Item = ChangeEvent>,
+    ) -> Result<(), IndexerError> {
+        if !self.0 {
+            return Ok(());
+        }
+        for event in events {
+            conn.execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "INSERT INTO change_event_outbox (event_type, payload) VALUES ($1, $2)",
+                vec![
+                    event.event_type().as_str().into(),
+                    serde_json::to_value(&event)?.into(),
+                ],
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(FromQueryResult)]
+struct QueuedEvent {
+    id: i64,
+    event_type: String,
+    payload: serde_json::Value,
+}
+
+/// Publishes the queued change events as JSON to the configured `change_event_stream`, through
+/// the messenger connection of the worker that owns it.
+pub struct ChangeEventPublisher {
+    stream: &'static str,
+}
+
+impl ChangeEventPublisher {
+    /// Registers the change event stream on `messenger`. None when no stream is configured.
+    pub async fn new<T: Messenger>(config: &IndexerConfig, messenger: &mut T) -> Option<Self> {
+        // The messenger only takes static stream names, leaked once per worker.
+        let stream = &*Box::leak(config.change_event_stream.clone()?.into_boxed_str());
+        if let Err(e) = messenger.add_stream(stream).await {
+            error!("Failed to add change event stream {}: {}", stream, e);
+            return None;
+        }
+        messenger
+            .set_buffer_size(stream, CHANGE_EVENT_BUFFER_SIZE)
+            .await;
+        Some(ChangeEventPublisher { stream })
+    }
+
+    /// Publishes a batch of queued events in the order they were queued and removes them from
+    /// the outbox. Rows are locked with `SKIP LOCKED` so workers sharing the outbox don't publish
+    /// an event twice. A failed send is recorded on its row and ends the batch, leaving it and
+    /// the events after it for the next call. An event is published again if the removal doesn't
+    /// commit, consumers see every event at least once.
+    pub async fn publish<T: Messenger>(
+        &self,
+        db: &DatabaseConnection,
+        messenger: &mut T,
+    ) -> Result<usize, IndexerError> {
+        let txn = db.begin().await?;
+        let events = QueuedEvent::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT id, event_type, payload FROM change_event_outbox \
+             ORDER BY id \
+             LIMIT $1 \
+             FOR UPDATE SKIP LOCKED",
+            vec![PUBLISH_BATCH_SIZE.into()],
+        ))
+        .all(&txn)
+        .await?;
+
+        let mut published = 0;
+        for event in events {
+            let event_type = event.event_type.as_str();
+            if let Err(e) = messenger
+                .send(self.stream, event.payload.to_string().as_bytes())
+                .await
+            {
+                error!("Failed to publish change event to {}: {}", self.stream, e);
+                metric! {
+                    statsd_count!("indexer.change_event.error", 1, "event_type" => event_type);
+                }
+                txn.execute(Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    "UPDATE change_event_outbox SET attempts = attempts + 1, last_error = $2 \
+                     WHERE id = $1",
+                    vec![event.id.into(), e.to_string().into()],
+                ))
+                .await?;
+                break;
+            }
+            txn.execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "DELETE FROM change_event_outbox WHERE id = $1",
+                vec![event.id.into()],
+            ))
+            .await?;
+            metric! {
+                statsd_count!("indexer.change_event", 1, "event_type" => event_type);
+            }
+            published += 1;
+        }
+        txn.commit().await?;
+        Ok(published)
+    }
+}
@@ -1,11 +1,13 @@
 use std::fmt::{Display, Formatter};
 
-use common::config::load_config_using_env_prefix;
+use common::{config::load_config_using_env_prefix, utils::ProgramIds};
 use figment::value::Value;
 use plerkle_messenger::MessengerConfig;
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use rwa_types::events::DEFAULT_ACCOUNT_CHANGE_CHANNEL;
 use serde::Deserialize;
-use std::env;
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::{collections::HashMap, env, str::FromStr};
 use tracing_subscriber::fmt;
 
 use crate::error::IndexerError;
@@ -20,8 +22,16 @@ pub struct IndexerConfig {
     pub metrics_host: Option<String>,
     pub max_postgres_connections: Option<u32>,
     pub account_stream_worker_count: Option<u32>,
+    pub transaction_stream_worker_count: Option<u32>,
     pub code_version: Option<String>,
     pub pod_type: Option<PodType>,
+    pub max_retries: Option<usize>,
+    pub webhook_worker_count: Option<u32>,
+    pub webhook_max_attempts: Option<u32>,
+    pub change_event_stream: Option<String>,
+    pub program_ids: Option<ProgramIds>,
+    /// Anchor IDL files by program name, e.g. `policy_engine`.
+    pub idl_paths: Option<HashMap<String, String>>,
 }
 
 impl IndexerConfig {
@@ -35,6 +45,13 @@ impl IndexerConfig {
             .unwrap()
     }
 
+    pub fn get_listener_channel(&self) -> String {
+        self.database_config
+            .get(DATABASE_LISTENER_CHANNEL_KEY)
+            .and_then(|c| c.clone().into_string())
+            .unwrap_or_else(|| DEFAULT_ACCOUNT_CHANGE_CHANNEL.to_string())
+    }
+
     pub fn get_rpc_url(&self) -> String {
         self.rpc_config
             .get(RPC_URL_KEY)
@@ -45,6 +62,14 @@ impl IndexerConfig {
             .unwrap()
     }
 
+    pub fn get_rpc_commitment(&self) -> CommitmentConfig {
+        self.rpc_config
+            .get(RPC_COMMITMENT_KEY)
+            .and_then(|c| c.clone().into_string())
+            .and_then(|c| CommitmentConfig::from_str(&c).ok())
+            .unwrap_or_else(CommitmentConfig::finalized)
+    }
+
     pub fn get_messenger_client_config(&self) -> MessengerConfig {
         let mut mc = self.messenger_config.clone();
         mc.connection_config
@@ -55,6 +80,27 @@ impl IndexerConfig {
     pub fn get_account_stream_worker_count(&self) -> u32 {
         self.account_stream_worker_count.unwrap_or(2)
     }
+
+    pub fn get_transaction_stream_worker_count(&self) -> u32 {
+        self.transaction_stream_worker_count.unwrap_or(2)
+    }
+
+    pub fn get_max_retries(&self) -> usize {
+        self.max_retries.unwrap_or(DEFAULT_MAX_RETRIES)
+    }
+
+    pub fn get_webhook_worker_count(&self) -> u32 {
+        self.webhook_worker_count.unwrap_or(1)
+    }
+
+    pub fn get_webhook_max_attempts(&self) -> u32 {
+        self.webhook_max_attempts
+            .unwrap_or(DEFAULT_WEBHOOK_MAX_ATTEMPTS)
+    }
+
+    pub fn get_program_ids(&self) -> ProgramIds {
+        self.program_ids.unwrap_or_default()
+    }
 }
 
 // Types and constants used for Figment configuration items.
@@ -68,6 +114,8 @@ pub type RpcConfig = figment::value::Dict;
 pub const RPC_URL_KEY: &str = "url";
 pub const RPC_COMMITMENT_KEY: &str = "commitment";
 pub const CODE_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const DEFAULT_MAX_RETRIES: usize = 5;
+pub const DEFAULT_WEBHOOK_MAX_ATTEMPTS: u32 = 8;
 
 #[derive(Deserialize, PartialEq, Eq, Debug, Clone)]
 pub enum PodType {
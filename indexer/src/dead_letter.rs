@@ -0,0 +1,63 @@
+use crate::error::IndexerError;
+use plerkle_serialization::{AccountInfo, TransactionInfo};
+use rwa_types::dao::{failed_account_update, failed_transaction_update};
+use sea_orm::{ActiveValue::Set, DatabaseConnection, EntityTrait};
+
+/// Keeps a message the indexer gave up on in `failed_account_update`, so it can be replayed
+/// with `acc_forwarder replay-failed` once a fix is deployed.
+pub async fn store_failed_account_update(
+    db: &DatabaseConnection,
+    stream: &str,
+    payload: &[u8],
+    account_update: Option<&AccountInfo<'_>>,
+    error: &str,
+    tries: usize,
+) -> Result<(), IndexerError> {
+    let active_model = failed_account_update::ActiveModel {
+        pubkey: Set(account_update
+            .and_then(|account| account.pubkey())
+            .map(|pubkey| pubkey.0.to_vec())),
+        owner: Set(account_update
+            .and_then(|account| account.owner())
+            .map(|owner| owner.0.to_vec())),
+        slot: Set(account_update.map(|account| account.slot() as i64)),
+        stream: Set(stream.to_string()),
+        payload: Set(payload.to_vec()),
+        error: Set(error.to_string()),
+        tries: Set(tries as i32),
+        ..Default::default()
+    };
+
+    failed_account_update::Entity::insert(active_model)
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// Keeps a transaction the indexer gave up on in `failed_transaction_update`, so it can be
+/// replayed with `acc_forwarder replay-failed-transactions` once a fix is deployed.
+pub async fn store_failed_transaction_update(
+    db: &DatabaseConnection,
+    stream: &str,
+    payload: &[u8],
+    transaction: Option<&TransactionInfo<'_>>,
+    error: &str,
+    tries: usize,
+) -> Result<(), IndexerError> {
+    let active_model = failed_transaction_update::ActiveModel {
+        signature: Set(transaction
+            .and_then(|transaction| transaction.signature())
+            .and_then(|signature| bs58::decode(signature).into_vec().ok())),
+        slot: Set(transaction.map(|transaction| transaction.slot() as i64)),
+        stream: Set(stream.to_string()),
+        payload: Set(payload.to_vec()),
+        error: Set(error.to_string()),
+        tries: Set(tries as i32),
+        ..Default::default()
+    };
+
+    failed_transaction_update::Entity::insert(active_model)
+        .exec(db)
+        .await?;
+    Ok(())
+}
@@ -32,6 +32,10 @@ pub enum IndexerError {
     CacheStorageWriteError(String),
     #[error("AssetIndex Error {0}")]
     AssetIndexError(String),
+    /// The update concerns no indexed asset, e.g. an account of an unknown type or a Token-2022
+    /// account of another mint.
+    #[error("Ignored account: {0}")]
+    IgnoredAccount(String),
 }
 
 impl From<reqwest::Error> for IndexerError {
@@ -0,0 +1,155 @@
+use crate::{
+    config::IndexerConfig, error::IndexerError, metric,
+    program_handlers::RWA_MINTS_REFRESH_INTERVAL,
+};
+use acc_forwarder::{fetch_and_send_token_accounts, AccountStream};
+use cadence_macros::{is_global_default_set, statsd_count};
+use log::{error, info};
+use plerkle_messenger::ACCOUNT_STREAM;
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbBackend, FromQueryResult, SqlxPostgresConnector,
+    Statement,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+use tokio::{task::JoinHandle, time::sleep};
+
+const HOLDER_BACKFILL_BUFFER_SIZE: usize = 10_000_000;
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+// How long a claimed mint may take before other workers pick it up again.
+const CLAIM_TIMEOUT: Duration = Duration::from_secs(600);
+
+// Retries back off exponentially from the base delay, up to the max delay.
+const BASE_RETRY_DELAY_SECS: f64 = 30.0;
+const MAX_RETRY_DELAY_SECS: f64 = 3600.0;
+
+#[derive(FromQueryResult)]
+struct PendingBackfill {
+    mint: Vec<u8>,
+    attempts: i32,
+}
+
+/// Queues a backfill of the token accounts of `mint`. Run it in the transaction that first
+/// indexes the mint's asset controller: Token-2022 accounts of a mint are dropped until its
+/// asset controller is indexed, so holders that didn't change since have to be fetched. The
+/// backfill waits one [`RWA_MINTS_REFRESH_INTERVAL`], by then every pod knows the mint and
+/// stores the accounts it is sent.
+pub async fn queue_holder_backfill<C: ConnectionTrait>(
+    conn: &C,
+    mint: Vec<u8>,
+) -> Result<(), IndexerError> {
+    conn.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "INSERT INTO holder_backfill (mint, next_attempt_at) \
+         VALUES ($1, now() + make_interval(secs => $2)) \
+         ON CONFLICT (mint) DO NOTHING",
+        vec![mint.into(), RWA_MINTS_REFRESH_INTERVAL.as_secs_f64().into()],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Fetches the token accounts of the mints queued in `holder_backfill` over RPC and pushes them
+/// onto `ACCOUNT_STREAM`, the way `acc_forwarder` sends them. Mints are claimed with
+/// `SKIP LOCKED`, so workers across pods share the queue. Failed backfills are retried with
+/// exponential backoff until they complete.
+pub fn holder_backfill_worker(pool: Pool<Postgres>, config: IndexerConfig) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let db = SqlxPostgresConnector::from_sqlx_postgres_pool(pool);
+        let client =
+            RpcClient::new_with_commitment(config.get_rpc_url(), config.get_rpc_commitment());
+        let messenger = match AccountStream::connect(
+            config.get_messenger_client_config(),
+            ACCOUNT_STREAM,
+            HOLDER_BACKFILL_BUFFER_SIZE,
+        )
+        .await
+        {
+            Ok(messenger) => messenger,
+            Err(e) => {
+                error!("Holder backfill failed to connect to messenger: {}", e);
+                return;
+            }
+        };
+        loop {
+            match backfill_next(&db, &client, &messenger).await {
+                Ok(true) => {}
+                Ok(false) => sleep(POLL_INTERVAL).await,
+                Err(e) => {
+                    error!("Failed to backfill holders: {}", e);
+                    sleep(POLL_INTERVAL).await;
+                }
+            }
+        }
+    })
+}
+
+/// Claims the next due mint for [`CLAIM_TIMEOUT`] and backfills it. False when none is due.
+async fn backfill_next(
+    db: &DatabaseConnection,
+    client: &RpcClient,
+    messenger: &AccountStream,
+) -> Result<bool, IndexerError> {
+    let pending = PendingBackfill::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "UPDATE holder_backfill \
+         SET next_attempt_at = now() + make_interval(secs => $1) \
+         WHERE mint = ( \
+             SELECT mint FROM holder_backfill \
+             WHERE completed_at IS NULL AND next_attempt_at <= now() \
+             ORDER BY next_attempt_at \
+             LIMIT 1 \
+             FOR UPDATE SKIP LOCKED \
+         ) \
+         RETURNING mint, attempts",
+        vec![CLAIM_TIMEOUT.as_secs_f64().into()],
+    ))
+    .one(db)
+    .await?;
+    let pending = match pending {
+        Some(pending) => pending,
+        None => return Ok(false),
+    };
+
+    let mint = Pubkey::try_from(pending.mint.as_slice())
+        .map_err(|e| IndexerError::DeserializationError(e.to_string()))?;
+    let result = fetch_and_send_token_accounts(mint, client, messenger).await;
+    let attempts = pending.attempts + 1;
+    let (last_error, status) = match result {
+        Ok(sent) => {
+            info!("Backfilled {} token accounts of {}", sent, mint);
+            metric! {
+                statsd_count!("indexer.holder_backfill.accounts", sent as i64);
+            }
+            (None, "completed")
+        }
+        Err(e) => {
+            error!("Failed to backfill holders of {}: {}", mint, e);
+            (Some(e.to_string()), "failed")
+        }
+    };
+    let retry_delay = (BASE_RETRY_DELAY_SECS * 2f64.powi(attempts - 1)).min(MAX_RETRY_DELAY_SECS);
+
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "UPDATE holder_backfill SET attempts = $2, last_error = $3, \
+             next_attempt_at = now() + make_interval(secs => $4), \
+             completed_at = CASE WHEN $5 THEN now() END \
+         WHERE mint = $1",
+        vec![
+            pending.mint.into(),
+            attempts.into(),
+            last_error.into(),
+            retry_delay.into(),
+            (status == "completed").into(),
+        ],
+    ))
+    .await?;
+
+    metric! {
+        statsd_count!("indexer.holder_backfill.attempt", 1, "status" => status);
+    }
+    Ok(true)
+}
@@ -1,8 +1,15 @@
 pub mod account_updates;
 pub mod ack;
+pub mod backfiller;
+pub mod change_stream;
 pub mod config;
 pub mod database;
+pub mod dead_letter;
 pub mod error;
+pub mod holder_backfill;
 pub mod metrics;
 pub mod program_handlers;
+pub mod runner;
 pub mod stream;
+pub mod transaction_updates;
+pub mod webhooks;
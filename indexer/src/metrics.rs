@@ -2,7 +2,7 @@ use std::net::UdpSocket;
 
 use cadence::{BufferedUdpMetricSink, QueuingMetricSink, StatsdClient};
 use cadence_macros::{is_global_default_set, set_global_default, statsd_count, statsd_time};
-use log::{error, warn};
+use log::{debug, error, warn};
 use tokio::time::Instant;
 
 use crate::{
@@ -40,21 +40,47 @@ pub fn setup_metrics(config: &IndexerConfig) {
     }
 }
 
-// Returns a boolean indicating whether the redis message should be ACK'd.
-// If the message is not ACK'd, it will be retried as long as it is under the retry limit.
+/// What the stream consumer should do with a message once it has been handled.
+#[derive(Debug, PartialEq, Eq)]
+pub enum MessageOutcome {
+    Ack,
+    Retry,
+    /// The message can't be processed; ack it and keep the payload for replay.
+    DeadLetter(String),
+}
+
+/// The stream message a handling result is captured for.
+pub struct CapturedMessage<'a> {
+    pub stream: &'a str,
+    /// Metric tag identifying the kind of message, e.g. `("owner", program_id)`.
+    pub label: (&'a str, &'a str),
+    pub tries: usize,
+    pub max_retries: usize,
+    /// When handling started.
+    pub proc: Instant,
+    pub txn_sig: Option<&'a str>,
+    pub account: Option<String>,
+}
+
+// Returns whether the redis message should be ACK'd, retried or dead-lettered.
+// If the message is not ACK'd, it will be retried until it reaches `max_retries`.
 pub fn capture_result(
-    _id: String,
-    stream: &str,
-    label: (&str, &str),
-    tries: usize,
+    message: CapturedMessage<'_>,
     res: Result<(), IndexerError>,
-    proc: Instant,
-    txn_sig: Option<&str>,
-    account: Option<String>,
-) -> bool {
+) -> MessageOutcome {
+    let CapturedMessage {
+        stream,
+        label,
+        tries,
+        max_retries,
+        proc,
+        txn_sig,
+        account,
+    } = message;
     #[allow(unused_assignments)]
     let mut should_ack = false;
     let mut dropped_message = false;
+    let mut failure = None;
     match res {
         Ok(_) => {
             metric! {
@@ -77,6 +103,15 @@ pub fn capture_result(
             }
             should_ack = true;
         }
+        Err(IndexerError::IgnoredAccount(e)) => {
+            metric! {
+                statsd_count!("indexer.ignored_account", 1, label.0 => &label.1, "stream" => stream);
+            }
+            if let Some(account) = account {
+                debug!("Ignoring account {}: {}", account, e);
+            }
+            should_ack = true;
+        }
         Err(IndexerError::DeserializationError(e)) => {
             metric! {
                 statsd_count!("indexer.ingest_error", 1, label.0 => &label.1, "stream" => stream, "error" => "de");
@@ -91,6 +126,7 @@ pub fn capture_result(
             // Non-retryable error.
             should_ack = true;
             dropped_message = true;
+            failure = Some(e);
         }
         Err(IndexerError::ParsingError(e)) => {
             metric! {
@@ -106,6 +142,7 @@ pub fn capture_result(
             // Non-retryable error.
             should_ack = true;
             dropped_message = true;
+            failure = Some(e);
         }
         Err(IndexerError::DatabaseError(e)) => {
             metric! {
@@ -117,6 +154,7 @@ pub fn capture_result(
                 warn!("{}", e);
             }
             should_ack = false;
+            failure = Some(e);
         }
         Err(IndexerError::AssetIndexError(e)) => {
             metric! {
@@ -128,6 +166,7 @@ pub fn capture_result(
                 warn!("Error indexing account: {:?}", e);
             }
             should_ack = false;
+            failure = Some(e);
         }
         Err(err) => {
             if let Some(sig) = txn_sig {
@@ -141,13 +180,25 @@ pub fn capture_result(
                 statsd_count!("indexer.ingest_update_error", 1, label.0 => &label.1, "stream" => stream, "error" => "u");
             }
             should_ack = false;
+            failure = Some(err.to_string());
         }
     }
+    // Messages that keep failing are dead-lettered rather than redelivered forever.
+    if !should_ack && tries >= max_retries {
+        warn!("Giving up on message after {} tries", tries);
+        should_ack = true;
+        dropped_message = true;
+    }
     // Triggers Helius alarm.
     if dropped_message {
         metric! {
             statsd_count!("indexer.dropped_message", 1, "stream" => stream);
         }
+        return MessageOutcome::DeadLetter(failure.unwrap_or_default());
+    }
+    if should_ack {
+        MessageOutcome::Ack
+    } else {
+        MessageOutcome::Retry
     }
-    should_ack
 }
@@ -1,32 +1,44 @@
-use crate::{config::IndexerConfig, error::IndexerError};
+use crate::{
+    error::IndexerError,
+    holder_backfill::queue_holder_backfill,
+    program_handlers::{
+        registry::{AccountHandler, HandlerContext},
+        rwa_mints::RwaMints,
+        utils::{close_account, commit_upsert, execute_upsert},
+    },
+};
+use async_trait::async_trait;
+use num_traits::FromPrimitive;
 use plerkle_serialization::AccountInfo;
 use rwa_types::dao::{
     asset_controller, sea_orm_active_enums::AssetControllerVersion, tracker_account,
+    transaction_approval_account,
 };
 use sea_orm::{
-    query::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait, DatabaseConnection,
-    DbBackend, EntityTrait,
+    query::*, sea_query::OnConflict, ActiveValue::Set, DbBackend, EntityName, EntityTrait,
 };
 use serde_json::json;
-use transformer::programs::asset_controller::AssetControllerProgram;
+use transformer::programs::{asset_controller::AssetControllerProgram, ProgramParseResult};
 
-pub async fn handle_asset_controller_program_account<'a, 'b, 'c>(
+pub async fn handle_asset_controller_program_account<'a, 'b>(
     account_update: &'a AccountInfo<'a>,
     parsing_result: &'b AssetControllerProgram,
-    db: &'c DatabaseConnection,
-    _config: &IndexerConfig,
+    context: &HandlerContext<'_>,
+    rwa_mints: &RwaMints,
 ) -> Result<(), IndexerError> {
     let key = *account_update.pubkey().unwrap();
     let key_bytes = key.0.to_vec();
     match &parsing_result {
         AssetControllerProgram::AssetControllerAccount(ac) => {
+            let asset_mint = ac.asset_mint.to_bytes().to_vec();
             let active_model = asset_controller::ActiveModel {
                 id: Set(key_bytes.clone()),
-                asset_mint: Set(ac.asset_mint.to_bytes().to_vec()),
+                asset_mint: Set(asset_mint.clone()),
                 authority: Set(ac.authority.to_bytes().to_vec()),
                 delegate: Set(ac.delegate.to_bytes().to_vec()),
                 version: Set(AssetControllerVersion::from(ac.version)),
                 closed: Set(false),
+                closed_at_slot: Set(None),
                 slot_updated: Set(account_update.slot() as i64),
                 ..Default::default()
             };
@@ -40,6 +52,7 @@ pub async fn handle_asset_controller_program_account<'a, 'b, 'c>(
                             asset_controller::Column::Delegate,
                             asset_controller::Column::Version,
                             asset_controller::Column::Closed,
+                            asset_controller::Column::ClosedAtSlot,
                             asset_controller::Column::SlotUpdated,
                         ])
                         .to_owned(),
@@ -50,12 +63,21 @@ pub async fn handle_asset_controller_program_account<'a, 'b, 'c>(
                 "{} WHERE excluded.slot_updated >= asset_controller.slot_updated OR asset_controller.slot_updated IS NULL",
                 query.sql);
 
-            let txn = db.begin().await?;
-            txn.execute(query)
-                .await
-                .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
-
-            txn.commit().await?;
+            let txn = context.db.begin().await?;
+            let outcome = execute_upsert(&txn, query).await?;
+            if outcome.written() {
+                queue_holder_backfill(&txn, asset_mint.clone()).await?;
+            }
+            commit_upsert(
+                context,
+                txn,
+                asset_controller::Entity,
+                key_bytes,
+                account_update.slot() as i64,
+                outcome,
+            )
+            .await?;
+            rwa_mints.insert(asset_mint);
             Ok(())
         }
         AssetControllerProgram::TrackerAccount(ta) => {
@@ -67,6 +89,8 @@ pub async fn handle_asset_controller_program_account<'a, 'b, 'c>(
                 transfer_timestamps: Set(Some(
                     json!({ "transfer_timestamps": ta.transfer_timestamps }),
                 )),
+                closed: Set(false),
+                closed_at_slot: Set(None),
                 slot_updated: Set(account_update.slot() as i64),
                 ..Default::default()
             };
@@ -79,6 +103,8 @@ pub async fn handle_asset_controller_program_account<'a, 'b, 'c>(
                             tracker_account::Column::Owner,
                             tracker_account::Column::TransferAmounts,
                             tracker_account::Column::TransferTimestamps,
+                            tracker_account::Column::Closed,
+                            tracker_account::Column::ClosedAtSlot,
                             tracker_account::Column::SlotUpdated,
                         ])
                         .to_owned(),
@@ -88,15 +114,107 @@ pub async fn handle_asset_controller_program_account<'a, 'b, 'c>(
             query.sql = format!(
                 "{} WHERE excluded.slot_updated >= tracker_account.slot_updated OR tracker_account.slot_updated IS NULL",
                 query.sql);
-            let txn = db.begin().await?;
-            txn.execute(query)
-                .await
-                .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
-            txn.commit().await?;
+            let txn = context.db.begin().await?;
+            let outcome = execute_upsert(&txn, query).await?;
+            commit_upsert(
+                context,
+                txn,
+                tracker_account::Entity,
+                key_bytes,
+                account_update.slot() as i64,
+                outcome,
+            )
+            .await
+        }
+        AssetControllerProgram::TransactionApprovalAccount(ta) => {
+            let amount =
+                sqlx::types::Decimal::from_u64(ta.amount).expect("Failed to convert to Decimal");
 
-            Ok(())
+            let active_model = transaction_approval_account::ActiveModel {
+                id: Set(key_bytes.clone()),
+                asset_mint: Set(ta.asset_mint.to_bytes().to_vec()),
+                from_token_account: Set(ta.from_token_account.to_bytes().to_vec()),
+                to_token_account: Set(ta.to_token_account.to_bytes().to_vec()),
+                amount: Set(amount),
+                expiry_timestamp: Set(ta.expiry_timestamp),
+                closed: Set(false),
+                closed_at_slot: Set(None),
+                slot_updated: Set(account_update.slot() as i64),
+                ..Default::default()
+            };
+
+            let mut query = transaction_approval_account::Entity::insert(active_model)
+                .on_conflict(
+                    OnConflict::columns([transaction_approval_account::Column::Id])
+                        .update_columns([
+                            transaction_approval_account::Column::AssetMint,
+                            transaction_approval_account::Column::FromTokenAccount,
+                            transaction_approval_account::Column::ToTokenAccount,
+                            transaction_approval_account::Column::Amount,
+                            transaction_approval_account::Column::ExpiryTimestamp,
+                            transaction_approval_account::Column::Closed,
+                            transaction_approval_account::Column::ClosedAtSlot,
+                            transaction_approval_account::Column::SlotUpdated,
+                        ])
+                        .to_owned(),
+                )
+                .build(DbBackend::Postgres);
+
+            query.sql = format!(
+                "{} WHERE excluded.slot_updated >= transaction_approval_account.slot_updated OR transaction_approval_account.slot_updated IS NULL",
+                query.sql);
+            let txn = context.db.begin().await?;
+            let outcome = execute_upsert(&txn, query).await?;
+            commit_upsert(
+                context,
+                txn,
+                transaction_approval_account::Entity,
+                key_bytes,
+                account_update.slot() as i64,
+                outcome,
+            )
+            .await
+        }
+        AssetControllerProgram::EmptyAccount => {
+            close_account(
+                context,
+                &[
+                    asset_controller::Entity.table_name(),
+                    tracker_account::Entity.table_name(),
+                    transaction_approval_account::Entity.table_name(),
+                ],
+                key_bytes.clone(),
+                account_update.slot() as i64,
+            )
+            .await
         }
-        _ => Err(IndexerError::NotImplemented),
     }?;
     Ok(())
 }
+
+pub struct AssetControllerHandler {
+    pub rwa_mints: RwaMints,
+}
+
+#[async_trait]
+impl AccountHandler for AssetControllerHandler {
+    async fn handle_account(
+        &self,
+        account: &AccountInfo<'_>,
+        parsed: ProgramParseResult<'_>,
+        context: &HandlerContext<'_>,
+    ) -> Result<(), IndexerError> {
+        match parsed {
+            ProgramParseResult::AssetControllerProgram(parsing_result) => {
+                handle_asset_controller_program_account(
+                    account,
+                    parsing_result,
+                    context,
+                    &self.rwa_mints,
+                )
+                .await
+            }
+            _ => Err(IndexerError::NotImplemented),
+        }
+    }
+}
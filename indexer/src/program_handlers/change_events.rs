@@ -0,0 +1,241 @@
+use crate::{error::IndexerError, program_handlers::utils::resolve_mint};
+use rwa_types::events::{ChangeEvent, WebhookEvent, WebhookEventType};
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use serde_json::Value;
+
+/// Policy columns whose change loosens or tightens a policy.
+const POLICY_FIELDS: [&str; 5] = [
+    "policy_type",
+    "comparsion_type",
+    "identity_levels",
+    "total_limit",
+    "timeframe",
+];
+
+const AUTHORITY_FIELDS: [&str; 2] = ["authority", "delegate"];
+
+/// The program owning accounts of `table`, as used in `webhook_subscription.program`. None for
+/// tables that never produce an event, which keeps tracker updates from paying for the lookup.
+fn program_of(table: &str) -> Option<&'static str> {
+    match table {
+        "asset_controller" => Some("asset_controller"),
+        "data_registry" | "data_account" => Some("data_registry"),
+        "identity_registry" | "identity_account" => Some("identity_registry"),
+        "policy_engine" | "policy_account" => Some("policy_engine"),
+        _ => None,
+    }
+}
+
+fn is_open(data: &Value) -> bool {
+    !data["closed"].as_bool().unwrap_or_default()
+}
+
+fn changed(previous: Option<&Value>, current: &Value, fields: &[&str]) -> bool {
+    previous.map_or(false, |previous| {
+        fields
+            .iter()
+            .any(|field| previous.get(field) != current.get(field))
+    })
+}
+
+/// The event an account of `table` moving from its `previous` to its `current` snapshot is.
+/// `created` is whether the write inserted the account's row. A missing `previous` snapshot
+/// alone doesn't make an account new, rows indexed before history was recorded have none.
+fn event_type(
+    table: &str,
+    previous: Option<&Value>,
+    current: &Value,
+    created: bool,
+) -> Option<WebhookEventType> {
+    let reopened = previous.map_or(false, |previous| !is_open(previous));
+    let opened = is_open(current) && (created || reopened);
+    let closed = !is_open(current) && previous.map_or(false, is_open);
+    match table {
+        "identity_account" if opened => Some(WebhookEventType::IdentityAccountCreated),
+        "identity_account" if changed(previous, current, &["levels"]) => {
+            Some(WebhookEventType::IdentityLevelsChanged)
+        }
+        "policy_account" if opened => Some(WebhookEventType::PolicyAdded),
+        "policy_account" if closed => Some(WebhookEventType::PolicyRemoved),
+        "policy_account" if changed(previous, current, &POLICY_FIELDS) => {
+            Some(WebhookEventType::PolicyUpdated)
+        }
+        "data_account" if opened => Some(WebhookEventType::DataAccountAdded),
+        "asset_controller" | "data_registry" | "identity_registry" | "policy_engine"
+            if changed(previous, current, &AUTHORITY_FIELDS) =>
+        {
+            Some(WebhookEventType::AuthorityChanged)
+        }
+        _ => None,
+    }
+}
+
+/// Base58 encodes the byte columns of a history snapshot, the only top level arrays in it.
+fn encode_bytes(mut data: Value) -> Value {
+    if let Some(columns) = data.as_object_mut() {
+        for value in columns.values_mut().filter(|value| value.is_array()) {
+            if let Ok(bytes) = serde_json::from_value::<Vec<u8>>(value.clone()) {
+                *value = Value::String(bs58::encode(bytes).into_string());
+            }
+        }
+    }
+    data
+}
+
+fn address(data: &Value, key: &str) -> Option<String> {
+    serde_json::from_value::<Vec<u8>>(data.get(key)?.clone())
+        .ok()
+        .map(|bytes| bs58::encode(bytes).into_string())
+}
+
+fn levels(data: &Value) -> Vec<u8> {
+    serde_json::from_value(data["levels"]["levels"].clone()).unwrap_or_default()
+}
+
+/// Builds the typed event for `event_type` out of the account's snapshots.
+fn change_event(
+    event_type: WebhookEventType,
+    table: &str,
+    pubkey: String,
+    mint: Option<String>,
+    previous: Option<&Value>,
+    current: &Value,
+    slot: i64,
+) -> ChangeEvent {
+    let previous = previous.unwrap_or(current);
+    match event_type {
+        WebhookEventType::IdentityAccountCreated => ChangeEvent::IdentityAccountCreated {
+            identity_account: pubkey,
+            owner: address(current, "owner").unwrap_or_default(),
+            mint,
+            levels: levels(current),
+            slot,
+        },
+        WebhookEventType::IdentityLevelsChanged => ChangeEvent::IdentityLevelsChanged {
+            identity_account: pubkey,
+            owner: address(current, "owner").unwrap_or_default(),
+            mint,
+            old: levels(previous),
+            new: levels(current),
+            slot,
+        },
+        WebhookEventType::PolicyAdded => ChangeEvent::PolicyAdded {
+            policy_account: pubkey,
+            mint,
+            policy: encode_bytes(current.clone()),
+            slot,
+        },
+        WebhookEventType::PolicyUpdated => ChangeEvent::PolicyUpdated {
+            policy_account: pubkey,
+            mint,
+            old: encode_bytes(previous.clone()),
+            new: encode_bytes(current.clone()),
+            slot,
+        },
+        WebhookEventType::PolicyRemoved => ChangeEvent::PolicyRemoved {
+            policy_account: pubkey,
+            mint,
+            slot,
+        },
+        WebhookEventType::AuthorityChanged => ChangeEvent::AuthorityChanged {
+            account: pubkey,
+            account_type: table.to_string(),
+            mint,
+            old_authority: address(previous, "authority").unwrap_or_default(),
+            new_authority: address(current, "authority").unwrap_or_default(),
+            old_delegate: address(previous, "delegate"),
+            new_delegate: address(current, "delegate"),
+            slot,
+        },
+        WebhookEventType::DataAccountAdded => ChangeEvent::DataAccountAdded {
+            data_account: pubkey,
+            data_registry: address(current, "data_registry").unwrap_or_default(),
+            mint,
+            slot,
+        },
+    }
+}
+
+/// Compares the two latest `account_history` snapshots of `id` and queues a `webhook_delivery`
+/// for every active subscription matching the resulting event. Runs in the transaction that
+/// appended the latest snapshot, reading the prior state before it's committed. `created` is
+/// whether that transaction inserted the account's row. The typed event is returned for the
+/// caller to publish once the transaction commits.
+pub async fn enqueue_webhook_events<C: ConnectionTrait>(
+    conn: &C,
+    table: &str,
+    id: &[u8],
+    slot: i64,
+    created: bool,
+) -> Result<Option<ChangeEvent>, IndexerError> {
+    let program = match program_of(table) {
+        Some(program) => program,
+        None => return Ok(None),
+    };
+    let mut snapshots = conn
+        .query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT data FROM account_history WHERE pubkey = $1 AND account_type = $2 \
+             ORDER BY slot DESC, id DESC LIMIT 2",
+            vec![id.to_vec().into(), table.into()],
+        ))
+        .await?
+        .into_iter()
+        .map(|row| row.try_get::<Value>("", "data"))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter();
+    let current = match snapshots.next() {
+        Some(current) => current,
+        None => return Ok(None),
+    };
+    let previous = snapshots.next();
+    let event_type = match event_type(table, previous.as_ref(), &current, created) {
+        Some(event_type) => event_type,
+        None => return Ok(None),
+    };
+
+    let mint = resolve_mint(conn, table, id).await?;
+    let pubkey = bs58::encode(id).into_string();
+    let mint_address = mint.as_ref().map(|mint| bs58::encode(mint).into_string());
+    let change = change_event(
+        event_type,
+        table,
+        pubkey.clone(),
+        mint_address.clone(),
+        previous.as_ref(),
+        &current,
+        slot,
+    );
+    let event = WebhookEvent {
+        event_type,
+        program: program.to_string(),
+        account_type: table.to_string(),
+        pubkey,
+        mint: mint_address,
+        slot,
+        previous: previous.map(encode_bytes),
+        current: encode_bytes(current),
+    };
+
+    let query = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "INSERT INTO webhook_delivery (subscription_id, event_type, pubkey, slot, payload) \
+         SELECT id, $1, $2, $3, $4 FROM webhook_subscription \
+         WHERE active \
+             AND (mint IS NULL OR mint = $5) \
+             AND (program IS NULL OR program = $6) \
+             AND (event_type IS NULL OR event_type = $1)",
+        vec![
+            event_type.as_str().into(),
+            id.to_vec().into(),
+            slot.into(),
+            serde_json::to_value(&event)?.into(),
+            mint.into(),
+            program.into(),
+        ],
+    );
+    conn.execute(query)
+        .await
+        .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
+    Ok(Some(change))
+}
@@ -1,20 +1,25 @@
-use crate::{config::IndexerConfig, error::IndexerError};
+use crate::{
+    error::IndexerError,
+    program_handlers::{
+        registry::{AccountHandler, HandlerContext},
+        utils::{close_account, commit_upsert, execute_upsert},
+    },
+};
+use async_trait::async_trait;
 use plerkle_serialization::AccountInfo;
 use rwa_types::dao::{
     data_account, data_registry,
     sea_orm_active_enums::{DataAccountType, DataRegistryVersion},
 };
 use sea_orm::{
-    query::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait, DatabaseConnection,
-    DbBackend, EntityTrait,
+    query::*, sea_query::OnConflict, ActiveValue::Set, DbBackend, EntityName, EntityTrait,
 };
-use transformer::programs::data_registry::DataRegistryProgram;
+use transformer::programs::{data_registry::DataRegistryProgram, ProgramParseResult};
 
-pub async fn handle_data_registry_program_account<'a, 'b, 'c>(
+pub async fn handle_data_registry_program_account<'a, 'b>(
     account_update: &'a AccountInfo<'a>,
     parsing_result: &'b DataRegistryProgram,
-    db: &'c DatabaseConnection,
-    _config: &IndexerConfig,
+    context: &HandlerContext<'_>,
 ) -> Result<(), IndexerError> {
     let key = *account_update.pubkey().unwrap();
     let key_bytes = key.0.to_vec();
@@ -26,6 +31,7 @@ pub async fn handle_data_registry_program_account<'a, 'b, 'c>(
                 authority: Set(dr.authority.to_bytes().to_vec()),
                 version: Set(DataRegistryVersion::from(dr.version)),
                 closed: Set(false),
+                closed_at_slot: Set(None),
                 slot_updated: Set(account_update.slot() as i64),
                 ..Default::default()
             };
@@ -38,6 +44,7 @@ pub async fn handle_data_registry_program_account<'a, 'b, 'c>(
                             data_registry::Column::Authority,
                             data_registry::Column::Version,
                             data_registry::Column::Closed,
+                            data_registry::Column::ClosedAtSlot,
                             data_registry::Column::SlotUpdated,
                         ])
                         .to_owned(),
@@ -48,13 +55,17 @@ pub async fn handle_data_registry_program_account<'a, 'b, 'c>(
                 "{} WHERE excluded.slot_updated >= data_registry.slot_updated OR data_registry.slot_updated IS NULL",
                 query.sql);
 
-            let txn = db.begin().await?;
-            txn.execute(query)
-                .await
-                .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
-            txn.commit().await?;
-
-            Ok(())
+            let txn = context.db.begin().await?;
+            let outcome = execute_upsert(&txn, query).await?;
+            commit_upsert(
+                context,
+                txn,
+                data_registry::Entity,
+                key_bytes,
+                account_update.slot() as i64,
+                outcome,
+            )
+            .await
         }
         DataRegistryProgram::DataAccount(da) => {
             let active_model = data_account::ActiveModel {
@@ -63,6 +74,8 @@ pub async fn handle_data_registry_program_account<'a, 'b, 'c>(
                 uri: Set(da.uri.clone()),
                 data_registry: Set(da.data_registry.to_bytes().to_vec()),
                 data_type: Set(DataAccountType::from(da._type.clone())),
+                closed: Set(false),
+                closed_at_slot: Set(None),
                 slot_updated: Set(account_update.slot() as i64),
                 ..Default::default()
             };
@@ -75,6 +88,8 @@ pub async fn handle_data_registry_program_account<'a, 'b, 'c>(
                             data_account::Column::Uri,
                             data_account::Column::DataRegistry,
                             data_account::Column::DataType,
+                            data_account::Column::Closed,
+                            data_account::Column::ClosedAtSlot,
                             data_account::Column::SlotUpdated,
                         ])
                         .to_owned(),
@@ -85,14 +100,49 @@ pub async fn handle_data_registry_program_account<'a, 'b, 'c>(
                 "{} WHERE excluded.slot_updated >= data_account.slot_updated OR data_account.slot_updated IS NULL",
                 query.sql);
 
-            let txn = db.begin().await?;
-            txn.execute(query)
-                .await
-                .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
-            txn.commit().await?;
-            Ok(())
+            let txn = context.db.begin().await?;
+            let outcome = execute_upsert(&txn, query).await?;
+            commit_upsert(
+                context,
+                txn,
+                data_account::Entity,
+                key_bytes,
+                account_update.slot() as i64,
+                outcome,
+            )
+            .await
+        }
+        DataRegistryProgram::EmptyAccount => {
+            close_account(
+                context,
+                &[
+                    data_registry::Entity.table_name(),
+                    data_account::Entity.table_name(),
+                ],
+                key_bytes.clone(),
+                account_update.slot() as i64,
+            )
+            .await
         }
-        _ => Err(IndexerError::NotImplemented),
     }?;
     Ok(())
 }
+
+pub struct DataRegistryHandler;
+
+#[async_trait]
+impl AccountHandler for DataRegistryHandler {
+    async fn handle_account(
+        &self,
+        account: &AccountInfo<'_>,
+        parsed: ProgramParseResult<'_>,
+        context: &HandlerContext<'_>,
+    ) -> Result<(), IndexerError> {
+        match parsed {
+            ProgramParseResult::DataRegistryProgram(parsing_result) => {
+                handle_data_registry_program_account(account, parsing_result, context).await
+            }
+            _ => Err(IndexerError::NotImplemented),
+        }
+    }
+}
@@ -1,21 +1,26 @@
-use crate::{config::IndexerConfig, error::IndexerError};
+use crate::{
+    error::IndexerError,
+    program_handlers::{
+        registry::{AccountHandler, HandlerContext},
+        utils::{close_account, commit_upsert, execute_upsert},
+    },
+};
+use async_trait::async_trait;
 use plerkle_serialization::AccountInfo;
 use rwa_types::dao::{
     identity_account, identity_registry,
     sea_orm_active_enums::{IdentityAccountVersion, IdentityRegistryVersion},
 };
 use sea_orm::{
-    query::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait, DatabaseConnection,
-    DbBackend, EntityTrait,
+    query::*, sea_query::OnConflict, ActiveValue::Set, DbBackend, EntityName, EntityTrait,
 };
 use serde_json::json;
-use transformer::programs::identity_registry::IdentityRegistryProgram;
+use transformer::programs::{identity_registry::IdentityRegistryProgram, ProgramParseResult};
 
-pub async fn handle_identity_registry_program_account<'a, 'b, 'c>(
+pub async fn handle_identity_registry_program_account<'a, 'b>(
     account_update: &'a AccountInfo<'a>,
     parsing_result: &'b IdentityRegistryProgram,
-    db: &'c DatabaseConnection,
-    _config: &IndexerConfig,
+    context: &HandlerContext<'_>,
 ) -> Result<(), IndexerError> {
     let key = *account_update.pubkey().unwrap();
     let key_bytes = key.0.to_vec();
@@ -28,6 +33,7 @@ pub async fn handle_identity_registry_program_account<'a, 'b, 'c>(
                 delegate: Set(ir.delegate.to_bytes().to_vec()),
                 version: Set(IdentityRegistryVersion::from(ir.version)),
                 closed: Set(false),
+                closed_at_slot: Set(None),
                 slot_updated: Set(account_update.slot() as i64),
                 ..Default::default()
             };
@@ -41,6 +47,7 @@ pub async fn handle_identity_registry_program_account<'a, 'b, 'c>(
                             identity_registry::Column::Delegate,
                             identity_registry::Column::Version,
                             identity_registry::Column::Closed,
+                            identity_registry::Column::ClosedAtSlot,
                             identity_registry::Column::SlotUpdated,
                         ])
                         .to_owned(),
@@ -51,12 +58,17 @@ pub async fn handle_identity_registry_program_account<'a, 'b, 'c>(
                 "{} WHERE excluded.slot_updated >= identity_registry.slot_updated OR identity_registry.slot_updated IS NULL",
                 query.sql);
 
-            let txn = db.begin().await?;
-            txn.execute(query)
-                .await
-                .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
-            txn.commit().await?;
-            Ok(())
+            let txn = context.db.begin().await?;
+            let outcome = execute_upsert(&txn, query).await?;
+            commit_upsert(
+                context,
+                txn,
+                identity_registry::Entity,
+                key_bytes,
+                account_update.slot() as i64,
+                outcome,
+            )
+            .await
         }
         IdentityRegistryProgram::IdentityAccount(ia) => {
             let active_model = identity_account::ActiveModel {
@@ -65,6 +77,8 @@ pub async fn handle_identity_registry_program_account<'a, 'b, 'c>(
                 identity_registry: Set(ia.identity_registry.to_bytes().to_vec()),
                 version: Set(IdentityAccountVersion::from(ia.version)),
                 levels: Set(Some(json!({ "levels": ia.levels }))),
+                closed: Set(false),
+                closed_at_slot: Set(None),
                 slot_updated: Set(account_update.slot() as i64),
                 ..Default::default()
             };
@@ -77,6 +91,8 @@ pub async fn handle_identity_registry_program_account<'a, 'b, 'c>(
                             identity_account::Column::IdentityRegistry,
                             identity_account::Column::Version,
                             identity_account::Column::Levels,
+                            identity_account::Column::Closed,
+                            identity_account::Column::ClosedAtSlot,
                             identity_account::Column::SlotUpdated,
                         ])
                         .to_owned(),
@@ -87,15 +103,49 @@ pub async fn handle_identity_registry_program_account<'a, 'b, 'c>(
                 "{} WHERE excluded.slot_updated >= identity_account.slot_updated OR identity_account.slot_updated IS NULL",
                 query.sql);
 
-            let txn = db.begin().await?;
-            txn.execute(query)
-                .await
-                .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
-            txn.commit().await?;
-
-            Ok(())
+            let txn = context.db.begin().await?;
+            let outcome = execute_upsert(&txn, query).await?;
+            commit_upsert(
+                context,
+                txn,
+                identity_account::Entity,
+                key_bytes,
+                account_update.slot() as i64,
+                outcome,
+            )
+            .await
+        }
+        IdentityRegistryProgram::EmptyAccount => {
+            close_account(
+                context,
+                &[
+                    identity_registry::Entity.table_name(),
+                    identity_account::Entity.table_name(),
+                ],
+                key_bytes.clone(),
+                account_update.slot() as i64,
+            )
+            .await
         }
-        _ => Err(IndexerError::NotImplemented),
     }?;
     Ok(())
 }
+
+pub struct IdentityRegistryHandler;
+
+#[async_trait]
+impl AccountHandler for IdentityRegistryHandler {
+    async fn handle_account(
+        &self,
+        account: &AccountInfo<'_>,
+        parsed: ProgramParseResult<'_>,
+        context: &HandlerContext<'_>,
+    ) -> Result<(), IndexerError> {
+        match parsed {
+            ProgramParseResult::IdentityRegistryProgram(parsing_result) => {
+                handle_identity_registry_program_account(account, parsing_result, context).await
+            }
+            _ => Err(IndexerError::NotImplemented),
+        }
+    }
+}
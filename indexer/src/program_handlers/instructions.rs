@@ -0,0 +1,87 @@
+use crate::error::IndexerError;
+use rwa_types::dao::rwa_instruction;
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue::Set, ConnectionTrait, DatabaseConnection, DbBackend,
+    EntityTrait,
+};
+use solana_sdk::pubkey::Pubkey;
+use transformer::programs::instruction::ParsedInstruction;
+
+/// Transaction level fields shared by every instruction row.
+pub struct TransactionContext {
+    pub signature: Vec<u8>,
+    pub slot: u64,
+    /// The fee payer, which is always the first account key.
+    pub signer: Option<Pubkey>,
+}
+
+/// Position of an instruction inside its transaction.
+pub struct InstructionPosition {
+    pub instruction_index: usize,
+    /// 0 for the top-level instruction, n for the n-th instruction it invoked.
+    pub inner_index: usize,
+}
+
+pub fn instruction_model(
+    context: &TransactionContext,
+    position: InstructionPosition,
+    program_id: &Pubkey,
+    accounts: &[Pubkey],
+    data: &[u8],
+    parsed: Option<ParsedInstruction>,
+) -> Result<rwa_instruction::ActiveModel, IndexerError> {
+    let (instruction_name, args) = match parsed {
+        Some(parsed) => (
+            parsed.name.to_string(),
+            parsed.args.map(serde_json::to_value).transpose()?,
+        ),
+        None => ("unknown".to_string(), None),
+    };
+    let accounts = accounts
+        .iter()
+        .map(|account| account.to_string())
+        .collect::<Vec<_>>();
+
+    Ok(rwa_instruction::ActiveModel {
+        signature: Set(context.signature.clone()),
+        slot: Set(context.slot as i64),
+        program_id: Set(program_id.to_bytes().to_vec()),
+        instruction_index: Set(position.instruction_index as i32),
+        inner_index: Set(position.inner_index as i32),
+        instruction_name: Set(instruction_name),
+        accounts: Set(serde_json::to_value(accounts)?),
+        args: Set(args),
+        data: Set(data.to_vec()),
+        signer: Set(context
+            .signer
+            .as_ref()
+            .map(|signer| signer.to_bytes().to_vec())),
+        ..Default::default()
+    })
+}
+
+/// Stores the RWA instructions of a transaction in one statement. Instructions of a redelivered
+/// transaction are already stored and are left as they are.
+pub async fn save_instructions(
+    db: &DatabaseConnection,
+    instructions: Vec<rwa_instruction::ActiveModel>,
+) -> Result<(), IndexerError> {
+    if instructions.is_empty() {
+        return Ok(());
+    }
+    let query = rwa_instruction::Entity::insert_many(instructions)
+        .on_conflict(
+            OnConflict::columns([
+                rwa_instruction::Column::Signature,
+                rwa_instruction::Column::InstructionIndex,
+                rwa_instruction::Column::InnerIndex,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+    db.execute(query)
+        .await
+        .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
+    Ok(())
+}
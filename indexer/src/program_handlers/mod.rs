@@ -1,67 +1,66 @@
-use crate::{config::IndexerConfig, error::IndexerError};
+use crate::{change_stream::ChangeEventOutbox, config::IndexerConfig, error::IndexerError};
 use log::error;
-use plerkle_serialization::{AccountInfo, Pubkey as FBPubkey};
+use plerkle_serialization::{
+    AccountInfo, CompiledInstruction, Pubkey as FBPubkey, TransactionInfo,
+};
+use rwa_types::dao::rwa_instruction;
 use sea_orm::{DatabaseConnection, SqlxPostgresConnector};
 use solana_sdk::pubkey::Pubkey;
 use sqlx::PgPool;
-use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use transformer::{
-    program_handler::ProgramParser,
-    programs::{
-        asset_controller::AssetControllerParser, data_registry::DataRegistryParser,
-        identity_registry::IdentityRegistryParser, policy_engine::PolicyEngineParser,
-        ProgramParseResult,
-    },
+    error::TransformerError, program_handler::ProgramParser, programs::is_closed_account,
 };
 
-use crate::program_handlers::asset_controller::handle_asset_controller_program_account;
-
+pub use self::registry::{AccountHandler, HandlerContext, ProgramRegistry};
+pub(crate) use self::rwa_mints::RWA_MINTS_REFRESH_INTERVAL;
 use self::{
-    data_registry::handle_data_registry_program_account,
-    identity_registry::handle_identity_registry_program_account,
-    policy_engine::handle_policy_engine_program_account,
+    instructions::{instruction_model, save_instructions, InstructionPosition, TransactionContext},
+    raw_account::{close_raw_account, save_raw_account},
 };
 
 mod asset_controller;
+mod change_events;
 mod data_registry;
 mod identity_registry;
+mod instructions;
 mod policy_engine;
+mod raw_account;
+mod registry;
+mod rwa_mints;
+mod token_extensions;
 mod utils;
 
 pub struct ProgramHandler {
     storage: DatabaseConnection,
-    matchers: HashMap<Pubkey, Box<dyn ProgramParser>>,
-    key_set: HashSet<Pubkey>,
+    programs: Arc<ProgramRegistry>,
     config: IndexerConfig,
+    change_events: ChangeEventOutbox,
+    listener_channel: String,
 }
 
 impl ProgramHandler {
     pub fn new(pool: PgPool, config: IndexerConfig) -> Self {
-        let mut matchers: HashMap<Pubkey, Box<dyn ProgramParser>> = HashMap::with_capacity(1);
-        let asset_controller = AssetControllerParser {};
-        let data_registry = DataRegistryParser {};
-        let identity_registry = IdentityRegistryParser {};
-        let policy_engine = PolicyEngineParser {};
-        matchers.insert(asset_controller.key(), Box::new(asset_controller));
-        matchers.insert(data_registry.key(), Box::new(data_registry));
-        matchers.insert(identity_registry.key(), Box::new(identity_registry));
-        matchers.insert(policy_engine.key(), Box::new(policy_engine));
-        let hs = matchers.iter().fold(HashSet::new(), |mut acc, (k, _)| {
-            acc.insert(*k);
-            acc
-        });
         let pool: PgPool = pool;
         ProgramHandler {
             storage: SqlxPostgresConnector::from_sqlx_postgres_pool(pool),
-            matchers,
-            key_set: hs,
+            programs: Arc::new(ProgramRegistry::rwa_programs(&config.get_program_ids())),
+            listener_channel: config.get_listener_channel(),
+            change_events: ChangeEventOutbox::new(&config),
             config,
         }
     }
 
+    /// Indexes the programs of `programs` instead of only the RWA programs.
+    pub fn with_programs(mut self, programs: Arc<ProgramRegistry>) -> Self {
+        self.programs = programs;
+        self
+    }
+
     pub fn match_program(&self, key: &FBPubkey) -> Option<&Box<dyn ProgramParser>> {
-        self.matchers
+        self.programs
             .get(&Pubkey::try_from(key.0.as_slice()).unwrap())
+            .map(|program| &program.parser)
     }
 
     pub async fn handle_account_update<'b>(
@@ -69,83 +68,168 @@ impl ProgramHandler {
         acct: AccountInfo<'b>,
         config: &IndexerConfig,
     ) -> Result<(), IndexerError> {
-        let owner = acct.owner().unwrap();
-        if let Some(program) = self.match_program(owner) {
-            let result = program.handle_account(&acct)?;
-            let concrete = result.result_type();
-            match concrete {
-                ProgramParseResult::AssetControllerProgram(parsing_result) => {
-                    handle_asset_controller_program_account(
-                        &acct,
-                        parsing_result,
-                        &self.storage,
-                        config,
-                    )
-                    .await
-                    .map_err(|err| {
-                        error!(
-                            "Failed to handle asset controller account {:?}: {:?}",
-                            bs58::encode(acct.pubkey().unwrap().0.as_slice()).into_string(),
-                            err
-                        );
-                        err
-                    })
+        let owner = Pubkey::try_from(acct.owner().unwrap().0.as_slice()).unwrap();
+        if let Some(program) = self.programs.get(&owner) {
+            let context = HandlerContext {
+                db: &self.storage,
+                config,
+                events: &self.change_events,
+                listener_channel: &self.listener_channel,
+            };
+            let result = match (program.parser.handle_account(&acct), &program.idl) {
+                (Err(TransformerError::UnknownAccountDiscriminator), Some(idl)) => {
+                    let data = acct
+                        .data()
+                        .map(|data| data.iter().collect::<Vec<_>>())
+                        .unwrap_or_default();
+                    return match idl.decode_account(&data)? {
+                        Some(decoded) => {
+                            save_raw_account(&self.storage, &acct, &owner, decoded).await
+                        }
+                        None => Err(IndexerError::IgnoredAccount(
+                            TransformerError::UnknownAccountDiscriminator.to_string(),
+                        )),
+                    };
                 }
-                ProgramParseResult::DataRegistryProgram(parsing_result) => {
-                    handle_data_registry_program_account(
-                        &acct,
-                        parsing_result,
-                        &self.storage,
-                        config,
-                    )
-                    .await
-                    .map_err(|err| {
-                        error!(
-                            "Failed to handle data registry account {:?}: {:?}",
-                            bs58::encode(acct.pubkey().unwrap().0.as_slice()).into_string(),
-                            err
-                        );
-                        err
-                    })
+                (Err(TransformerError::UnknownAccountDiscriminator), None) => {
+                    return Err(IndexerError::IgnoredAccount(
+                        TransformerError::UnknownAccountDiscriminator.to_string(),
+                    ));
                 }
-
-                ProgramParseResult::IdentityRegistryProgram(parsing_result) => {
-                    handle_identity_registry_program_account(
-                        &acct,
-                        parsing_result,
-                        &self.storage,
-                        config,
-                    )
-                    .await
-                    .map_err(|err| {
-                        error!(
-                            "Failed to handle identity registry account {:?}: {:?}",
-                            bs58::encode(acct.pubkey().unwrap().0.as_slice()).into_string(),
-                            err
-                        );
-                        err
-                    })
+                (Err(err), _) if !program.handler.is_indexed(&acct, &context).await? => {
+                    return Err(IndexerError::IgnoredAccount(err.to_string()));
                 }
-                ProgramParseResult::PolicyEngineProgram(parsing_result) => {
-                    handle_policy_engine_program_account(
-                        &acct,
-                        parsing_result,
-                        &self.storage,
-                        config,
-                    )
-                    .await
-                    .map_err(|err| {
-                        error!(
-                            "Failed to handle policy engine account {:?}: {:?}",
-                            bs58::encode(acct.pubkey().unwrap().0.as_slice()).into_string(),
-                            err
-                        );
+                (result, _) => result?,
+            };
+            program
+                .handler
+                .handle_account(&acct, result.result_type(), &context)
+                .await
+                .map_err(|err| {
+                    error!(
+                        "Failed to handle account {:?} of program {}: {:?}",
+                        bs58::encode(acct.pubkey().unwrap().0.as_slice()).into_string(),
+                        owner,
                         err
-                    })
-                }
-                _ => Err(IndexerError::NotImplemented),
-            }?;
+                    );
+                    err
+                })?;
+            if program.idl.is_some() && is_closed_account(&acct) {
+                close_raw_account(
+                    &self.storage,
+                    acct.pubkey().unwrap().0.to_vec(),
+                    acct.slot() as i64,
+                )
+                .await?;
+            }
         }
         Ok(())
     }
+
+    /// Decodes and stores every instruction of the RWA programs in the transaction, including
+    /// the ones invoked through CPI.
+    pub async fn handle_transaction<'b>(
+        &self,
+        tx: TransactionInfo<'b>,
+    ) -> Result<(), IndexerError> {
+        if tx.is_vote() {
+            return Ok(());
+        }
+        let keys = tx
+            .account_keys()
+            .map(|keys| {
+                keys.iter()
+                    .map(|key| Pubkey::try_from(key.0.as_slice()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()
+            .map_err(|e| IndexerError::DeserializationError(e.to_string()))?
+            .unwrap_or_default();
+        let signature = tx
+            .signature()
+            .ok_or_else(|| IndexerError::DeserializationError("Missing signature".to_string()))
+            .and_then(|signature| {
+                bs58::decode(signature)
+                    .into_vec()
+                    .map_err(|e| IndexerError::DeserializationError(e.to_string()))
+            })?;
+        let context = TransactionContext {
+            signature,
+            slot: tx.slot(),
+            signer: keys.first().copied(),
+        };
+
+        let mut instructions = Vec::new();
+        if let Some(outer_instructions) = tx.outer_instructions() {
+            for (instruction_index, instruction) in outer_instructions.iter().enumerate() {
+                let position = InstructionPosition {
+                    instruction_index,
+                    inner_index: 0,
+                };
+                if let Some(model) =
+                    self.parse_instruction(&context, &keys, position, instruction)?
+                {
+                    instructions.push(model);
+                }
+            }
+        }
+        if let Some(inner_instructions) = tx.compiled_inner_instructions() {
+            for group in inner_instructions.iter() {
+                for (i, inner) in group.instructions().into_iter().flatten().enumerate() {
+                    let position = InstructionPosition {
+                        instruction_index: group.index() as usize,
+                        inner_index: i + 1,
+                    };
+                    if let Some(instruction) = inner.compiled_instruction() {
+                        if let Some(model) =
+                            self.parse_instruction(&context, &keys, position, instruction)?
+                        {
+                            instructions.push(model);
+                        }
+                    }
+                }
+            }
+        }
+
+        save_instructions(&self.storage, instructions).await
+    }
+
+    fn parse_instruction(
+        &self,
+        context: &TransactionContext,
+        keys: &[Pubkey],
+        position: InstructionPosition,
+        instruction: CompiledInstruction<'_>,
+    ) -> Result<Option<rwa_instruction::ActiveModel>, IndexerError> {
+        let program_id = match keys.get(instruction.program_id_index() as usize) {
+            Some(program_id) => program_id,
+            None => return Ok(None),
+        };
+        let program = match self.programs.get(program_id) {
+            Some(program) if program.parser.handles_instructions() => &program.parser,
+            _ => return Ok(None),
+        };
+        let accounts = instruction
+            .accounts()
+            .into_iter()
+            .flatten()
+            .filter_map(|index| keys.get(index as usize).copied())
+            .collect::<Vec<_>>();
+        let data = instruction
+            .data()
+            .map(|data| data.iter().collect::<Vec<_>>())
+            .unwrap_or_default();
+        let parsed = match program.handle_instruction(&data) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                error!(
+                    "Failed to parse instruction {}.{} of program {}: {:?}",
+                    position.instruction_index, position.inner_index, program_id, err
+                );
+                None
+            }
+        };
+
+        instruction_model(context, position, program_id, &accounts, &data, parsed).map(Some)
+    }
 }
@@ -1,4 +1,11 @@
-use crate::{config::IndexerConfig, error::IndexerError};
+use crate::{
+    error::IndexerError,
+    program_handlers::{
+        registry::{AccountHandler, HandlerContext},
+        utils::{close_account, commit_upsert, execute_upsert},
+    },
+};
+use async_trait::async_trait;
 use num_traits::FromPrimitive;
 use plerkle_serialization::AccountInfo;
 use policy_engine::Policy;
@@ -7,17 +14,15 @@ use rwa_types::dao::{
     sea_orm_active_enums::{PolicyAccountType, PolicyEngineVersion},
 };
 use sea_orm::{
-    query::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait, DatabaseConnection,
-    DbBackend, EntityTrait,
+    query::*, sea_query::OnConflict, ActiveValue::Set, DbBackend, EntityName, EntityTrait,
 };
 use serde_json::json;
-use transformer::programs::policy_engine::PolicyEngineProgram;
+use transformer::programs::{policy_engine::PolicyEngineProgram, ProgramParseResult};
 
-pub async fn handle_policy_engine_program_account<'a, 'b, 'c>(
+pub async fn handle_policy_engine_program_account<'a, 'b>(
     account_update: &'a AccountInfo<'a>,
     parsing_result: &'b PolicyEngineProgram,
-    db: &'c DatabaseConnection,
-    _config: &IndexerConfig,
+    context: &HandlerContext<'_>,
 ) -> Result<(), IndexerError> {
     let key = *account_update.pubkey().unwrap();
     let key_bytes = key.0.to_vec();
@@ -32,6 +37,7 @@ pub async fn handle_policy_engine_program_account<'a, 'b, 'c>(
                 policies: Set(Some(json!({ "policies": pe.policies}))),
                 version: Set(PolicyEngineVersion::from(pe.version)),
                 closed: Set(false),
+                closed_at_slot: Set(None),
                 slot_updated: Set(account_update.slot() as i64),
                 ..Default::default()
             };
@@ -46,6 +52,8 @@ pub async fn handle_policy_engine_program_account<'a, 'b, 'c>(
                             engine::Column::MaxTimeframe,
                             engine::Column::Policies,
                             engine::Column::Version,
+                            engine::Column::Closed,
+                            engine::Column::ClosedAtSlot,
                             engine::Column::SlotUpdated,
                         ])
                         .to_owned(),
@@ -56,12 +64,17 @@ pub async fn handle_policy_engine_program_account<'a, 'b, 'c>(
                 "{} WHERE excluded.slot_updated >= policy_engine.slot_updated OR policy_engine.slot_updated IS NULL",
                 query.sql);
 
-            let txn = db.begin().await?;
-            txn.execute(query)
-                .await
-                .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
-            txn.commit().await?;
-            Ok(())
+            let txn = context.db.begin().await?;
+            let outcome = execute_upsert(&txn, query).await?;
+            commit_upsert(
+                context,
+                txn,
+                engine::Entity,
+                key_bytes,
+                account_update.slot() as i64,
+                outcome,
+            )
+            .await
         }
         PolicyEngineProgram::PolicyAccount(pe) => {
             let (limit, timeframe) = match pe.policy {
@@ -86,6 +99,8 @@ pub async fn handle_policy_engine_program_account<'a, 'b, 'c>(
                 identity_levels: Set(Some(
                     json!({ "identity_levels": pe.identity_filter.identity_levels}),
                 )),
+                closed: Set(false),
+                closed_at_slot: Set(None),
                 slot_updated: Set(account_update.slot() as i64),
                 timeframe: Set(timeframe),
                 total_limit: Set(total_limit),
@@ -102,6 +117,8 @@ pub async fn handle_policy_engine_program_account<'a, 'b, 'c>(
                             policy_account::Column::PolicyEngine,
                             policy_account::Column::Timeframe,
                             policy_account::Column::TotalLimit,
+                            policy_account::Column::Closed,
+                            policy_account::Column::ClosedAtSlot,
                             policy_account::Column::SlotUpdated,
                         ])
                         .to_owned(),
@@ -112,14 +129,49 @@ pub async fn handle_policy_engine_program_account<'a, 'b, 'c>(
                 "{} WHERE excluded.slot_updated >= policy_account.slot_updated OR policy_account.slot_updated IS NULL",
                 query.sql);
 
-            let txn = db.begin().await?;
-            txn.execute(query)
-                .await
-                .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
-            txn.commit().await?;
-            Ok(())
+            let txn = context.db.begin().await?;
+            let outcome = execute_upsert(&txn, query).await?;
+            commit_upsert(
+                context,
+                txn,
+                policy_account::Entity,
+                key_bytes,
+                account_update.slot() as i64,
+                outcome,
+            )
+            .await
+        }
+        PolicyEngineProgram::EmptyAccount => {
+            close_account(
+                context,
+                &[
+                    engine::Entity.table_name(),
+                    policy_account::Entity.table_name(),
+                ],
+                key_bytes.clone(),
+                account_update.slot() as i64,
+            )
+            .await
         }
-        _ => Err(IndexerError::NotImplemented),
     }?;
     Ok(())
 }
+
+pub struct PolicyEngineHandler;
+
+#[async_trait]
+impl AccountHandler for PolicyEngineHandler {
+    async fn handle_account(
+        &self,
+        account: &AccountInfo<'_>,
+        parsed: ProgramParseResult<'_>,
+        context: &HandlerContext<'_>,
+    ) -> Result<(), IndexerError> {
+        match parsed {
+            ProgramParseResult::PolicyEngineProgram(parsing_result) => {
+                handle_policy_engine_program_account(account, parsing_result, context).await
+            }
+            _ => Err(IndexerError::NotImplemented),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::error::IndexerError;
+use plerkle_serialization::AccountInfo;
+use rwa_types::dao::raw_account;
+use sea_orm::{
+    query::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait, DatabaseConnection,
+    DbBackend, EntityTrait, Statement,
+};
+use solana_sdk::pubkey::Pubkey;
+use transformer::programs::idl::DecodedAccount;
+
+/// Stores an account only the program's IDL could decode. These rows are a capture of account
+/// types the typed parsers don't cover yet, they have no history or change events.
+pub async fn save_raw_account(
+    db: &DatabaseConnection,
+    account_update: &AccountInfo<'_>,
+    program_id: &Pubkey,
+    decoded: DecodedAccount,
+) -> Result<(), IndexerError> {
+    let active_model = raw_account::ActiveModel {
+        id: Set(account_update.pubkey().unwrap().0.to_vec()),
+        program_id: Set(program_id.to_bytes().to_vec()),
+        account_type: Set(decoded.account_type),
+        raw_decoded: Set(decoded.data),
+        closed: Set(false),
+        closed_at_slot: Set(None),
+        slot_updated: Set(account_update.slot() as i64),
+        ..Default::default()
+    };
+
+    let mut query = raw_account::Entity::insert(active_model)
+        .on_conflict(
+            OnConflict::columns([raw_account::Column::Id])
+                .update_columns([
+                    raw_account::Column::ProgramId,
+                    raw_account::Column::AccountType,
+                    raw_account::Column::RawDecoded,
+                    raw_account::Column::Closed,
+                    raw_account::Column::ClosedAtSlot,
+                    raw_account::Column::SlotUpdated,
+                ])
+                .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+
+    query.sql = format!(
+        "{} WHERE excluded.slot_updated >= raw_account.slot_updated OR raw_account.slot_updated IS NULL",
+        query.sql
+    );
+    db.execute(query)
+        .await
+        .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
+    Ok(())
+}
+
+/// Marks `id` closed if it was stored as a raw account.
+pub async fn close_raw_account(
+    db: &DatabaseConnection,
+    id: Vec<u8>,
+    slot: i64,
+) -> Result<(), IndexerError> {
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "UPDATE raw_account SET closed = true, closed_at_slot = $2, slot_updated = $2 WHERE id = $1 AND slot_updated <= $2",
+        vec![id.into(), slot.into()],
+    ))
+    .await
+    .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use crate::{change_stream::ChangeEventOutbox, config::IndexerConfig, error::IndexerError};
+use async_trait::async_trait;
+use common::utils::ProgramIds;
+use log::info;
+use plerkle_serialization::AccountInfo;
+use sea_orm::DatabaseConnection;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use transformer::{
+    program_handler::ProgramParser,
+    programs::{
+        asset_controller::AssetControllerParser, data_registry::DataRegistryParser,
+        identity_registry::IdentityRegistryParser, idl::IdlDecoder,
+        policy_engine::PolicyEngineParser, token_extensions::TokenExtensionsParser,
+        ProgramParseResult,
+    },
+};
+
+use super::{
+    asset_controller::AssetControllerHandler, data_registry::DataRegistryHandler,
+    identity_registry::IdentityRegistryHandler, policy_engine::PolicyEngineHandler,
+    rwa_mints::RwaMints, token_extensions::TokenExtensionsHandler,
+};
+
+/// What a storage handler gets to work with besides the account.
+pub struct HandlerContext<'a> {
+    pub db: &'a DatabaseConnection,
+    pub config: &'a IndexerConfig,
+    pub events: &'a ChangeEventOutbox,
+    /// The `pg_notify` channel account changes are announced on.
+    pub listener_channel: &'a str,
+}
+
+/// Stores the parsed accounts of one program. Paired with the program's parser in a
+/// [`ProgramRegistry`]; `parsed` is whatever that parser returned for `account`.
+#[async_trait]
+pub trait AccountHandler: Sync + Send {
+    async fn handle_account(
+        &self,
+        account: &AccountInfo<'_>,
+        parsed: ProgramParseResult<'_>,
+        context: &HandlerContext<'_>,
+    ) -> Result<(), IndexerError>;
+
+    /// Whether `account`, which the program's parser failed on, may belong to an indexed
+    /// asset. Failures on accounts that don't are acked rather than dead-lettered.
+    async fn is_indexed(
+        &self,
+        _account: &AccountInfo<'_>,
+        _context: &HandlerContext<'_>,
+    ) -> Result<bool, IndexerError> {
+        Ok(true)
+    }
+}
+
+pub(crate) struct RegisteredProgram {
+    pub parser: Box<dyn ProgramParser>,
+    pub handler: Box<dyn AccountHandler>,
+    /// Decodes the accounts `parser` doesn't know into `raw_account`.
+    pub idl: Option<IdlDecoder>,
+}
+
+/// The programs the indexer parses and stores, keyed by program id. Start from
+/// [`ProgramRegistry::rwa_programs`] and `register` additional programs, such as a transfer hook,
+/// to index them alongside the RWA programs. Their instructions are recorded in
+/// `rwa_instruction` when the parser handles instructions.
+#[derive(Default)]
+pub struct ProgramRegistry {
+    programs: HashMap<Pubkey, RegisteredProgram>,
+}
+
+impl ProgramRegistry {
+    /// The four RWA programs, deployed at `program_ids`, and the Token-2022 accounts of RWA
+    /// mints.
+    pub fn rwa_programs(program_ids: &ProgramIds) -> Self {
+        let rwa_mints = RwaMints::default();
+        let mut registry = Self::default();
+        registry
+            .register(
+                AssetControllerParser::new(program_ids.asset_controller),
+                AssetControllerHandler {
+                    rwa_mints: rwa_mints.clone(),
+                },
+            )
+            .register(
+                DataRegistryParser::new(program_ids.data_registry),
+                DataRegistryHandler,
+            )
+            .register(
+                IdentityRegistryParser::new(program_ids.identity_registry),
+                IdentityRegistryHandler,
+            )
+            .register(
+                PolicyEngineParser::new(program_ids.policy_engine),
+                PolicyEngineHandler,
+            )
+            .register(
+                TokenExtensionsParser {},
+                TokenExtensionsHandler { rwa_mints },
+            );
+        registry
+    }
+
+    /// Indexes the accounts owned by `parser.key()` with `handler`, replacing any program
+    /// registered under the same id.
+    pub fn register(
+        &mut self,
+        parser: impl ProgramParser + 'static,
+        handler: impl AccountHandler + 'static,
+    ) -> &mut Self {
+        self.programs.insert(
+            parser.key(),
+            RegisteredProgram {
+                parser: Box::new(parser),
+                handler: Box::new(handler),
+                idl: None,
+            },
+        );
+        self
+    }
+
+    /// Falls back to decoding accounts of `program_id` with `idl` when its parser doesn't know
+    /// their discriminator.
+    pub fn set_idl(
+        &mut self,
+        program_id: &Pubkey,
+        idl: IdlDecoder,
+    ) -> Result<&mut Self, IndexerError> {
+        let program =
+            self.programs
+                .get_mut(program_id)
+                .ok_or_else(|| IndexerError::ConfigurationError {
+                    msg: format!("IDL for unregistered program {}", program_id),
+                })?;
+        program.idl = Some(idl);
+        Ok(self)
+    }
+
+    /// Loads the IDLs of the RWA programs configured in `idl_paths`.
+    pub fn load_idls(&mut self, config: &IndexerConfig) -> Result<&mut Self, IndexerError> {
+        let program_ids = config.get_program_ids();
+        for (name, path) in config.idl_paths.iter().flatten() {
+            let program_id =
+                program_ids
+                    .by_name(name)
+                    .ok_or_else(|| IndexerError::ConfigurationError {
+                        msg: format!("IDL for unknown program {}", name),
+                    })?;
+            let idl =
+                IdlDecoder::from_file(path).map_err(|e| IndexerError::ConfigurationError {
+                    msg: format!("Failed to load IDL {}: {}", path, e),
+                })?;
+            info!("Loaded IDL {} for program {}", path, program_id);
+            self.set_idl(&program_id, idl)?;
+        }
+        Ok(self)
+    }
+
+    pub(crate) fn get(&self, program_id: &Pubkey) -> Option<&RegisteredProgram> {
+        self.programs.get(program_id)
+    }
+}
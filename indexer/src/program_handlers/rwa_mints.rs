@@ -0,0 +1,80 @@
+use crate::error::IndexerError;
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Statement};
+use std::{
+    collections::HashSet,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+
+/// How long the set is used before it is reloaded, which picks up the mints other pods indexed.
+pub const RWA_MINTS_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Default)]
+struct Loaded {
+    mints: HashSet<Vec<u8>>,
+    at: Option<Instant>,
+}
+
+#[derive(Default)]
+struct Inner {
+    loaded: RwLock<Loaded>,
+    reload: Mutex<()>,
+}
+
+/// The mints whose Token-2022 accounts are indexed: the mints of asset controllers and the
+/// mints already in `token_mint`. Kept in memory so the Token-2022 updates of other mints, by
+/// far most of them, are dropped without a query. Writes of this process are added as they
+/// commit, the rest is picked up by reloading every [`RWA_MINTS_REFRESH_INTERVAL`]. Mints are
+/// never removed, a closed asset's accounts keep being indexed.
+#[derive(Clone, Default)]
+pub struct RwaMints(Arc<Inner>);
+
+impl RwaMints {
+    pub async fn contains(
+        &self,
+        db: &DatabaseConnection,
+        mint: &[u8],
+    ) -> Result<bool, IndexerError> {
+        if self.is_stale() {
+            // One caller reloads, the others wait for it rather than reading a stale set.
+            let _reload = self.0.reload.lock().await;
+            if self.is_stale() {
+                self.reload(db).await?;
+            }
+        }
+        Ok(self.0.loaded.read().unwrap().mints.contains(mint))
+    }
+
+    pub fn insert(&self, mint: Vec<u8>) {
+        self.0.loaded.write().unwrap().mints.insert(mint);
+    }
+
+    fn is_stale(&self) -> bool {
+        self.0
+            .loaded
+            .read()
+            .unwrap()
+            .at
+            .map_or(true, |at| at.elapsed() >= RWA_MINTS_REFRESH_INTERVAL)
+    }
+
+    async fn reload(&self, db: &DatabaseConnection) -> Result<(), IndexerError> {
+        let rows = db
+            .query_all(Statement::from_string(
+                DbBackend::Postgres,
+                "SELECT asset_mint AS mint FROM asset_controller UNION SELECT id FROM token_mint"
+                    .to_string(),
+            ))
+            .await?;
+        let mints = rows
+            .into_iter()
+            .map(|row| row.try_get::<Vec<u8>>("", "mint"))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut loaded = self.0.loaded.write().unwrap();
+        // Extended rather than replaced, keeping mints inserted after the query ran.
+        loaded.mints.extend(mints);
+        loaded.at = Some(Instant::now());
+        Ok(())
+    }
+}
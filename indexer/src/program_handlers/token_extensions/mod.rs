@@ -0,0 +1,219 @@
+use crate::{
+    error::IndexerError,
+    program_handlers::{
+        registry::{AccountHandler, HandlerContext},
+        rwa_mints::RwaMints,
+        utils::{close_account, commit_upsert, execute_upsert, resolve_mint},
+    },
+};
+use async_trait::async_trait;
+use num_traits::FromPrimitive;
+use plerkle_serialization::AccountInfo;
+use rwa_types::dao::{token_account, token_mint};
+use sea_orm::{
+    query::*, sea_query::OnConflict, ActiveValue::Set, DbBackend, EntityName, EntityTrait,
+};
+use solana_sdk::pubkey::Pubkey;
+use transformer::programs::{token_extensions::TokenExtensionsProgram, ProgramParseResult};
+
+pub async fn handle_token_extensions_program_account<'a, 'b>(
+    account_update: &'a AccountInfo<'a>,
+    parsing_result: &'b TokenExtensionsProgram,
+    context: &HandlerContext<'_>,
+    rwa_mints: &RwaMints,
+) -> Result<(), IndexerError> {
+    let key = *account_update.pubkey().unwrap();
+    let key_bytes = key.0.to_vec();
+    match &parsing_result {
+        TokenExtensionsProgram::Mint(mint) => {
+            // A mint can arrive before its asset controller, the transfer hook still marks it.
+            let hooked = mint.transfer_hook_program_id
+                == Some(context.config.get_program_ids().asset_controller);
+            if !hooked && !rwa_mints.contains(context.db, &key_bytes).await? {
+                return Ok(());
+            }
+
+            let active_model = token_mint::ActiveModel {
+                id: Set(key_bytes.clone()),
+                supply: Set(sqlx::types::Decimal::from_u64(mint.supply)
+                    .expect("Failed to convert to Decimal")),
+                decimals: Set(mint.decimals as i32),
+                mint_authority: Set(to_bytes(mint.mint_authority)),
+                freeze_authority: Set(to_bytes(mint.freeze_authority)),
+                transfer_hook_program_id: Set(to_bytes(mint.transfer_hook_program_id)),
+                transfer_hook_authority: Set(to_bytes(mint.transfer_hook_authority)),
+                metadata_address: Set(to_bytes(mint.metadata_address)),
+                metadata_pointer_authority: Set(to_bytes(mint.metadata_pointer_authority)),
+                closed: Set(false),
+                closed_at_slot: Set(None),
+                slot_updated: Set(account_update.slot() as i64),
+                ..Default::default()
+            };
+
+            let mut query = token_mint::Entity::insert(active_model)
+                .on_conflict(
+                    OnConflict::columns([token_mint::Column::Id])
+                        .update_columns([
+                            token_mint::Column::Supply,
+                            token_mint::Column::Decimals,
+                            token_mint::Column::MintAuthority,
+                            token_mint::Column::FreezeAuthority,
+                            token_mint::Column::TransferHookProgramId,
+                            token_mint::Column::TransferHookAuthority,
+                            token_mint::Column::MetadataAddress,
+                            token_mint::Column::MetadataPointerAuthority,
+                            token_mint::Column::Closed,
+                            token_mint::Column::ClosedAtSlot,
+                            token_mint::Column::SlotUpdated,
+                        ])
+                        .to_owned(),
+                )
+                .build(DbBackend::Postgres);
+
+            query.sql = format!(
+                "{} WHERE excluded.slot_updated >= token_mint.slot_updated OR token_mint.slot_updated IS NULL",
+                query.sql);
+
+            let txn = context.db.begin().await?;
+            let outcome = execute_upsert(&txn, query).await?;
+            commit_upsert(
+                context,
+                txn,
+                token_mint::Entity,
+                key_bytes.clone(),
+                account_update.slot() as i64,
+                outcome,
+            )
+            .await?;
+            rwa_mints.insert(key_bytes);
+            Ok(())
+        }
+        TokenExtensionsProgram::TokenAccount(ta) => {
+            let mint = ta.mint.to_bytes().to_vec();
+            if !rwa_mints.contains(context.db, &mint).await? {
+                return Ok(());
+            }
+
+            let active_model = token_account::ActiveModel {
+                id: Set(key_bytes.clone()),
+                mint: Set(mint),
+                owner: Set(ta.owner.to_bytes().to_vec()),
+                amount: Set(sqlx::types::Decimal::from_u64(ta.amount)
+                    .expect("Failed to convert to Decimal")),
+                delegate: Set(to_bytes(ta.delegate)),
+                frozen: Set(ta.frozen),
+                closed: Set(false),
+                closed_at_slot: Set(None),
+                slot_updated: Set(account_update.slot() as i64),
+                ..Default::default()
+            };
+
+            let mut query = token_account::Entity::insert(active_model)
+                .on_conflict(
+                    OnConflict::columns([token_account::Column::Id])
+                        .update_columns([
+                            token_account::Column::Mint,
+                            token_account::Column::Owner,
+                            token_account::Column::Amount,
+                            token_account::Column::Delegate,
+                            token_account::Column::Frozen,
+                            token_account::Column::Closed,
+                            token_account::Column::ClosedAtSlot,
+                            token_account::Column::SlotUpdated,
+                        ])
+                        .to_owned(),
+                )
+                .build(DbBackend::Postgres);
+
+            query.sql = format!(
+                "{} WHERE excluded.slot_updated >= token_account.slot_updated OR token_account.slot_updated IS NULL",
+                query.sql);
+
+            let txn = context.db.begin().await?;
+            let outcome = execute_upsert(&txn, query).await?;
+            commit_upsert(
+                context,
+                txn,
+                token_account::Entity,
+                key_bytes,
+                account_update.slot() as i64,
+                outcome,
+            )
+            .await
+        }
+        TokenExtensionsProgram::Unsupported => Ok(()),
+        TokenExtensionsProgram::EmptyAccount => {
+            // A closed account has no data left to read its mint from, so it's looked up among
+            // the RWA mints and the indexed token accounts instead.
+            let table = if rwa_mints.contains(context.db, &key_bytes).await? {
+                token_mint::Entity.table_name()
+            } else if resolve_mint(context.db, token_account::Entity.table_name(), &key_bytes)
+                .await?
+                .is_some()
+            {
+                token_account::Entity.table_name()
+            } else {
+                return Ok(());
+            };
+            close_account(
+                context,
+                &[table],
+                key_bytes.clone(),
+                account_update.slot() as i64,
+            )
+            .await
+        }
+    }
+}
+
+fn to_bytes(pubkey: Option<Pubkey>) -> Option<Vec<u8>> {
+    pubkey.map(|pubkey| pubkey.to_bytes().to_vec())
+}
+
+/// Only Token-2022 accounts of RWA mints are stored, the program owns far more accounts than
+/// the RWA assets.
+pub struct TokenExtensionsHandler {
+    pub rwa_mints: RwaMints,
+}
+
+#[async_trait]
+impl AccountHandler for TokenExtensionsHandler {
+    async fn handle_account(
+        &self,
+        account: &AccountInfo<'_>,
+        parsed: ProgramParseResult<'_>,
+        context: &HandlerContext<'_>,
+    ) -> Result<(), IndexerError> {
+        match parsed {
+            ProgramParseResult::TokenExtensionsProgram(parsing_result) => {
+                handle_token_extensions_program_account(
+                    account,
+                    parsing_result,
+                    context,
+                    &self.rwa_mints,
+                )
+                .await
+            }
+            _ => Err(IndexerError::NotImplemented),
+        }
+    }
+
+    /// A mint is keyed by itself, a token account starts with its mint.
+    async fn is_indexed(
+        &self,
+        account: &AccountInfo<'_>,
+        context: &HandlerContext<'_>,
+    ) -> Result<bool, IndexerError> {
+        let key = account.pubkey().unwrap().0.to_vec();
+        if self.rwa_mints.contains(context.db, &key).await? {
+            return Ok(true);
+        }
+        match account.data().filter(|data| data.len() >= 32) {
+            Some(data) => {
+                let mint = data.iter().take(32).collect::<Vec<_>>();
+                self.rwa_mints.contains(context.db, &mint).await
+            }
+            None => Ok(false),
+        }
+    }
+}
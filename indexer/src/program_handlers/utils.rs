@@ -1,6 +1,15 @@
-use crate::metric;
+use crate::{
+    error::IndexerError,
+    metric,
+    program_handlers::{change_events::enqueue_webhook_events, registry::HandlerContext},
+};
 use cadence_macros::{is_global_default_set, statsd_count};
-use sea_orm::{query::*, ConnectionTrait, DbErr, EntityTrait};
+use rwa_types::{dao::account_history, events::AccountChange};
+use sea_orm::{
+    query::*, ActiveValue::Set, ConnectionTrait, DatabaseTransaction, DbBackend, DbErr, EntityName,
+    EntityTrait, PrimaryKeyTrait, Statement,
+};
+use serde::Serialize;
 use std::time::Duration;
 use tokio::time::sleep;
 
@@ -36,3 +45,249 @@ fn record_metric(metric_name: &str, success: bool, retries: u32) {
         statsd_count!(metric_name, 1, "success" => success, "retry_count" => retry_count);
     }
 }
+
+/// Columns that point a child account at its parent registry.
+const HISTORY_PARENT_KEYS: [&str; 3] = ["data_registry", "identity_registry", "policy_engine"];
+
+/// Appends the current row for `id` to `account_history`. Must run inside the upsert's
+/// transaction, and only when the slot guard let the write through.
+pub async fn record_history<E>(
+    txn: &DatabaseTransaction,
+    entity: E,
+    id: Vec<u8>,
+) -> Result<(), IndexerError>
+where
+    E: EntityTrait,
+    E::Model: Serialize,
+    Vec<u8>: Into<<E::PrimaryKey as PrimaryKeyTrait>::ValueType>,
+{
+    let model = match E::find_by_id(id.clone()).one(txn).await? {
+        Some(model) => model,
+        None => return Ok(()),
+    };
+    let data = serde_json::to_value(&model)?;
+    let bytes = |key: &str| {
+        data.get(key)
+            .and_then(|value| serde_json::from_value::<Vec<u8>>(value.clone()).ok())
+    };
+
+    let slot = data["slot_updated"].as_i64().unwrap_or_default();
+
+    let active_model = account_history::ActiveModel {
+        pubkey: Set(id.clone()),
+        account_type: Set(entity.table_name().to_string()),
+        asset_mint: Set(bytes("asset_mint")),
+        parent: Set(HISTORY_PARENT_KEYS.iter().find_map(|key| bytes(key))),
+        slot: Set(slot),
+        closed: Set(data["closed"].as_bool().unwrap_or_default()),
+        data: Set(data),
+        ..Default::default()
+    };
+    account_history::Entity::insert(active_model)
+        .exec(txn)
+        .await
+        .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
+    Ok(())
+}
+
+/// Query resolving the mint an account of `table` belongs to, child accounts go through their
+/// parent registry.
+fn mint_query(table: &str) -> String {
+    match table {
+        "data_account" | "identity_account" | "policy_account" => {
+            let parent = match table {
+                "data_account" => "data_registry",
+                "identity_account" => "identity_registry",
+                _ => "policy_engine",
+            };
+            format!(
+                "SELECT p.asset_mint AS mint FROM {table} c \
+                 INNER JOIN {parent} p ON p.id = c.{parent} WHERE c.id = $1"
+            )
+        }
+        "token_mint" => "SELECT id AS mint FROM token_mint WHERE id = $1".to_string(),
+        "token_account" => "SELECT mint FROM token_account WHERE id = $1".to_string(),
+        _ => format!("SELECT asset_mint AS mint FROM {table} WHERE id = $1"),
+    }
+}
+
+/// The mint an account of `table` belongs to, if it is indexed.
+pub async fn resolve_mint<C: ConnectionTrait>(
+    conn: &C,
+    table: &str,
+    id: &[u8],
+) -> Result<Option<Vec<u8>>, IndexerError> {
+    conn.query_one(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        &mint_query(table),
+        vec![id.to_vec().into()],
+    ))
+    .await?
+    .map(|row| row.try_get::<Vec<u8>>("", "mint"))
+    .transpose()
+    .map_err(Into::into)
+}
+
+/// The wallet owning `id`, for the tables of accounts held by a wallet.
+async fn resolve_owner<C: ConnectionTrait>(
+    conn: &C,
+    table: &str,
+    id: &[u8],
+) -> Result<Option<Vec<u8>>, IndexerError> {
+    if !matches!(
+        table,
+        "identity_account" | "tracker_account" | "token_account"
+    ) {
+        return Ok(None);
+    }
+    conn.query_one(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        &format!("SELECT owner FROM {table} WHERE id = $1"),
+        vec![id.to_vec().into()],
+    ))
+    .await?
+    .map(|row| row.try_get::<Vec<u8>>("", "owner"))
+    .transpose()
+    .map_err(Into::into)
+}
+
+/// Sends an `AccountChange` for `id` on `channel`. Run it in the write's transaction, Postgres
+/// only delivers the notification once that commits.
+pub async fn notify_change<C: ConnectionTrait>(
+    conn: &C,
+    channel: &str,
+    table: &str,
+    id: &[u8],
+    slot: i64,
+) -> Result<(), IndexerError> {
+    let mint = resolve_mint(conn, table, id).await?;
+    let owner = resolve_owner(conn, table, id).await?;
+    let change = AccountChange {
+        table: table.to_string(),
+        pubkey: bs58::encode(id).into_string(),
+        mint: mint.map(|mint| bs58::encode(mint).into_string()),
+        owner: owner.map(|owner| bs58::encode(owner).into_string()),
+        slot,
+    };
+
+    conn.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT pg_notify($1, $2)",
+        vec![channel.into(), serde_json::to_string(&change)?.into()],
+    ))
+    .await
+    .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
+    Ok(())
+}
+
+/// What a slot guarded upsert did to the account's row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpsertOutcome {
+    /// The row didn't exist and was inserted.
+    Inserted,
+    /// The existing row was overwritten.
+    Updated,
+    /// The slot guard kept the existing, newer row.
+    Skipped,
+}
+
+impl UpsertOutcome {
+    pub fn written(self) -> bool {
+        self != UpsertOutcome::Skipped
+    }
+}
+
+/// Runs a slot guarded upsert in `txn`. Postgres leaves `xmax` at zero only on a freshly
+/// inserted row, which tells a new account apart from an update of an indexed one.
+pub async fn execute_upsert(
+    txn: &DatabaseTransaction,
+    mut query: Statement,
+) -> Result<UpsertOutcome, IndexerError> {
+    query.sql = format!("{} RETURNING (xmax = 0) AS inserted", query.sql);
+    let row = txn
+        .query_one(query)
+        .await
+        .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
+    Ok(match row {
+        Some(row) if row.try_get::<bool>("", "inserted")? => UpsertOutcome::Inserted,
+        Some(_) => UpsertOutcome::Updated,
+        None => UpsertOutcome::Skipped,
+    })
+}
+
+/// Finishes an upsert of `id` into `entity` run in `txn`. When the slot guard let the write
+/// through, the new state is appended to `account_history` and announced on the listener
+/// channel, and its webhook deliveries and change event are queued.
+pub async fn commit_upsert<E>(
+    context: &HandlerContext<'_>,
+    txn: DatabaseTransaction,
+    entity: E,
+    id: Vec<u8>,
+    slot: i64,
+    outcome: UpsertOutcome,
+) -> Result<(), IndexerError>
+where
+    E: EntityTrait,
+    E::Model: Serialize,
+    Vec<u8>: Into<<E::PrimaryKey as PrimaryKeyTrait>::ValueType>,
+{
+    if outcome.written() {
+        let table = entity.table_name().to_string();
+        record_history(&txn, entity, id.clone()).await?;
+        let created = outcome == UpsertOutcome::Inserted;
+        let change = enqueue_webhook_events(&txn, &table, &id, slot, created).await?;
+        notify_change(&txn, context.listener_channel, &table, &id, slot).await?;
+        context.events.queue(&txn, change).await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Marks `id` closed in whichever of `tables` holds it. Uses the same slot guard as the
+/// upserts so a replayed close can't override a newer write. The closed state is appended to
+/// `account_history` by copying the account's latest snapshot, announced on the listener
+/// channel and queued for publishing as a change event.
+pub async fn close_account(
+    context: &HandlerContext<'_>,
+    tables: &[&str],
+    id: Vec<u8>,
+    slot: i64,
+) -> Result<(), IndexerError> {
+    let txn = context.db.begin().await?;
+    for table in tables {
+        let query = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            &format!(
+                "UPDATE {table} SET closed = true, closed_at_slot = $2, slot_updated = $2 WHERE id = $1 AND slot_updated <= $2"
+            ),
+            vec![id.clone().into(), slot.into()],
+        );
+        let result = txn
+            .execute(query)
+            .await
+            .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
+        if result.rows_affected() == 0 {
+            continue;
+        }
+
+        let history = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "INSERT INTO account_history (pubkey, account_type, asset_mint, parent, slot, closed, data) \
+             SELECT pubkey, account_type, asset_mint, parent, $3, true, \
+                 data || jsonb_build_object('closed', true, 'closed_at_slot', $3, 'slot_updated', $3) \
+             FROM account_history \
+             WHERE pubkey = $1 AND account_type = $2 \
+             ORDER BY slot DESC, id DESC \
+             LIMIT 1",
+            vec![id.clone().into(), table.to_string().into(), slot.into()],
+        );
+        txn.execute(history)
+            .await
+            .map_err(|db_err| IndexerError::AssetIndexError(db_err.to_string()))?;
+        let change = enqueue_webhook_events(&txn, table, &id, slot, false).await?;
+        context.events.queue(&txn, change).await?;
+        notify_change(&txn, context.listener_channel, table, &id, slot).await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
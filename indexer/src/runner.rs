@@ -0,0 +1,137 @@
+use crate::{
+    account_updates::account_worker,
+    ack::ack_worker,
+    backfiller::backfiller,
+    config::{init_logger, IndexerConfig, PodType},
+    database::setup_database,
+    error::IndexerError,
+    holder_backfill::holder_backfill_worker,
+    metric,
+    metrics::setup_metrics,
+    program_handlers::ProgramRegistry,
+    stream::StreamSizeTimer,
+    transaction_updates::transaction_worker,
+    webhooks::webhook_worker,
+};
+use cadence_macros::{is_global_default_set, statsd_count};
+use chrono::Duration;
+use log::{error, info};
+use plerkle_messenger::{
+    redis_messenger::RedisMessenger, ConsumptionType, ACCOUNT_STREAM, ACC_BACKFILL,
+    TRANSACTION_BACKFILL, TRANSACTION_STREAM,
+};
+use std::sync::Arc;
+
+use tokio::{signal, task::JoinSet};
+
+/// Runs the indexer until ctrl-c. Crates indexing their own programs next to the RWA programs
+/// call this with their additions registered on `ProgramRegistry::rwa_programs`. The backfiller
+/// only covers the RWA programs.
+pub async fn run_indexer(
+    config: IndexerConfig,
+    programs: ProgramRegistry,
+) -> Result<(), IndexerError> {
+    init_logger();
+    info!("Starting indexer");
+
+    // Setup Metrics ---------------------------------------------
+
+    // Optionally setup metrics if config demands it
+    setup_metrics(&config);
+
+    // One pool many clones, this thing is thread safe and send sync
+    let database_pool = setup_database(config.clone()).await;
+
+    //The pod_type determines the type of pod the indexer is running in
+    let pod_type = config.pod_type.clone().unwrap_or(PodType::Regular);
+
+    info!("Starting Program with Pod Type: {}", pod_type);
+
+    let mut tasks = JoinSet::new();
+
+    // Stream Size Timers ----------------------------------------
+    // Setup Stream Size Timers, these are small processes that run every 30 seconds and farm metrics for the size of the streams.
+    // If metrics are disabled, these will not run.
+    let stream_metrics_timer = Duration::seconds(30).to_std().unwrap();
+
+    let mut timer_acc = StreamSizeTimer::new(
+        stream_metrics_timer,
+        config.messenger_config.clone(),
+        match pod_type {
+            PodType::Backfiller => ACC_BACKFILL,
+            PodType::Regular => ACCOUNT_STREAM,
+        },
+    )?;
+    if let Some(t) = timer_acc.start::<RedisMessenger>().await {
+        tasks.spawn(t);
+    }
+
+    let mut timer_txn = StreamSizeTimer::new(
+        stream_metrics_timer,
+        config.messenger_config.clone(),
+        match pod_type {
+            PodType::Backfiller => TRANSACTION_BACKFILL,
+            PodType::Regular => TRANSACTION_STREAM,
+        },
+    )?;
+    if let Some(t) = timer_txn.start::<RedisMessenger>().await {
+        tasks.spawn(t);
+    }
+
+    let (_ack_task, ack_sender) =
+        ack_worker::<RedisMessenger>(config.get_messenger_client_config());
+    let programs = Arc::new(programs);
+    for i in 0..config.get_account_stream_worker_count() {
+        let _account = account_worker::<RedisMessenger>(
+            database_pool.clone(),
+            config.clone(),
+            Arc::clone(&programs),
+            ack_sender.clone(),
+            if i == 0 {
+                ConsumptionType::Redeliver
+            } else {
+                ConsumptionType::New
+            },
+            &pod_type,
+        );
+    }
+    for i in 0..config.get_transaction_stream_worker_count() {
+        let _txn = transaction_worker::<RedisMessenger>(
+            database_pool.clone(),
+            config.clone(),
+            Arc::clone(&programs),
+            ack_sender.clone(),
+            if i == 0 {
+                ConsumptionType::Redeliver
+            } else {
+                ConsumptionType::New
+            },
+            &pod_type,
+        );
+    }
+
+    for _ in 0..config.get_webhook_worker_count() {
+        let _webhooks = webhook_worker(database_pool.clone(), config.clone());
+    }
+
+    if pod_type == PodType::Backfiller {
+        let _backfill = backfiller(database_pool.clone(), config.clone());
+    } else {
+        let _holders = holder_backfill_worker(database_pool.clone(), config.clone());
+    }
+
+    metric! {
+        statsd_count!("indexer.startup", 1);
+    }
+    match signal::ctrl_c().await {
+        Ok(()) => {}
+        Err(err) => {
+            error!("Unable to listen for shutdown signal: {}", err);
+            // we also shut down in case of error
+        }
+    }
+
+    tasks.shutdown().await;
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use crate::{
+    config::{IndexerConfig, PodType},
+    dead_letter::store_failed_transaction_update,
+    metric,
+    metrics::{capture_result, CapturedMessage, MessageOutcome},
+    program_handlers::{ProgramHandler, ProgramRegistry},
+};
+use cadence_macros::{is_global_default_set, statsd_count, statsd_time};
+use chrono::Utc;
+use log::{debug, error};
+use plerkle_messenger::{
+    ConsumptionType, Messenger, RecvData, TRANSACTION_BACKFILL, TRANSACTION_STREAM,
+};
+use plerkle_serialization::{root_as_transaction_info, TransactionInfo};
+use sea_orm::{DatabaseConnection, SqlxPostgresConnector};
+use sqlx::{Pool, Postgres};
+use tokio::{
+    sync::mpsc::UnboundedSender,
+    task::{JoinHandle, JoinSet},
+    time::Instant,
+};
+
+pub fn transaction_worker<T: Messenger>(
+    pool: Pool<Postgres>,
+    config: IndexerConfig,
+    programs: Arc<ProgramRegistry>,
+    ack_channel: UnboundedSender<(&'static str, String)>,
+    consumption_type: ConsumptionType,
+    pod_type: &PodType,
+) -> JoinHandle<()> {
+    let stream_key = match pod_type {
+        PodType::Backfiller => TRANSACTION_BACKFILL,
+        PodType::Regular => TRANSACTION_STREAM,
+    };
+
+    tokio::spawn(async move {
+        let config_clone = config.clone();
+        let source = T::new(config_clone.get_messenger_client_config()).await;
+        if let Ok(mut msg) = source {
+            let db = SqlxPostgresConnector::from_sqlx_postgres_pool(pool.clone());
+            let manager = Arc::new(ProgramHandler::new(pool, config).with_programs(programs));
+            loop {
+                let e: Result<Vec<RecvData>, plerkle_messenger::MessengerError> =
+                    msg.recv(stream_key, consumption_type.clone()).await;
+                let mut tasks = JoinSet::new();
+                match e {
+                    Ok(data) => {
+                        let len = data.len();
+                        for item in data {
+                            tasks.spawn(handle_transaction(
+                                Arc::clone(&manager),
+                                db.clone(),
+                                item,
+                                stream_key,
+                                config_clone.clone(),
+                            ));
+                        }
+                        if len > 0 {
+                            debug!("Processed {} transactions", len);
+                        }
+                    }
+                    Err(e) => {
+                        error!("Error receiving from transaction stream: {}", e);
+                        metric! {
+                            statsd_count!("indexer.stream.receive_error", 1, "stream" => stream_key);
+                        }
+                    }
+                }
+                while let Some(res) = tasks.join_next().await {
+                    if let Ok(Some(id)) = res {
+                        let send = ack_channel.send((stream_key, id));
+                        if let Err(err) = send {
+                            metric! {
+                                error!("Transaction stream ack error: {}", err);
+                                statsd_count!("indexer.stream.ack_error", 1, "stream" => stream_key);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+async fn handle_transaction(
+    manager: Arc<ProgramHandler>,
+    db: DatabaseConnection,
+    item: RecvData,
+    stream_key: &str,
+    config: IndexerConfig,
+) -> Option<String> {
+    let id = item.id;
+    let data = item.data;
+    if item.tries > 0 {
+        metric! {
+            statsd_count!("indexer.transaction_stream_redelivery", 1);
+        }
+    }
+    // Get root of transaction info flatbuffers object.
+    let transaction = match root_as_transaction_info(&data) {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Error reading transaction: {}", e);
+            return dead_letter(&db, id, stream_key, &data, None, &e.to_string(), item.tries).await;
+        }
+    };
+    let signature = transaction.signature().map(str::to_string);
+
+    metric! {
+        statsd_count!("indexer.seen", 1, "stream" => stream_key);
+        let seen_at = Utc::now();
+        statsd_time!(
+            "indexer.bus_ingest_time",
+            std::cmp::max(seen_at.timestamp_millis() - transaction.seen_at(), 0) as u64,
+            "stream" => stream_key
+        );
+    }
+    let begin_processing = Instant::now();
+    let res = manager.handle_transaction(transaction).await;
+    let outcome = capture_result(
+        CapturedMessage {
+            stream: stream_key,
+            label: ("type", "transaction"),
+            tries: item.tries,
+            max_retries: config.get_max_retries(),
+            proc: begin_processing,
+            txn_sig: signature.as_deref(),
+            account: None,
+        },
+        res,
+    );
+    match outcome {
+        MessageOutcome::Ack => Some(id),
+        MessageOutcome::Retry => None,
+        MessageOutcome::DeadLetter(error) => {
+            dead_letter(
+                &db,
+                id,
+                stream_key,
+                &data,
+                Some(&transaction),
+                &error,
+                item.tries,
+            )
+            .await
+        }
+    }
+}
+
+// Only acks the message once it is safely stored, otherwise it is left for redelivery.
+async fn dead_letter(
+    db: &DatabaseConnection,
+    id: String,
+    stream_key: &str,
+    payload: &[u8],
+    transaction: Option<&TransactionInfo<'_>>,
+    error: &str,
+    tries: usize,
+) -> Option<String> {
+    match store_failed_transaction_update(db, stream_key, payload, transaction, error, tries).await
+    {
+        Ok(()) => Some(id),
+        Err(e) => {
+            error!("Failed to dead-letter transaction {}: {}", id, e);
+            None
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{config::IndexerConfig, error::IndexerError, metric};
+use cadence_macros::{is_global_default_set, statsd_count};
+use crypto::{hmac::Hmac, mac::Mac, sha2::Sha256};
+use log::error;
+use reqwest::{header::CONTENT_TYPE, Client};
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbBackend, FromQueryResult, SqlxPostgresConnector,
+    Statement,
+};
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+use tokio::{task::JoinHandle, time::sleep};
+
+pub const SIGNATURE_HEADER: &str = "X-RWA-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-RWA-Timestamp";
+pub const EVENT_HEADER: &str = "X-RWA-Event";
+pub const DELIVERY_HEADER: &str = "X-RWA-Delivery";
+
+const DELIVERY_BATCH_SIZE: i64 = 50;
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+// How long a claimed batch may take before other workers pick its rows up again.
+const CLAIM_TIMEOUT: Duration = Duration::from_secs(60);
+
+// Retries back off exponentially from the base delay, up to the max delay.
+const BASE_RETRY_DELAY_SECS: f64 = 10.0;
+const MAX_RETRY_DELAY_SECS: f64 = 3600.0;
+
+#[derive(FromQueryResult)]
+struct PendingDelivery {
+    id: i64,
+    event_type: String,
+    payload: serde_json::Value,
+    attempts: i32,
+    url: String,
+    secret: String,
+}
+
+/// Hex encoded HMAC-SHA256 of `{timestamp}.{body}` keyed with the subscription's secret, sent in
+/// `X-RWA-Signature` so receivers can authenticate the delivery and reject replays.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = Hmac::new(Sha256::new(), secret.as_bytes());
+    mac.input(format!("{}.{}", timestamp, body).as_bytes());
+    hex::encode(mac.result().code())
+}
+
+/// POSTs pending `webhook_delivery` rows to their subscription's URL. Rows are claimed with
+/// `SKIP LOCKED`, so any number of workers across pods can share the queue. Failed attempts are
+/// retried with exponential backoff until `webhook_max_attempts`, after which the row is marked
+/// failed. The row keeps the outcome of its latest attempt.
+pub fn webhook_worker(pool: Pool<Postgres>, config: IndexerConfig) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let db = SqlxPostgresConnector::from_sqlx_postgres_pool(pool);
+        let client = match Client::builder().timeout(REQUEST_TIMEOUT).build() {
+            Ok(client) => client,
+            Err(e) => {
+                error!("Failed to build webhook client: {}", e);
+                return;
+            }
+        };
+        let max_attempts = config.get_webhook_max_attempts() as i32;
+        loop {
+            match deliver_batch(&db, &client, max_attempts).await {
+                Ok(0) => sleep(POLL_INTERVAL).await,
+                Ok(_) => {}
+                Err(e) => {
+                    error!("Failed to deliver webhooks: {}", e);
+                    sleep(POLL_INTERVAL).await;
+                }
+            }
+        }
+    })
+}
+
+/// Claims a batch of due deliveries by marking them `in_flight` for [`CLAIM_TIMEOUT`], then
+/// sends them without holding a transaction or row locks. Rows left `in_flight` by a worker that
+/// died mid-batch are claimed again once their claim expires.
+async fn deliver_batch(
+    db: &DatabaseConnection,
+    client: &Client,
+    max_attempts: i32,
+) -> Result<usize, IndexerError> {
+    let deliveries = PendingDelivery::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "UPDATE webhook_delivery d \
+         SET status = 'in_flight', locked_until = now() + make_interval(secs => $2) \
+         FROM webhook_subscription s \
+         WHERE s.id = d.subscription_id AND d.id IN ( \
+             SELECT due.id FROM webhook_delivery due \
+             INNER JOIN webhook_subscription sub ON sub.id = due.subscription_id \
+             WHERE sub.active AND due.next_attempt_at <= now() \
+                 AND (due.status = 'pending' \
+                     OR (due.status = 'in_flight' AND due.locked_until <= now())) \
+             ORDER BY due.next_attempt_at \
+             LIMIT $1 \
+             FOR UPDATE OF due SKIP LOCKED \
+         ) \
+         RETURNING d.id, d.event_type, d.payload, d.attempts, s.url, s.secret",
+        vec![
+            DELIVERY_BATCH_SIZE.into(),
+            CLAIM_TIMEOUT.as_secs_f64().into(),
+        ],
+    ))
+    .all(db)
+    .await?;
+
+    let outcomes =
+        futures::future::join_all(deliveries.iter().map(|delivery| send(client, delivery))).await;
+    for (delivery, (response_status, error)) in deliveries.iter().zip(outcomes) {
+        // The row is retried once its claim expires when the outcome can't be recorded.
+        if let Err(e) = record_attempt(db, delivery, response_status, error, max_attempts).await {
+            error!("Failed to record webhook delivery {}: {}", delivery.id, e);
+        }
+    }
+    Ok(deliveries.len())
+}
+
+/// Returns the response status, if any, and the error when the receiver didn't answer with 2xx.
+async fn send(client: &Client, delivery: &PendingDelivery) -> (Option<u16>, Option<String>) {
+    let body = delivery.payload.to_string();
+    let timestamp = chrono::Utc::now().timestamp();
+    let response = client
+        .post(&delivery.url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, &delivery.event_type)
+        .header(DELIVERY_HEADER, delivery.id)
+        .header(TIMESTAMP_HEADER, timestamp)
+        .header(SIGNATURE_HEADER, sign(&delivery.secret, timestamp, &body))
+        .body(body)
+        .send()
+        .await;
+    match response {
+        Ok(response) if response.status().is_success() => (Some(response.status().as_u16()), None),
+        Ok(response) => (
+            Some(response.status().as_u16()),
+            Some(format!("Receiver responded with {}", response.status())),
+        ),
+        Err(e) => (None, Some(e.to_string())),
+    }
+}
+
+async fn record_attempt(
+    db: &DatabaseConnection,
+    delivery: &PendingDelivery,
+    response_status: Option<u16>,
+    error: Option<String>,
+    max_attempts: i32,
+) -> Result<(), IndexerError> {
+    let attempts = delivery.attempts + 1;
+    let status = match error {
+        None => "delivered",
+        Some(_) if attempts >= max_attempts => "failed",
+        Some(_) => "pending",
+    };
+    let retry_delay = (BASE_RETRY_DELAY_SECS * 2f64.powi(attempts - 1)).min(MAX_RETRY_DELAY_SECS);
+
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "UPDATE webhook_delivery SET status = $2, attempts = $3, response_status = $4, \
+             last_error = $5, last_attempt_at = now(), locked_until = NULL, \
+             next_attempt_at = now() + make_interval(secs => $6), \
+             delivered_at = CASE WHEN $7 THEN now() END \
+         WHERE id = $1 AND status = 'in_flight'",
+        vec![
+            delivery.id.into(),
+            status.into(),
+            attempts.into(),
+            response_status.map(i32::from).into(),
+            error.into(),
+            retry_delay.into(),
+            (status == "delivered").into(),
+        ],
+    ))
+    .await?;
+
+    metric! {
+        statsd_count!("indexer.webhook.attempt", 1, "status" => status);
+    }
+    Ok(())
+}
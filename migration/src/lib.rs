@@ -1,4 +1,5 @@
 pub use sea_orm_migration::prelude::*;
+use std::marker::PhantomData;
 
 mod m20220101_000001_init;
 mod m20240226_180047_create_asset_controller;
@@ -6,6 +7,21 @@ mod m20240226_180544_create_data_registry;
 mod m20240226_180606_create_identity_registry;
 mod m20240226_180630_create_policy_engine;
 mod m20240301_101641_create_inital_indices;
+mod m20240311_093012_create_identity_account_owner_index;
+mod m20240314_154230_create_transaction_approval_account;
+mod m20240318_101530_add_closed_columns;
+mod m20240320_142211_create_account_history;
+mod m20240322_091544_create_backfill_progress;
+mod m20240325_160302_create_failed_account_update;
+mod m20240327_113045_create_rwa_instruction;
+mod m20240329_084410_create_token_accounts;
+mod m20240402_101215_create_authority_indices;
+mod m20240405_112230_create_webhooks;
+mod m20240408_094517_create_raw_account;
+mod m20240409_093120_create_registry_child_indices;
+mod m20240410_083015_create_change_event_outbox;
+mod m20240411_101240_create_holder_backfill;
+mod m20240411_134502_create_failed_transaction_update;
 mod model;
 
 pub struct Migrator;
@@ -20,6 +36,39 @@ impl MigratorTrait for Migrator {
             Box::new(m20240226_180606_create_identity_registry::Migration),
             Box::new(m20240226_180630_create_policy_engine::Migration),
             Box::new(m20240301_101641_create_inital_indices::Migration),
+            Box::new(m20240311_093012_create_identity_account_owner_index::Migration),
+            Box::new(m20240314_154230_create_transaction_approval_account::Migration),
+            Box::new(m20240318_101530_add_closed_columns::Migration),
+            Box::new(m20240320_142211_create_account_history::Migration),
+            Box::new(m20240322_091544_create_backfill_progress::Migration),
+            Box::new(m20240325_160302_create_failed_account_update::Migration),
+            Box::new(m20240327_113045_create_rwa_instruction::Migration),
+            Box::new(m20240329_084410_create_token_accounts::Migration),
+            Box::new(m20240402_101215_create_authority_indices::Migration),
+            Box::new(m20240405_112230_create_webhooks::Migration),
+            Box::new(m20240408_094517_create_raw_account::Migration),
+            Box::new(m20240409_093120_create_registry_child_indices::Migration),
+            Box::new(m20240410_083015_create_change_event_outbox::Migration),
+            Box::new(m20240411_101240_create_holder_backfill::Migration),
+            Box::new(m20240411_134502_create_failed_transaction_update::Migration),
         ]
     }
 }
+
+/// Migrations of programs registered on the indexer next to the RWA programs.
+pub trait ExtraMigrations: Send {
+    /// Their names have to be unique and sort after every RWA migration.
+    fn migrations() -> Vec<Box<dyn MigrationTrait>>;
+}
+
+/// Runs the RWA migrations followed by those of `T`.
+pub struct MigratorWith<T>(PhantomData<T>);
+
+#[async_trait::async_trait]
+impl<T: ExtraMigrations> MigratorTrait for MigratorWith<T> {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        let mut migrations = Migrator::migrations();
+        migrations.extend(T::migrations());
+        migrations
+    }
+}
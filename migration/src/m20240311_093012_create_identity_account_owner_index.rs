@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::IdentityAccount;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_identity_account_owner")
+                    .col(IdentityAccount::Owner)
+                    .table(IdentityAccount::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_identity_account_owner")
+                    .table(IdentityAccount::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
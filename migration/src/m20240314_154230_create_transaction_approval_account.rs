@@ -0,0 +1,104 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+
+use crate::model::table::TransactionApprovalAccount;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TransactionApprovalAccount::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TransactionApprovalAccount::Id)
+                            .binary()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(TransactionApprovalAccount::AssetMint)
+                            .binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TransactionApprovalAccount::FromTokenAccount)
+                            .binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TransactionApprovalAccount::ToTokenAccount)
+                            .binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TransactionApprovalAccount::ExpiryTimestamp)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TransactionApprovalAccount::Closed)
+                            .boolean()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TransactionApprovalAccount::SlotUpdated)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TransactionApprovalAccount::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(TransactionApprovalAccount::LastUpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                r#"ALTER TABLE transaction_approval_account ADD COLUMN amount "uint64_t" NOT NULL;"#
+                    .to_string(),
+            ))
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_transaction_approval_account_mint")
+                    .col(TransactionApprovalAccount::AssetMint)
+                    .table(TransactionApprovalAccount::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(TransactionApprovalAccount::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,213 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::{
+    AssetController, DataAccount, DataRegistry, IdentityAccount, IdentityRegistry, PolicyAccount,
+    PolicyEngine, TrackerAccount, TransactionApprovalAccount,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Child accounts were created without a closed flag.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TrackerAccount::Table)
+                    .add_column(
+                        ColumnDef::new(TrackerAccount::Closed)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .add_column(ColumnDef::new(TrackerAccount::ClosedAtSlot).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DataAccount::Table)
+                    .add_column(
+                        ColumnDef::new(DataAccount::Closed)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .add_column(ColumnDef::new(DataAccount::ClosedAtSlot).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(IdentityAccount::Table)
+                    .add_column(
+                        ColumnDef::new(IdentityAccount::Closed)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .add_column(ColumnDef::new(IdentityAccount::ClosedAtSlot).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PolicyAccount::Table)
+                    .add_column(
+                        ColumnDef::new(PolicyAccount::Closed)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .add_column(ColumnDef::new(PolicyAccount::ClosedAtSlot).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AssetController::Table)
+                    .add_column(ColumnDef::new(AssetController::ClosedAtSlot).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DataRegistry::Table)
+                    .add_column(ColumnDef::new(DataRegistry::ClosedAtSlot).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(IdentityRegistry::Table)
+                    .add_column(ColumnDef::new(IdentityRegistry::ClosedAtSlot).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PolicyEngine::Table)
+                    .add_column(ColumnDef::new(PolicyEngine::ClosedAtSlot).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TransactionApprovalAccount::Table)
+                    .add_column(
+                        ColumnDef::new(TransactionApprovalAccount::ClosedAtSlot).big_integer(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TrackerAccount::Table)
+                    .drop_column(TrackerAccount::Closed)
+                    .drop_column(TrackerAccount::ClosedAtSlot)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DataAccount::Table)
+                    .drop_column(DataAccount::Closed)
+                    .drop_column(DataAccount::ClosedAtSlot)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(IdentityAccount::Table)
+                    .drop_column(IdentityAccount::Closed)
+                    .drop_column(IdentityAccount::ClosedAtSlot)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PolicyAccount::Table)
+                    .drop_column(PolicyAccount::Closed)
+                    .drop_column(PolicyAccount::ClosedAtSlot)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AssetController::Table)
+                    .drop_column(AssetController::ClosedAtSlot)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DataRegistry::Table)
+                    .drop_column(DataRegistry::ClosedAtSlot)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(IdentityRegistry::Table)
+                    .drop_column(IdentityRegistry::ClosedAtSlot)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PolicyEngine::Table)
+                    .drop_column(PolicyEngine::ClosedAtSlot)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TransactionApprovalAccount::Table)
+                    .drop_column(TransactionApprovalAccount::ClosedAtSlot)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
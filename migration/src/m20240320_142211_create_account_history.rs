@@ -0,0 +1,97 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::AccountHistory;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AccountHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AccountHistory::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AccountHistory::Pubkey).binary().not_null())
+                    .col(
+                        ColumnDef::new(AccountHistory::AccountType)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AccountHistory::AssetMint).binary())
+                    .col(ColumnDef::new(AccountHistory::Parent).binary())
+                    .col(
+                        ColumnDef::new(AccountHistory::Slot)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AccountHistory::Closed).boolean().not_null())
+                    .col(
+                        ColumnDef::new(AccountHistory::Data)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AccountHistory::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_account_history_pubkey_slot")
+                    .col(AccountHistory::Pubkey)
+                    .col(AccountHistory::Slot)
+                    .table(AccountHistory::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_account_history_mint_type_slot")
+                    .col(AccountHistory::AssetMint)
+                    .col(AccountHistory::AccountType)
+                    .col(AccountHistory::Slot)
+                    .table(AccountHistory::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_account_history_parent_type_slot")
+                    .col(AccountHistory::Parent)
+                    .col(AccountHistory::AccountType)
+                    .col(AccountHistory::Slot)
+                    .table(AccountHistory::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AccountHistory::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::BackfillProgress;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(BackfillProgress::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(BackfillProgress::ProgramId)
+                            .binary()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(BackfillProgress::LastPubkey).binary())
+                    .col(
+                        ColumnDef::new(BackfillProgress::AccountsSent)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(BackfillProgress::Slot).big_integer())
+                    .col(
+                        ColumnDef::new(BackfillProgress::Completed)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(BackfillProgress::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(BackfillProgress::LastUpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BackfillProgress::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
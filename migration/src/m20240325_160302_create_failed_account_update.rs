@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::FailedAccountUpdate;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(FailedAccountUpdate::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(FailedAccountUpdate::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(FailedAccountUpdate::Pubkey).binary())
+                    .col(ColumnDef::new(FailedAccountUpdate::Owner).binary())
+                    .col(ColumnDef::new(FailedAccountUpdate::Slot).big_integer())
+                    .col(
+                        ColumnDef::new(FailedAccountUpdate::Stream)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(FailedAccountUpdate::Payload)
+                            .binary()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(FailedAccountUpdate::Error).text().not_null())
+                    .col(
+                        ColumnDef::new(FailedAccountUpdate::Tries)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(FailedAccountUpdate::ReplayedAt).timestamp())
+                    .col(
+                        ColumnDef::new(FailedAccountUpdate::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_failed_account_update_pubkey")
+                    .col(FailedAccountUpdate::Pubkey)
+                    .table(FailedAccountUpdate::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(FailedAccountUpdate::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
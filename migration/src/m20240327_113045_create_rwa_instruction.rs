@@ -0,0 +1,119 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+
+use crate::model::table::RwaInstruction;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RwaInstruction::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RwaInstruction::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(RwaInstruction::Signature)
+                            .binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RwaInstruction::Slot)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RwaInstruction::ProgramId)
+                            .binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RwaInstruction::InstructionIndex)
+                            .integer()
+                            .not_null(),
+                    )
+                    // 0 for a top-level instruction, n for the n-th instruction it invoked.
+                    .col(
+                        ColumnDef::new(RwaInstruction::InnerIndex)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RwaInstruction::InstructionName)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RwaInstruction::Accounts)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(RwaInstruction::Args).json_binary())
+                    .col(ColumnDef::new(RwaInstruction::Data).binary().not_null())
+                    .col(ColumnDef::new(RwaInstruction::Signer).binary())
+                    .col(
+                        ColumnDef::new(RwaInstruction::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .unique()
+                    .name("idx_rwa_instruction_position")
+                    .col(RwaInstruction::Signature)
+                    .col(RwaInstruction::InstructionIndex)
+                    .col(RwaInstruction::InnerIndex)
+                    .table(RwaInstruction::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_rwa_instruction_program_slot")
+                    .col(RwaInstruction::ProgramId)
+                    .col(RwaInstruction::Slot)
+                    .table(RwaInstruction::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Lets "every instruction that touched this account" be answered with `accounts ? $1`.
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "CREATE INDEX IF NOT EXISTS idx_rwa_instruction_accounts ON rwa_instruction USING GIN (accounts);"
+                    .to_string(),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RwaInstruction::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+
+use crate::model::table::{TokenAccount, TokenMint};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TokenMint::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TokenMint::Id)
+                            .binary()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TokenMint::Decimals).integer().not_null())
+                    .col(ColumnDef::new(TokenMint::MintAuthority).binary())
+                    .col(ColumnDef::new(TokenMint::FreezeAuthority).binary())
+                    .col(ColumnDef::new(TokenMint::TransferHookProgramId).binary())
+                    .col(ColumnDef::new(TokenMint::TransferHookAuthority).binary())
+                    .col(ColumnDef::new(TokenMint::MetadataAddress).binary())
+                    .col(ColumnDef::new(TokenMint::MetadataPointerAuthority).binary())
+                    .col(
+                        ColumnDef::new(TokenMint::Closed)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(TokenMint::ClosedAtSlot).big_integer())
+                    .col(
+                        ColumnDef::new(TokenMint::SlotUpdated)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TokenMint::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(TokenMint::LastUpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                r#"ALTER TABLE token_mint ADD COLUMN supply "uint64_t" NOT NULL;"#.to_string(),
+            ))
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TokenAccount::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TokenAccount::Id)
+                            .binary()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TokenAccount::Mint).binary().not_null())
+                    .col(ColumnDef::new(TokenAccount::Owner).binary().not_null())
+                    .col(ColumnDef::new(TokenAccount::Delegate).binary())
+                    .col(ColumnDef::new(TokenAccount::Frozen).boolean().not_null())
+                    .col(
+                        ColumnDef::new(TokenAccount::Closed)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(TokenAccount::ClosedAtSlot).big_integer())
+                    .col(
+                        ColumnDef::new(TokenAccount::SlotUpdated)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TokenAccount::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(TokenAccount::LastUpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                r#"ALTER TABLE token_account ADD COLUMN amount "uint64_t" NOT NULL;"#.to_string(),
+            ))
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_token_account_mint")
+                    .col(TokenAccount::Mint)
+                    .table(TokenAccount::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_token_account_owner")
+                    .col(TokenAccount::Owner)
+                    .table(TokenAccount::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TokenAccount::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(TokenMint::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::{AssetController, DataRegistry, IdentityRegistry, PolicyEngine};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_asset_controller_authority")
+                    .col(AssetController::Authority)
+                    .table(AssetController::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_asset_controller_delegate")
+                    .col(AssetController::Delegate)
+                    .table(AssetController::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_data_registry_authority")
+                    .col(DataRegistry::Authority)
+                    .table(DataRegistry::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_identity_registry_authority")
+                    .col(IdentityRegistry::Authority)
+                    .table(IdentityRegistry::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_identity_registry_delegate")
+                    .col(IdentityRegistry::Delegate)
+                    .table(IdentityRegistry::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_policy_engine_authority")
+                    .col(PolicyEngine::Authority)
+                    .table(PolicyEngine::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_policy_engine_delegate")
+                    .col(PolicyEngine::Delegate)
+                    .table(PolicyEngine::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_asset_controller_authority")
+                    .table(AssetController::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_asset_controller_delegate")
+                    .table(AssetController::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_data_registry_authority")
+                    .table(DataRegistry::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_identity_registry_authority")
+                    .table(IdentityRegistry::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_identity_registry_delegate")
+                    .table(IdentityRegistry::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_policy_engine_authority")
+                    .table(PolicyEngine::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_policy_engine_delegate")
+                    .table(PolicyEngine::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::{WebhookDelivery, WebhookSubscription};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebhookSubscription::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WebhookSubscription::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(WebhookSubscription::Url).text().not_null())
+                    .col(
+                        ColumnDef::new(WebhookSubscription::Secret)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(WebhookSubscription::Mint).binary())
+                    .col(ColumnDef::new(WebhookSubscription::Program).string())
+                    .col(ColumnDef::new(WebhookSubscription::EventType).string())
+                    .col(
+                        ColumnDef::new(WebhookSubscription::Active)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscription::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebhookDelivery::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WebhookDelivery::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDelivery::SubscriptionId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDelivery::EventType)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(WebhookDelivery::Pubkey).binary().not_null())
+                    .col(
+                        ColumnDef::new(WebhookDelivery::Slot)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDelivery::Payload)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDelivery::Status)
+                            .string()
+                            .not_null()
+                            .default("pending"),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDelivery::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDelivery::NextAttemptAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(WebhookDelivery::LockedUntil).timestamp())
+                    .col(ColumnDef::new(WebhookDelivery::LastAttemptAt).timestamp())
+                    .col(ColumnDef::new(WebhookDelivery::ResponseStatus).integer())
+                    .col(ColumnDef::new(WebhookDelivery::LastError).text())
+                    .col(ColumnDef::new(WebhookDelivery::DeliveredAt).timestamp())
+                    .col(
+                        ColumnDef::new(WebhookDelivery::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_webhook_delivery_subscription")
+                            .from(WebhookDelivery::Table, WebhookDelivery::SubscriptionId)
+                            .to(WebhookSubscription::Table, WebhookSubscription::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_webhook_delivery_status_next_attempt")
+                    .col(WebhookDelivery::Status)
+                    .col(WebhookDelivery::NextAttemptAt)
+                    .table(WebhookDelivery::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_webhook_delivery_subscription")
+                    .col(WebhookDelivery::SubscriptionId)
+                    .col(WebhookDelivery::CreatedAt)
+                    .table(WebhookDelivery::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(WebhookDelivery::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(WebhookSubscription::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
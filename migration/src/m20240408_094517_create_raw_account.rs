@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::RawAccount;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RawAccount::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RawAccount::Id)
+                            .binary()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(RawAccount::ProgramId).binary().not_null())
+                    .col(ColumnDef::new(RawAccount::AccountType).string().not_null())
+                    // The account decoded with the program's IDL.
+                    .col(
+                        ColumnDef::new(RawAccount::RawDecoded)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RawAccount::Closed)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(RawAccount::ClosedAtSlot).big_integer())
+                    .col(
+                        ColumnDef::new(RawAccount::SlotUpdated)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RawAccount::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_raw_account_program_type")
+                    .col(RawAccount::ProgramId)
+                    .col(RawAccount::AccountType)
+                    .table(RawAccount::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RawAccount::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::{DataAccount, IdentityAccount, PolicyAccount};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_identity_account_registry_closed")
+                    .col(IdentityAccount::IdentityRegistry)
+                    .col(IdentityAccount::Closed)
+                    .table(IdentityAccount::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_data_account_registry_closed")
+                    .col(DataAccount::DataRegistry)
+                    .col(DataAccount::Closed)
+                    .table(DataAccount::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_policy_account_engine_closed")
+                    .col(PolicyAccount::PolicyEngine)
+                    .col(PolicyAccount::Closed)
+                    .table(PolicyAccount::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_identity_account_registry_closed")
+                    .table(IdentityAccount::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_data_account_registry_closed")
+                    .table(DataAccount::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_policy_account_engine_closed")
+                    .table(PolicyAccount::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
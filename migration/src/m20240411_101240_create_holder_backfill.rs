@@ -0,0 +1,56 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::HolderBackfill;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(HolderBackfill::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HolderBackfill::Mint)
+                            .binary()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(HolderBackfill::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(HolderBackfill::LastError).text())
+                    .col(
+                        ColumnDef::new(HolderBackfill::NextAttemptAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(HolderBackfill::CompletedAt).timestamp())
+                    .col(
+                        ColumnDef::new(HolderBackfill::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HolderBackfill::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::FailedTransactionUpdate;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(FailedTransactionUpdate::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(FailedTransactionUpdate::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(FailedTransactionUpdate::Signature).binary())
+                    .col(ColumnDef::new(FailedTransactionUpdate::Slot).big_integer())
+                    .col(
+                        ColumnDef::new(FailedTransactionUpdate::Stream)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(FailedTransactionUpdate::Payload)
+                            .binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(FailedTransactionUpdate::Error)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(FailedTransactionUpdate::Tries)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(FailedTransactionUpdate::ReplayedAt).timestamp())
+                    .col(
+                        ColumnDef::new(FailedTransactionUpdate::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_failed_transaction_update_signature")
+                    .col(FailedTransactionUpdate::Signature)
+                    .table(FailedTransactionUpdate::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(FailedTransactionUpdate::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
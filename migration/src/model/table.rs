@@ -58,6 +58,7 @@ pub enum AssetController {
     Authority,
     Delegate,
     Closed,
+    ClosedAtSlot,
     SlotUpdated,
     CreatedAt,
     LastUpdatedAt,
@@ -72,6 +73,8 @@ pub enum TrackerAccount {
     Owner,
     TransferAmounts,
     TransferTimestamps,
+    Closed,
+    ClosedAtSlot,
     SlotUpdated,
     CreatedAt,
     LastUpdatedAt,
@@ -88,6 +91,7 @@ pub enum DataRegistry {
     Version,
     Delegate,
     Closed,
+    ClosedAtSlot,
     SlotUpdated,
     CreatedAt,
     LastUpdatedAt,
@@ -104,6 +108,8 @@ pub enum DataAccount {
     DataType,
     Name,
     Uri,
+    Closed,
+    ClosedAtSlot,
     SlotUpdated,
     CreatedAt,
     LastUpdatedAt,
@@ -120,6 +126,7 @@ pub enum IdentityRegistry {
     Version,
     Delegate,
     Closed,
+    ClosedAtSlot,
     SlotUpdated,
     CreatedAt,
     LastUpdatedAt,
@@ -135,6 +142,8 @@ pub enum IdentityAccount {
     Owner,
     IdentityRegistry,
     Levels,
+    Closed,
+    ClosedAtSlot,
     SlotUpdated,
     CreatedAt,
     LastUpdatedAt,
@@ -153,6 +162,7 @@ pub enum PolicyEngine {
     Policies,
     Version,
     Closed,
+    ClosedAtSlot,
     SlotUpdated,
     CreatedAt,
     LastUpdatedAt,
@@ -170,7 +180,213 @@ pub enum PolicyAccount {
     ComparsionType,
     TotalLimit,
     Timeframe,
+    Closed,
+    ClosedAtSlot,
+    SlotUpdated,
+    CreatedAt,
+    LastUpdatedAt,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum TransactionApprovalAccount {
+    #[iden = "transaction_approval_account"]
+    Table,
+    Id,
+    AssetMint,
+    FromTokenAccount,
+    ToTokenAccount,
+    Amount,
+    ExpiryTimestamp,
+    Closed,
+    ClosedAtSlot,
+    SlotUpdated,
+    CreatedAt,
+    LastUpdatedAt,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum AccountHistory {
+    #[iden = "account_history"]
+    Table,
+    Id,
+    Pubkey,
+    AccountType,
+    AssetMint,
+    Parent,
+    Slot,
+    Closed,
+    Data,
+    CreatedAt,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum BackfillProgress {
+    #[iden = "backfill_progress"]
+    Table,
+    ProgramId,
+    LastPubkey,
+    AccountsSent,
+    Slot,
+    Completed,
+    CreatedAt,
+    LastUpdatedAt,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum FailedAccountUpdate {
+    #[iden = "failed_account_update"]
+    Table,
+    Id,
+    Pubkey,
+    Owner,
+    Slot,
+    Stream,
+    Payload,
+    Error,
+    Tries,
+    ReplayedAt,
+    CreatedAt,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum FailedTransactionUpdate {
+    #[iden = "failed_transaction_update"]
+    Table,
+    Id,
+    Signature,
+    Slot,
+    Stream,
+    Payload,
+    Error,
+    Tries,
+    ReplayedAt,
+    CreatedAt,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum RwaInstruction {
+    #[iden = "rwa_instruction"]
+    Table,
+    Id,
+    Signature,
+    Slot,
+    ProgramId,
+    InstructionIndex,
+    InnerIndex,
+    InstructionName,
+    Accounts,
+    Args,
+    Data,
+    Signer,
+    CreatedAt,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum TokenMint {
+    #[iden = "token_mint"]
+    Table,
+    Id,
+    Supply,
+    Decimals,
+    MintAuthority,
+    FreezeAuthority,
+    TransferHookProgramId,
+    TransferHookAuthority,
+    MetadataAddress,
+    MetadataPointerAuthority,
+    Closed,
+    ClosedAtSlot,
     SlotUpdated,
     CreatedAt,
     LastUpdatedAt,
 }
+
+#[derive(Copy, Clone, Iden)]
+pub enum TokenAccount {
+    #[iden = "token_account"]
+    Table,
+    Id,
+    Mint,
+    Owner,
+    Amount,
+    Delegate,
+    Frozen,
+    Closed,
+    ClosedAtSlot,
+    SlotUpdated,
+    CreatedAt,
+    LastUpdatedAt,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum WebhookSubscription {
+    #[iden = "webhook_subscription"]
+    Table,
+    Id,
+    Url,
+    Secret,
+    Mint,
+    Program,
+    EventType,
+    Active,
+    CreatedAt,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum WebhookDelivery {
+    #[iden = "webhook_delivery"]
+    Table,
+    Id,
+    SubscriptionId,
+    EventType,
+    Pubkey,
+    Slot,
+    Payload,
+    Status,
+    Attempts,
+    NextAttemptAt,
+    LockedUntil,
+    LastAttemptAt,
+    ResponseStatus,
+    LastError,
+    DeliveredAt,
+    CreatedAt,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum RawAccount {
+    #[iden = "raw_account"]
+    Table,
+    Id,
+    ProgramId,
+    AccountType,
+    RawDecoded,
+    Closed,
+    ClosedAtSlot,
+    SlotUpdated,
+    CreatedAt,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum ChangeEventOutbox {
+    #[iden = "change_event_outbox"]
+    Table,
+    Id,
+    EventType,
+    Payload,
+    Attempts,
+    LastError,
+    CreatedAt,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum HolderBackfill {
+    #[iden = "holder_backfill"]
+    Table,
+    Mint,
+    Attempts,
+    LastError,
+    NextAttemptAt,
+    CompletedAt,
+    CreatedAt,
+}
@@ -20,6 +20,8 @@ pub struct Model {
     pub uri: String,
     pub data_registry: Vec<u8>,
     pub data_type: DataAccountType,
+    pub closed: bool,
+    pub closed_at_slot: Option<i64>,
     pub slot_updated: i64,
     pub created_at: DateTime,
     pub last_updated_at: DateTime,
@@ -32,6 +34,8 @@ pub enum Column {
     Uri,
     DataRegistry,
     DataType,
+    Closed,
+    ClosedAtSlot,
     SlotUpdated,
     CreatedAt,
     LastUpdatedAt,
@@ -61,6 +65,8 @@ impl ColumnTrait for Column {
             Self::Uri => ColumnType::String(None).def(),
             Self::DataRegistry => ColumnType::Binary.def(),
             Self::DataType => DataAccountType::db_type(),
+            Self::Closed => ColumnType::Boolean.def(),
+            Self::ClosedAtSlot => ColumnType::BigInteger.def().null(),
             Self::SlotUpdated => ColumnType::BigInteger.def(),
             Self::CreatedAt => ColumnType::DateTime.def(),
             Self::LastUpdatedAt => ColumnType::DateTime.def(),
@@ -20,6 +20,7 @@ pub struct Model {
     pub authority: Vec<u8>,
     pub version: DataRegistryVersion,
     pub closed: bool,
+    pub closed_at_slot: Option<i64>,
     pub slot_updated: i64,
     pub created_at: DateTime,
     pub last_updated_at: DateTime,
@@ -32,6 +33,7 @@ pub enum Column {
     Authority,
     Version,
     Closed,
+    ClosedAtSlot,
     SlotUpdated,
     CreatedAt,
     LastUpdatedAt,
@@ -61,6 +63,7 @@ impl ColumnTrait for Column {
             Self::Authority => ColumnType::Binary.def(),
             Self::Version => DataRegistryVersion::db_type(),
             Self::Closed => ColumnType::Boolean.def(),
+            Self::ClosedAtSlot => ColumnType::BigInteger.def().null(),
             Self::SlotUpdated => ColumnType::BigInteger.def(),
             Self::CreatedAt => ColumnType::DateTime.def(),
             Self::LastUpdatedAt => ColumnType::DateTime.def(),
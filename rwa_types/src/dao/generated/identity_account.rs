@@ -20,6 +20,8 @@ pub struct Model {
     pub identity_registry: Vec<u8>,
     pub version: IdentityAccountVersion,
     pub levels: Option<Json>,
+    pub closed: bool,
+    pub closed_at_slot: Option<i64>,
     pub slot_updated: i64,
     pub created_at: DateTime,
     pub last_updated_at: DateTime,
@@ -32,6 +34,8 @@ pub enum Column {
     IdentityRegistry,
     Version,
     Levels,
+    Closed,
+    ClosedAtSlot,
     SlotUpdated,
     CreatedAt,
     LastUpdatedAt,
@@ -61,6 +65,8 @@ impl ColumnTrait for Column {
             Self::IdentityRegistry => ColumnType::Binary.def(),
             Self::Version => IdentityAccountVersion::db_type(),
             Self::Levels => ColumnType::JsonBinary.def().null(),
+            Self::Closed => ColumnType::Boolean.def(),
+            Self::ClosedAtSlot => ColumnType::BigInteger.def().null(),
             Self::SlotUpdated => ColumnType::BigInteger.def(),
             Self::CreatedAt => ColumnType::DateTime.def(),
             Self::LastUpdatedAt => ColumnType::DateTime.def(),
@@ -21,6 +21,7 @@ pub struct Model {
     pub delegate: Vec<u8>,
     pub version: IdentityRegistryVersion,
     pub closed: bool,
+    pub closed_at_slot: Option<i64>,
     pub slot_updated: i64,
     pub created_at: DateTime,
     pub last_updated_at: DateTime,
@@ -34,6 +35,7 @@ pub enum Column {
     Delegate,
     Version,
     Closed,
+    ClosedAtSlot,
     SlotUpdated,
     CreatedAt,
     LastUpdatedAt,
@@ -64,6 +66,7 @@ impl ColumnTrait for Column {
             Self::Delegate => ColumnType::Binary.def(),
             Self::Version => IdentityRegistryVersion::db_type(),
             Self::Closed => ColumnType::Boolean.def(),
+            Self::ClosedAtSlot => ColumnType::BigInteger.def().null(),
             Self::SlotUpdated => ColumnType::BigInteger.def(),
             Self::CreatedAt => ColumnType::DateTime.def(),
             Self::LastUpdatedAt => ColumnType::DateTime.def(),
@@ -2,12 +2,25 @@
 
 pub mod prelude;
 
+pub mod account_history;
 pub mod asset_controller;
+pub mod backfill_progress;
+pub mod change_event_outbox;
 pub mod data_account;
 pub mod data_registry;
+pub mod failed_account_update;
+pub mod failed_transaction_update;
+pub mod holder_backfill;
 pub mod identity_account;
 pub mod identity_registry;
 pub mod policy_account;
 pub mod policy_engine;
+pub mod raw_account;
+pub mod rwa_instruction;
 pub mod sea_orm_active_enums;
+pub mod token_account;
+pub mod token_mint;
 pub mod tracker_account;
+pub mod transaction_approval_account;
+pub mod webhook_delivery;
+pub mod webhook_subscription;
@@ -21,6 +21,8 @@ pub struct Model {
     pub identity_levels: Option<Json>,
     pub timeframe: Option<i64>,
     pub policy_type: PolicyAccountType,
+    pub closed: bool,
+    pub closed_at_slot: Option<i64>,
     pub slot_updated: i64,
     pub created_at: DateTime,
     pub last_updated_at: DateTime,
@@ -35,6 +37,8 @@ pub enum Column {
     IdentityLevels,
     Timeframe,
     PolicyType,
+    Closed,
+    ClosedAtSlot,
     SlotUpdated,
     CreatedAt,
     LastUpdatedAt,
@@ -66,6 +70,8 @@ impl ColumnTrait for Column {
             Self::IdentityLevels => ColumnType::JsonBinary.def().null(),
             Self::Timeframe => ColumnType::BigInteger.def().null(),
             Self::PolicyType => PolicyAccountType::db_type(),
+            Self::Closed => ColumnType::Boolean.def(),
+            Self::ClosedAtSlot => ColumnType::BigInteger.def().null(),
             Self::SlotUpdated => ColumnType::BigInteger.def(),
             Self::CreatedAt => ColumnType::DateTime.def(),
             Self::LastUpdatedAt => ColumnType::DateTime.def(),
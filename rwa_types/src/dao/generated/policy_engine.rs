@@ -23,6 +23,7 @@ pub struct Model {
     pub version: PolicyEngineVersion,
     pub policies: Option<Json>,
     pub closed: bool,
+    pub closed_at_slot: Option<i64>,
     pub slot_updated: i64,
     pub created_at: DateTime,
     pub last_updated_at: DateTime,
@@ -38,6 +39,7 @@ pub enum Column {
     Version,
     Policies,
     Closed,
+    ClosedAtSlot,
     SlotUpdated,
     CreatedAt,
     LastUpdatedAt,
@@ -70,6 +72,7 @@ impl ColumnTrait for Column {
             Self::Version => PolicyEngineVersion::db_type(),
             Self::Policies => ColumnType::JsonBinary.def().null(),
             Self::Closed => ColumnType::Boolean.def(),
+            Self::ClosedAtSlot => ColumnType::BigInteger.def().null(),
             Self::SlotUpdated => ColumnType::BigInteger.def(),
             Self::CreatedAt => ColumnType::DateTime.def(),
             Self::LastUpdatedAt => ColumnType::DateTime.def(),
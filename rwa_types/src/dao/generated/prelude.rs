@@ -1,10 +1,23 @@
 //! SeaORM Entity. Generated by sea-orm-codegen 0.9.3
 
+pub use super::account_history::Entity as AccountHistory;
 pub use super::asset_controller::Entity as AssetController;
+pub use super::backfill_progress::Entity as BackfillProgress;
+pub use super::change_event_outbox::Entity as ChangeEventOutbox;
 pub use super::data_account::Entity as DataAccount;
 pub use super::data_registry::Entity as DataRegistry;
+pub use super::failed_account_update::Entity as FailedAccountUpdate;
+pub use super::failed_transaction_update::Entity as FailedTransactionUpdate;
+pub use super::holder_backfill::Entity as HolderBackfill;
 pub use super::identity_account::Entity as IdentityAccount;
 pub use super::identity_registry::Entity as IdentityRegistry;
 pub use super::policy_account::Entity as PolicyAccount;
 pub use super::policy_engine::Entity as PolicyEngine;
+pub use super::raw_account::Entity as RawAccount;
+pub use super::rwa_instruction::Entity as RwaInstruction;
+pub use super::token_account::Entity as TokenAccount;
+pub use super::token_mint::Entity as TokenMint;
 pub use super::tracker_account::Entity as TrackerAccount;
+pub use super::transaction_approval_account::Entity as TransactionApprovalAccount;
+pub use super::webhook_delivery::Entity as WebhookDelivery;
+pub use super::webhook_subscription::Entity as WebhookSubscription;
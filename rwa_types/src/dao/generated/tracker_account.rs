@@ -19,6 +19,8 @@ pub struct Model {
     pub owner: Vec<u8>,
     pub transfer_amounts: Option<Json>,
     pub transfer_timestamps: Option<Json>,
+    pub closed: bool,
+    pub closed_at_slot: Option<i64>,
     pub slot_updated: i64,
     pub created_at: DateTime,
     pub last_updated_at: DateTime,
@@ -31,6 +33,8 @@ pub enum Column {
     Owner,
     TransferAmounts,
     TransferTimestamps,
+    Closed,
+    ClosedAtSlot,
     SlotUpdated,
     CreatedAt,
     LastUpdatedAt,
@@ -60,6 +64,8 @@ impl ColumnTrait for Column {
             Self::Owner => ColumnType::Binary.def(),
             Self::TransferAmounts => ColumnType::JsonBinary.def().null(),
             Self::TransferTimestamps => ColumnType::JsonBinary.def().null(),
+            Self::Closed => ColumnType::Boolean.def(),
+            Self::ClosedAtSlot => ColumnType::BigInteger.def().null(),
             Self::SlotUpdated => ColumnType::BigInteger.def(),
             Self::CreatedAt => ColumnType::DateTime.def(),
             Self::LastUpdatedAt => ColumnType::DateTime.def(),
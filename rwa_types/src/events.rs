@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+
+/// Channel used for account change notifications when none is configured.
+pub const DEFAULT_ACCOUNT_CHANGE_CHANNEL: &str = "rwa_account_changes";
+
+/// Payload of the `pg_notify` the indexer sends after a write to an account table passed the
+/// slot guard. Addresses are base58.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AccountChange {
+    pub table: String,
+    pub pubkey: String,
+    /// The asset the account belongs to, if it could be resolved.
+    pub mint: Option<String>,
+    /// The wallet owning the account, for identity, tracker and token accounts.
+    pub owner: Option<String>,
+    pub slot: i64,
+}
+
+/// Account changes webhook subscriptions can filter on.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventType {
+    IdentityAccountCreated,
+    IdentityLevelsChanged,
+    PolicyAdded,
+    PolicyUpdated,
+    PolicyRemoved,
+    AuthorityChanged,
+    DataAccountAdded,
+}
+
+impl WebhookEventType {
+    /// Name stored in `webhook_subscription.event_type` and `webhook_delivery.event_type`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEventType::IdentityAccountCreated => "identity_account_created",
+            WebhookEventType::IdentityLevelsChanged => "identity_levels_changed",
+            WebhookEventType::PolicyAdded => "policy_added",
+            WebhookEventType::PolicyUpdated => "policy_updated",
+            WebhookEventType::PolicyRemoved => "policy_removed",
+            WebhookEventType::AuthorityChanged => "authority_changed",
+            WebhookEventType::DataAccountAdded => "data_account_added",
+        }
+    }
+}
+
+/// Body of a webhook delivery. `previous` and `current` are the account's snapshots before and
+/// after the change, with byte columns base58 encoded.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEvent {
+    pub event_type: WebhookEventType,
+    pub program: String,
+    pub account_type: String,
+    pub pubkey: String,
+    pub mint: Option<String>,
+    pub slot: i64,
+    pub previous: Option<serde_json::Value>,
+    pub current: serde_json::Value,
+}
+
+/// Typed account change the indexer publishes to its change event stream once the write that
+/// caused it is committed. Addresses are base58; policy snapshots are encoded like
+/// `WebhookEvent::current`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum ChangeEvent {
+    #[serde(rename_all = "camelCase")]
+    IdentityAccountCreated {
+        identity_account: String,
+        owner: String,
+        mint: Option<String>,
+        levels: Vec<u8>,
+        slot: i64,
+    },
+    #[serde(rename_all = "camelCase")]
+    IdentityLevelsChanged {
+        identity_account: String,
+        owner: String,
+        mint: Option<String>,
+        old: Vec<u8>,
+        new: Vec<u8>,
+        slot: i64,
+    },
+    #[serde(rename_all = "camelCase")]
+    PolicyAdded {
+        policy_account: String,
+        mint: Option<String>,
+        policy: serde_json::Value,
+        slot: i64,
+    },
+    #[serde(rename_all = "camelCase")]
+    PolicyUpdated {
+        policy_account: String,
+        mint: Option<String>,
+        old: serde_json::Value,
+        new: serde_json::Value,
+        slot: i64,
+    },
+    #[serde(rename_all = "camelCase")]
+    PolicyRemoved {
+        policy_account: String,
+        mint: Option<String>,
+        slot: i64,
+    },
+    /// Authority or delegate of a registry changed. Data registries have no delegate.
+    #[serde(rename_all = "camelCase")]
+    AuthorityChanged {
+        account: String,
+        account_type: String,
+        mint: Option<String>,
+        old_authority: String,
+        new_authority: String,
+        old_delegate: Option<String>,
+        new_delegate: Option<String>,
+        slot: i64,
+    },
+    #[serde(rename_all = "camelCase")]
+    DataAccountAdded {
+        data_account: String,
+        data_registry: String,
+        mint: Option<String>,
+        slot: i64,
+    },
+}
+
+impl ChangeEvent {
+    pub fn event_type(&self) -> WebhookEventType {
+        match self {
+            ChangeEvent::IdentityAccountCreated { .. } => WebhookEventType::IdentityAccountCreated,
+            ChangeEvent::IdentityLevelsChanged { .. } => WebhookEventType::IdentityLevelsChanged,
+            ChangeEvent::PolicyAdded { .. } => WebhookEventType::PolicyAdded,
+            ChangeEvent::PolicyUpdated { .. } => WebhookEventType::PolicyUpdated,
+            ChangeEvent::PolicyRemoved { .. } => WebhookEventType::PolicyRemoved,
+            ChangeEvent::AuthorityChanged { .. } => WebhookEventType::AuthorityChanged,
+            ChangeEvent::DataAccountAdded { .. } => WebhookEventType::DataAccountAdded,
+        }
+    }
+}
@@ -1,4 +1,6 @@
 #[cfg(feature = "sql_types")]
 pub mod dao;
+#[cfg(feature = "json_types")]
+pub mod events;
 #[cfg(feature = "sql_types")]
 pub mod rapi;
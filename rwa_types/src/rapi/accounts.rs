@@ -1,7 +1,13 @@
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use crate::dao::{asset_controller, data_registry, identity_registry, policy_engine};
+use crate::dao::{
+    asset_controller, data_account, data_registry, identity_account, identity_registry,
+    policy_account, policy_engine, sea_orm_active_enums, tracker_account,
+    transaction_approval_account,
+};
+
+use super::Page;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct AssetControllerAccount {
@@ -11,6 +17,10 @@ pub struct AssetControllerAccount {
     pub delegate: String,
     pub version: u8,
     pub closed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trackers: Option<Page<TrackerAccount>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approvals: Option<Page<TransactionApproval>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -19,6 +29,8 @@ pub struct DataRegistryAccount {
     pub mint: String,
     pub version: u8,
     pub closed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_accounts: Option<Page<DataAccount>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -29,6 +41,8 @@ pub struct IdentityRegistryAccount {
     pub delegate: String,
     pub version: u8,
     pub closed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identities: Option<Page<IdentityAccount>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -40,6 +54,83 @@ pub struct PolicyEngine {
     pub policies: Vec<String>,
     pub version: u8,
     pub closed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy_accounts: Option<Page<PolicyAccount>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TrackerAccount {
+    pub address: String,
+    pub mint: String,
+    pub owner: String,
+    pub transfer_amounts: Vec<u64>,
+    pub transfer_timestamps: Vec<i64>,
+    pub closed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+pub enum ApprovalStatus {
+    Pending,
+    Expired,
+    Consumed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TransactionApproval {
+    pub address: String,
+    pub mint: String,
+    pub from_token_account: String,
+    pub to_token_account: String,
+    pub amount: String,
+    pub expiry_timestamp: i64,
+    pub status: ApprovalStatus,
+    pub slot_updated: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+pub enum DataAccountType {
+    Title,
+    Legal,
+    Tax,
+    Miscellaneous,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DataAccount {
+    pub address: String,
+    pub data_registry: String,
+    pub name: String,
+    pub uri: String,
+    pub data_type: DataAccountType,
+    pub closed: bool,
+    pub slot_updated: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IdentityAccount {
+    pub address: String,
+    pub identity_registry: String,
+    pub owner: String,
+    pub levels: Vec<u8>,
+    pub version: u8,
+    pub closed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+pub enum PolicyAccountType {
+    IdentityApproval,
+    TransactionAmountLimit,
+    TransactionAmountVelocity,
+    TransactionCountVelocity,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PolicyAccount {
+    pub address: String,
+    pub policy_engine: String,
+    pub policy_type: PolicyAccountType,
+    pub identity_levels: Vec<u8>,
+    pub closed: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -63,6 +154,8 @@ impl From<asset_controller::Model> for AssetControllerAccount {
             delegate: bs58::encode(asset.delegate).into_string(),
             version: asset.version as u8,
             closed: asset.closed,
+            trackers: None,
+            approvals: None,
         }
     }
 }
@@ -74,6 +167,7 @@ impl From<data_registry::Model> for DataRegistryAccount {
             mint: bs58::encode(data.asset_mint).into_string(),
             version: data.version as u8,
             closed: data.closed,
+            data_accounts: None,
         }
     }
 }
@@ -87,6 +181,7 @@ impl From<identity_registry::Model> for IdentityRegistryAccount {
             delegate: bs58::encode(identity.delegate).into_string(),
             version: identity.version as u8,
             closed: identity.closed,
+            identities: None,
         }
     }
 }
@@ -125,6 +220,136 @@ impl From<policy_engine::Model> for PolicyEngine {
             policies,
             version: policy.version as u8,
             closed: policy.closed,
+            policy_accounts: None,
+        }
+    }
+}
+
+/// Child accounts store their arrays wrapped in an object, e.g. `{"levels": [1, 2]}`.
+pub(crate) fn json_array<T: DeserializeOwned>(
+    value: Option<sea_orm::JsonValue>,
+    key: &str,
+) -> Vec<T> {
+    value
+        .and_then(|mut obj| obj.get_mut(key).map(sea_orm::JsonValue::take))
+        .and_then(|arr| serde_json::from_value(arr).ok())
+        .unwrap_or_default()
+}
+
+impl From<tracker_account::Model> for TrackerAccount {
+    fn from(tracker: tracker_account::Model) -> Self {
+        TrackerAccount {
+            address: bs58::encode(tracker.id).into_string(),
+            mint: bs58::encode(tracker.asset_mint).into_string(),
+            owner: bs58::encode(tracker.owner).into_string(),
+            transfer_amounts: json_array(tracker.transfer_amounts, "transfer_amounts"),
+            transfer_timestamps: json_array(tracker.transfer_timestamps, "transfer_timestamps"),
+            closed: tracker.closed,
+        }
+    }
+}
+
+impl TransactionApproval {
+    /// `now` is the unix timestamp the expiry is checked against.
+    pub fn new(approval: transaction_approval_account::Model, now: i64) -> Self {
+        // Approvals are closed by the transfer hook once the transfer they cover goes through.
+        let status = if approval.closed {
+            ApprovalStatus::Consumed
+        } else if approval.expiry_timestamp < now {
+            ApprovalStatus::Expired
+        } else {
+            ApprovalStatus::Pending
+        };
+
+        TransactionApproval {
+            address: bs58::encode(approval.id).into_string(),
+            mint: bs58::encode(approval.asset_mint).into_string(),
+            from_token_account: bs58::encode(approval.from_token_account).into_string(),
+            to_token_account: bs58::encode(approval.to_token_account).into_string(),
+            amount: approval.amount.to_string(),
+            expiry_timestamp: approval.expiry_timestamp,
+            status,
+            slot_updated: approval.slot_updated,
+        }
+    }
+}
+
+impl From<sea_orm_active_enums::DataAccountType> for DataAccountType {
+    fn from(data_type: sea_orm_active_enums::DataAccountType) -> Self {
+        match data_type {
+            sea_orm_active_enums::DataAccountType::Title => DataAccountType::Title,
+            sea_orm_active_enums::DataAccountType::Legal => DataAccountType::Legal,
+            sea_orm_active_enums::DataAccountType::Tax => DataAccountType::Tax,
+            sea_orm_active_enums::DataAccountType::Miscellaneous => DataAccountType::Miscellaneous,
+        }
+    }
+}
+
+impl From<DataAccountType> for sea_orm_active_enums::DataAccountType {
+    fn from(data_type: DataAccountType) -> Self {
+        match data_type {
+            DataAccountType::Title => sea_orm_active_enums::DataAccountType::Title,
+            DataAccountType::Legal => sea_orm_active_enums::DataAccountType::Legal,
+            DataAccountType::Tax => sea_orm_active_enums::DataAccountType::Tax,
+            DataAccountType::Miscellaneous => sea_orm_active_enums::DataAccountType::Miscellaneous,
+        }
+    }
+}
+
+impl From<data_account::Model> for DataAccount {
+    fn from(data: data_account::Model) -> Self {
+        DataAccount {
+            address: bs58::encode(data.id).into_string(),
+            data_registry: bs58::encode(data.data_registry).into_string(),
+            name: data.name,
+            uri: data.uri,
+            data_type: DataAccountType::from(data.data_type),
+            closed: data.closed,
+            slot_updated: data.slot_updated,
+        }
+    }
+}
+
+impl From<identity_account::Model> for IdentityAccount {
+    fn from(identity: identity_account::Model) -> Self {
+        IdentityAccount {
+            address: bs58::encode(identity.id).into_string(),
+            identity_registry: bs58::encode(identity.identity_registry).into_string(),
+            owner: bs58::encode(identity.owner).into_string(),
+            levels: json_array(identity.levels, "levels"),
+            version: identity.version as u8,
+            closed: identity.closed,
+        }
+    }
+}
+
+impl From<sea_orm_active_enums::PolicyAccountType> for PolicyAccountType {
+    fn from(policy_type: sea_orm_active_enums::PolicyAccountType) -> Self {
+        match policy_type {
+            sea_orm_active_enums::PolicyAccountType::IdentityApproval => {
+                PolicyAccountType::IdentityApproval
+            }
+            sea_orm_active_enums::PolicyAccountType::TransactionAmountLimit => {
+                PolicyAccountType::TransactionAmountLimit
+            }
+            sea_orm_active_enums::PolicyAccountType::TransactionAmountVelocity => {
+                PolicyAccountType::TransactionAmountVelocity
+            }
+            sea_orm_active_enums::PolicyAccountType::TransactionCountVelocity => {
+                PolicyAccountType::TransactionCountVelocity
+            }
+        }
+    }
+}
+
+impl From<policy_account::Model> for PolicyAccount {
+    fn from(policy: policy_account::Model) -> Self {
+        PolicyAccount {
+            address: bs58::encode(policy.id).into_string(),
+            policy_engine: bs58::encode(policy.policy_engine).into_string(),
+            policy_type: PolicyAccountType::from(policy.policy_type),
+            identity_levels: json_array(policy.identity_levels, "identity_levels"),
+            closed: policy.closed,
         }
     }
 }
@@ -0,0 +1,406 @@
+use chrono::Utc;
+use num_traits::ToPrimitive;
+use policy_engine::ComparisionType;
+use schemars::JsonSchema;
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+
+use crate::dao::{
+    identity_account, policy_account,
+    sea_orm_active_enums::PolicyAccountType as DaoPolicyAccountType, tracker_account,
+};
+
+use super::{
+    accounts::json_array, get_identity_registry, get_policy_engine, PolicyAccountType,
+    TrackerAccount,
+};
+
+// `policy_account.comparsion_type` stores the discriminant of the on-chain enum.
+pub(crate) const COMPARISON_TYPE_AND: i32 = ComparisionType::And as i32;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PolicyCheck {
+    pub policy_account: String,
+    pub policy_type: PolicyAccountType,
+    pub passed: bool,
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TransferCompliance {
+    pub compliant: bool,
+    pub policies: Vec<PolicyCheck>,
+}
+
+/// Returns true when `levels` satisfies the policy's identity filter. Empty filter slots are zero;
+/// a filter without any levels matches no one.
+pub(crate) fn identity_filter_matches(
+    filter_levels: &[u8],
+    comparison_type: i32,
+    levels: &[u8],
+) -> bool {
+    let mut filter = filter_levels.iter().filter(|level| **level != 0).peekable();
+    if filter.peek().is_none() {
+        return false;
+    }
+    if comparison_type == COMPARISON_TYPE_AND {
+        filter.all(|level| levels.contains(level))
+    } else {
+        filter.any(|level| levels.contains(level))
+    }
+}
+
+/// Amounts and timestamps of the tracked transfers that happened within `timeframe` seconds of
+/// `timestamp`.
+pub(crate) fn transfers_within(
+    tracker: Option<&TrackerAccount>,
+    timestamp: i64,
+    timeframe: i64,
+) -> Vec<(u64, i64)> {
+    let window_start = timestamp.saturating_sub(timeframe);
+    tracker.map_or(Vec::new(), |tracker| {
+        tracker
+            .transfer_amounts
+            .iter()
+            .zip(tracker.transfer_timestamps.iter())
+            .filter(|(_, ts)| **ts != 0 && **ts > window_start && **ts <= timestamp)
+            .map(|(amount, ts)| (*amount, *ts))
+            .collect()
+    })
+}
+
+/// Sums the amount and count of tracked transfers that happened within `timeframe` seconds of `timestamp`.
+pub(crate) fn transfers_in_window(
+    tracker: Option<&TrackerAccount>,
+    timestamp: i64,
+    timeframe: i64,
+) -> (u128, u64) {
+    transfers_within(tracker, timestamp, timeframe)
+        .into_iter()
+        .fold((0u128, 0u64), |(total, count), (amount, _)| {
+            (total + amount as u128, count + 1)
+        })
+}
+
+pub(crate) async fn get_identity_levels(
+    db: &DatabaseConnection,
+    registry: Vec<u8>,
+    owner: Vec<u8>,
+) -> Result<Option<Vec<u8>>, DbErr> {
+    let account = identity_account::Entity::find()
+        .filter(identity_account::Column::IdentityRegistry.eq(registry))
+        .filter(identity_account::Column::Owner.eq(owner))
+        .filter(identity_account::Column::Closed.eq(false))
+        .one(db)
+        .await?;
+    Ok(account.map(|account| json_array(account.levels, "levels")))
+}
+
+pub(crate) async fn get_tracker_account(
+    db: &DatabaseConnection,
+    mint: Vec<u8>,
+    owner: Vec<u8>,
+) -> Result<Option<TrackerAccount>, DbErr> {
+    let account = tracker_account::Entity::find()
+        .filter(tracker_account::Column::AssetMint.eq(mint))
+        .filter(tracker_account::Column::Owner.eq(owner))
+        .filter(tracker_account::Column::Closed.eq(false))
+        .one(db)
+        .await?;
+    Ok(account.map(TrackerAccount::from))
+}
+
+fn evaluate_policy(
+    policy: &policy_account::Model,
+    sender_levels: Option<&[u8]>,
+    receiver_levels: Option<&[u8]>,
+    tracker: Option<&TrackerAccount>,
+    amount: u64,
+    timestamp: i64,
+) -> (bool, String) {
+    let filter_levels: Vec<u8> = json_array(policy.identity_levels.clone(), "identity_levels");
+    let matches = |levels: Option<&[u8]>| {
+        levels.map_or(false, |levels| {
+            identity_filter_matches(&filter_levels, policy.comparsion_type, levels)
+        })
+    };
+    let limit = policy
+        .total_limit
+        .and_then(|limit| limit.to_u64())
+        .unwrap_or(u64::MAX);
+    let timeframe = policy.timeframe.unwrap_or_default();
+
+    match policy.policy_type {
+        DaoPolicyAccountType::IdentityApproval => match receiver_levels {
+            None => (false, "Receiver has no identity account".to_string()),
+            Some(_) if matches(receiver_levels) => (
+                true,
+                "Receiver identity satisfies the identity filter".to_string(),
+            ),
+            Some(_) => (
+                false,
+                "Receiver identity does not satisfy the identity filter".to_string(),
+            ),
+        },
+        // The transfer hook needs the sender's identity account to evaluate any other policy.
+        _ if sender_levels.is_none() => (false, "Sender has no identity account".to_string()),
+        _ if !matches(sender_levels) => (
+            true,
+            "Policy does not apply to the sender's identity levels".to_string(),
+        ),
+        DaoPolicyAccountType::TransactionAmountLimit => {
+            if amount > limit {
+                (
+                    false,
+                    format!("Amount {} exceeds the limit of {}", amount, limit),
+                )
+            } else {
+                (true, format!("Amount is within the limit of {}", limit))
+            }
+        }
+        DaoPolicyAccountType::TransactionAmountVelocity => {
+            let (total, _) = transfers_in_window(tracker, timestamp, timeframe);
+            if total + amount as u128 > limit as u128 {
+                (
+                    false,
+                    format!(
+                        "Transferred {} plus {} exceeds the limit of {} per {} seconds",
+                        total, amount, limit, timeframe
+                    ),
+                )
+            } else {
+                (
+                    true,
+                    format!(
+                        "Transferred {} plus {} is within the limit of {} per {} seconds",
+                        total, amount, limit, timeframe
+                    ),
+                )
+            }
+        }
+        DaoPolicyAccountType::TransactionCountVelocity => {
+            let (_, count) = transfers_in_window(tracker, timestamp, timeframe);
+            if count + 1 > limit {
+                (
+                    false,
+                    format!(
+                        "{} transfers already made, limit is {} per {} seconds",
+                        count, limit, timeframe
+                    ),
+                )
+            } else {
+                (
+                    true,
+                    format!(
+                        "{} transfers made, limit is {} per {} seconds",
+                        count, limit, timeframe
+                    ),
+                )
+            }
+        }
+    }
+}
+
+/// Evaluates the mint's policies against a prospective transfer. Identity approval is checked
+/// against the receiver, amount and velocity limits against the sender and its tracker account.
+pub async fn check_transfer_compliance(
+    db: &DatabaseConnection,
+    mint: Vec<u8>,
+    from: Vec<u8>,
+    to: Vec<u8>,
+    amount: u64,
+    timestamp: Option<i64>,
+) -> Result<TransferCompliance, DbErr> {
+    let timestamp = timestamp.unwrap_or_else(|| Utc::now().timestamp());
+    let (policy_engine, identity_registry, tracker) = tokio::join!(
+        get_policy_engine(db, mint.clone()),
+        get_identity_registry(db, mint.clone()),
+        get_tracker_account(db, mint.clone(), from.clone())
+    );
+    // Closed accounts no longer constrain transfers.
+    let policy_engine = policy_engine?.filter(|engine| !engine.closed);
+    let identity_registry = identity_registry?.filter(|registry| !registry.closed);
+    let tracker = tracker?;
+
+    let policies = match policy_engine {
+        Some(engine) => {
+            policy_account::Entity::find()
+                .filter(policy_account::Column::PolicyEngine.eq(engine.id))
+                .filter(policy_account::Column::Closed.eq(false))
+                .all(db)
+                .await?
+        }
+        None => Vec::new(),
+    };
+
+    let (sender_levels, receiver_levels) = match identity_registry {
+        Some(registry) => {
+            let (sender, receiver) = tokio::join!(
+                get_identity_levels(db, registry.id.clone(), from),
+                get_identity_levels(db, registry.id, to)
+            );
+            (sender?, receiver?)
+        }
+        None => (None, None),
+    };
+
+    let policies: Vec<PolicyCheck> = policies
+        .into_iter()
+        .map(|policy| {
+            let (passed, reason) = evaluate_policy(
+                &policy,
+                sender_levels.as_deref(),
+                receiver_levels.as_deref(),
+                tracker.as_ref(),
+                amount,
+                timestamp,
+            );
+            PolicyCheck {
+                policy_account: bs58::encode(policy.id).into_string(),
+                policy_type: PolicyAccountType::from(policy.policy_type),
+                passed,
+                reason,
+            }
+        })
+        .collect();
+
+    Ok(TransferCompliance {
+        compliant: policies.iter().all(|check| check.passed),
+        policies,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::prelude::Decimal;
+    use serde_json::json;
+
+    use super::*;
+
+    const OR: i32 = 0;
+    const AND: i32 = COMPARISON_TYPE_AND;
+
+    fn tracker(transfers: &[(u64, i64)]) -> TrackerAccount {
+        TrackerAccount {
+            address: String::new(),
+            mint: String::new(),
+            owner: String::new(),
+            transfer_amounts: transfers.iter().map(|(amount, _)| *amount).collect(),
+            transfer_timestamps: transfers.iter().map(|(_, ts)| *ts).collect(),
+            closed: false,
+        }
+    }
+
+    fn policy(
+        policy_type: DaoPolicyAccountType,
+        identity_levels: [u8; 3],
+        total_limit: Option<u64>,
+        timeframe: Option<i64>,
+    ) -> policy_account::Model {
+        policy_account::Model {
+            id: vec![1; 32],
+            policy_engine: vec![2; 32],
+            comparsion_type: OR,
+            identity_levels: Some(json!({ "identity_levels": identity_levels })),
+            timeframe,
+            policy_type,
+            closed: false,
+            closed_at_slot: None,
+            slot_updated: 1,
+            created_at: Default::default(),
+            last_updated_at: Default::default(),
+            total_limit: total_limit.map(Decimal::from),
+        }
+    }
+
+    #[test]
+    fn test_identity_filter_matches() {
+        let cases: [(&[u8], i32, &[u8], bool); 9] = [
+            (&[1, 2, 0], OR, &[2], true),
+            (&[1, 2, 0], OR, &[3], false),
+            (&[1, 2, 0], OR, &[], false),
+            (&[1, 2, 0], AND, &[1, 2, 3], true),
+            (&[1, 2, 0], AND, &[1], false),
+            // Zero slots are unused, not a level to hold.
+            (&[1, 0, 0], AND, &[1], true),
+            (&[0, 0, 0], OR, &[1], false),
+            (&[0, 0, 0], AND, &[1], false),
+            (&[], AND, &[], false),
+        ];
+        for (filter_levels, comparison_type, levels, expected) in cases {
+            assert_eq!(
+                identity_filter_matches(filter_levels, comparison_type, levels),
+                expected,
+                "filter {filter_levels:?} ({comparison_type}) against {levels:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_transfers_in_window() {
+        let transfers = [(10, 100), (20, 150), (30, 200), (40, 0)];
+        let cases: [(Option<&[(u64, i64)]>, i64, i64, (u128, u64)); 6] = [
+            (None, 200, 100, (0, 0)),
+            (Some(&transfers), 200, 1000, (60, 3)),
+            // The window excludes its start and includes `timestamp`.
+            (Some(&transfers), 200, 100, (50, 2)),
+            (Some(&transfers), 200, 0, (0, 0)),
+            // Transfers after `timestamp` don't count.
+            (Some(&transfers), 150, 1000, (30, 2)),
+            // Unused slots have a zero timestamp.
+            (Some(&[(40, 0)]), 200, 1000, (0, 0)),
+        ];
+        for (transfers, timestamp, timeframe, expected) in cases {
+            let tracker = transfers.map(tracker);
+            assert_eq!(
+                transfers_in_window(tracker.as_ref(), timestamp, timeframe),
+                expected,
+                "{transfers:?} at {timestamp} over {timeframe}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_evaluate_policy() {
+        use DaoPolicyAccountType::*;
+
+        const LEVEL_1: Option<&[u8]> = Some(&[1]);
+        const LEVEL_2: Option<&[u8]> = Some(&[2]);
+        let approval = policy(IdentityApproval, [1, 0, 0], None, None);
+        let amount_limit = policy(TransactionAmountLimit, [1, 0, 0], Some(100), None);
+        let amount_velocity = policy(TransactionAmountVelocity, [1, 0, 0], Some(100), Some(100));
+        let long_velocity = policy(TransactionAmountVelocity, [1, 0, 0], Some(100), Some(200));
+        let count_velocity = policy(TransactionCountVelocity, [1, 0, 0], Some(2), Some(100));
+        let low_count_velocity = policy(TransactionCountVelocity, [1, 0, 0], Some(1), Some(100));
+        // 60 sent 100 seconds and 30 sent 50 seconds before the transfer.
+        let tracked = tracker(&[(60, 900), (30, 950)]);
+
+        let cases = [
+            // Identity approval checks the receiver only.
+            (&approval, None, LEVEL_1, 1, true),
+            (&approval, None, LEVEL_2, 1, false),
+            (&approval, LEVEL_1, None, 1, false),
+            // Other policies need the sender's identity and apply to matching levels only.
+            (&amount_limit, None, None, 1, false),
+            (&amount_limit, LEVEL_2, None, 500, true),
+            (&amount_limit, LEVEL_1, None, 100, true),
+            (&amount_limit, LEVEL_1, None, 101, false),
+            // The transfer at 900 falls on the start of the window and is left out.
+            (&amount_velocity, LEVEL_1, None, 70, true),
+            (&amount_velocity, LEVEL_1, None, 71, false),
+            (&long_velocity, LEVEL_1, None, 10, true),
+            (&long_velocity, LEVEL_1, None, 11, false),
+            (&count_velocity, LEVEL_1, None, 1, true),
+            (&low_count_velocity, LEVEL_1, None, 1, false),
+        ];
+        for (policy, sender, receiver, amount, expected) in cases {
+            let (passed, reason) =
+                evaluate_policy(policy, sender, receiver, Some(&tracked), amount, 1000);
+            assert_eq!(
+                passed, expected,
+                "{:?} sending {amount} from {sender:?} to {receiver:?}: {reason}",
+                policy.policy_type
+            );
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use crate::dao::{data_account, data_registry, sea_orm_active_enums};
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+};
+
+use super::{Cursor, DataAccount, DataAccountType, Page, Pagination, SortBy};
+
+/// How the data registry of a `getDataAccountsByMint` request is identified.
+pub enum DataRegistryKey {
+    Mint(Vec<u8>),
+    Registry(Vec<u8>),
+}
+
+/// Data accounts of a data registry, optionally restricted to some document types. An unknown
+/// mint or registry yields an empty page.
+pub async fn get_data_accounts_by_mint(
+    db: &DatabaseConnection,
+    key: DataRegistryKey,
+    data_types: Option<Vec<DataAccountType>>,
+    pagination: &Pagination,
+    show_closed: bool,
+) -> Result<Page<DataAccount>, DbErr> {
+    let registry = match key {
+        DataRegistryKey::Mint(mint) => {
+            data_registry::Entity::find()
+                .filter(data_registry::Column::AssetMint.eq(mint))
+                .one(db)
+                .await?
+        }
+        DataRegistryKey::Registry(id) => data_registry::Entity::find_by_id(id).one(db).await?,
+    };
+    let Some(registry) = registry else {
+        return Ok(pagination.empty_page());
+    };
+
+    let select = data_account::Entity::find()
+        .filter(data_account::Column::DataRegistry.eq(registry.id))
+        .filter(
+            Condition::all()
+                .add_option((!show_closed).then(|| data_account::Column::Closed.eq(false)))
+                .add_option(data_types.map(|data_types| {
+                    data_types
+                        .into_iter()
+                        .fold(Condition::any(), |condition, data_type| {
+                            condition.add(
+                                data_account::Column::DataType
+                                    .eq(sea_orm_active_enums::DataAccountType::from(data_type)),
+                            )
+                        })
+                })),
+        );
+    let total = select.clone().count(db).await?;
+    let accounts = pagination
+        .apply(
+            select,
+            data_account::Column::Id,
+            Some(data_account::Column::SlotUpdated),
+        )
+        .all(db)
+        .await?;
+
+    Ok(pagination.page_of(
+        accounts,
+        total,
+        |model| match pagination.sort_by {
+            SortBy::SlotUpdated => Cursor::SlotUpdated(model.slot_updated, model.id.clone()),
+            _ => Cursor::Id(model.id.clone()),
+        },
+        DataAccount::from,
+    ))
+}
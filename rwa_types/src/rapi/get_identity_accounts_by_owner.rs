@@ -0,0 +1,98 @@
+use schemars::JsonSchema;
+use sea_orm::{DatabaseConnection, DbBackend, DbErr, FromQueryResult, JsonValue, Statement};
+use serde::{Deserialize, Serialize};
+
+use super::{accounts::json_array, Cursor, Page, Pagination, SortBy};
+
+/// An identity account together with the registry (and therefore the asset) it belongs to.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IdentityAccountWithRegistry {
+    pub address: String,
+    pub owner: String,
+    pub identity_registry: String,
+    pub mint: String,
+    pub registry_authority: String,
+    pub registry_delegate: String,
+    pub levels: Vec<u8>,
+    pub closed: bool,
+    pub slot_updated: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct IdentityAccountWithRegistryRow {
+    id: Vec<u8>,
+    owner: Vec<u8>,
+    identity_registry: Vec<u8>,
+    levels: Option<JsonValue>,
+    closed: bool,
+    slot_updated: i64,
+    asset_mint: Vec<u8>,
+    authority: Vec<u8>,
+    delegate: Vec<u8>,
+}
+
+impl From<IdentityAccountWithRegistryRow> for IdentityAccountWithRegistry {
+    fn from(row: IdentityAccountWithRegistryRow) -> Self {
+        IdentityAccountWithRegistry {
+            address: bs58::encode(row.id).into_string(),
+            owner: bs58::encode(row.owner).into_string(),
+            identity_registry: bs58::encode(row.identity_registry).into_string(),
+            mint: bs58::encode(row.asset_mint).into_string(),
+            registry_authority: bs58::encode(row.authority).into_string(),
+            registry_delegate: bs58::encode(row.delegate).into_string(),
+            levels: json_array(row.levels, "levels"),
+            closed: row.closed,
+            slot_updated: row.slot_updated,
+        }
+    }
+}
+
+#[derive(Debug, FromQueryResult)]
+struct IdentityAccountCountRow {
+    total: i64,
+}
+
+pub async fn get_identity_accounts_by_owner(
+    db: &DatabaseConnection,
+    owner: Vec<u8>,
+    pagination: &Pagination,
+    show_closed: bool,
+) -> Result<Page<IdentityAccountWithRegistry>, DbErr> {
+    let mut values = vec![owner.clone().into(), show_closed.into()];
+    let (cursor_condition, order) =
+        pagination.sql_clauses("ia.id", Some("ia.slot_updated"), &mut values);
+    let statement = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        &format!(
+            "SELECT ia.id, ia.owner, ia.identity_registry, ia.levels, ia.closed, ia.slot_updated, \
+                 ir.asset_mint, ir.authority, ir.delegate \
+             FROM identity_account ia \
+             INNER JOIN identity_registry ir ON ir.id = ia.identity_registry \
+             WHERE ia.owner = $1 AND ($2 OR NOT ia.closed) AND {cursor_condition} \
+             {order}"
+        ),
+        values,
+    );
+    let count_statement = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT COUNT(*) AS total \
+         FROM identity_account ia \
+         INNER JOIN identity_registry ir ON ir.id = ia.identity_registry \
+         WHERE ia.owner = $1 AND ($2 OR NOT ia.closed)",
+        vec![owner.into(), show_closed.into()],
+    );
+
+    let (rows, count) = tokio::join!(
+        IdentityAccountWithRegistryRow::find_by_statement(statement).all(db),
+        IdentityAccountCountRow::find_by_statement(count_statement).one(db)
+    );
+    Ok(pagination.page_of(
+        rows?,
+        count?.map_or(0, |row| row.total as u64),
+        |row| match pagination.sort_by {
+            SortBy::SlotUpdated => Cursor::SlotUpdated(row.slot_updated, row.id.clone()),
+            _ => Cursor::Id(row.id.clone()),
+        },
+        IdentityAccountWithRegistry::from,
+    ))
+}
@@ -0,0 +1,121 @@
+use crate::dao::{policy_account, policy_engine};
+use schemars::JsonSchema;
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    accounts::json_array, Cursor, Page, Pagination, PolicyAccountType, SortBy, COMPARISON_TYPE_AND,
+};
+
+/// How a policy's identity levels are matched against a wallet's levels.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+pub enum ComparisonType {
+    /// The wallet holds at least one of the levels.
+    Or,
+    /// The wallet holds all of the levels.
+    And,
+}
+
+impl From<i32> for ComparisonType {
+    fn from(comparison_type: i32) -> Self {
+        if comparison_type == COMPARISON_TYPE_AND {
+            ComparisonType::And
+        } else {
+            ComparisonType::Or
+        }
+    }
+}
+
+/// A policy account with its on-chain policy decoded.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PolicyAccountDetails {
+    pub address: String,
+    pub policy_engine: String,
+    pub mint: String,
+    pub policy_type: PolicyAccountType,
+    pub comparison_type: ComparisonType,
+    /// Non-zero identity levels the policy applies to.
+    pub identity_levels: Vec<u8>,
+    /// Amount or transfer count limit, as a string to keep u64 precision.
+    pub limit: Option<String>,
+    /// Velocity window in seconds.
+    pub timeframe: Option<i64>,
+    pub closed: bool,
+    pub slot_updated: i64,
+}
+
+impl PolicyAccountDetails {
+    fn new(policy: policy_account::Model, mint: &[u8]) -> Self {
+        let identity_levels: Vec<u8> = json_array(policy.identity_levels, "identity_levels");
+        PolicyAccountDetails {
+            address: bs58::encode(policy.id).into_string(),
+            policy_engine: bs58::encode(policy.policy_engine).into_string(),
+            mint: bs58::encode(mint).into_string(),
+            policy_type: PolicyAccountType::from(policy.policy_type),
+            comparison_type: ComparisonType::from(policy.comparsion_type),
+            identity_levels: identity_levels
+                .into_iter()
+                .filter(|level| *level != 0)
+                .collect(),
+            limit: policy.total_limit.map(|limit| limit.to_string()),
+            timeframe: policy.timeframe,
+            closed: policy.closed,
+            slot_updated: policy.slot_updated,
+        }
+    }
+}
+
+/// How the policy engine of a `getPolicyAccounts` request is identified.
+pub enum PolicyEngineKey {
+    Mint(Vec<u8>),
+    PolicyEngine(Vec<u8>),
+}
+
+/// Policy accounts of a policy engine. An unknown mint or engine yields an empty page.
+pub async fn get_policy_accounts(
+    db: &DatabaseConnection,
+    key: PolicyEngineKey,
+    pagination: &Pagination,
+    show_closed: bool,
+) -> Result<Page<PolicyAccountDetails>, DbErr> {
+    let engine = match key {
+        PolicyEngineKey::Mint(mint) => {
+            policy_engine::Entity::find()
+                .filter(policy_engine::Column::AssetMint.eq(mint))
+                .one(db)
+                .await?
+        }
+        PolicyEngineKey::PolicyEngine(id) => policy_engine::Entity::find_by_id(id).one(db).await?,
+    };
+    let Some(engine) = engine else {
+        return Ok(pagination.empty_page());
+    };
+
+    let select = policy_account::Entity::find()
+        .filter(policy_account::Column::PolicyEngine.eq(engine.id))
+        .filter(
+            Condition::all()
+                .add_option((!show_closed).then(|| policy_account::Column::Closed.eq(false))),
+        );
+    let total = select.clone().count(db).await?;
+    let accounts = pagination
+        .apply(
+            select,
+            policy_account::Column::Id,
+            Some(policy_account::Column::SlotUpdated),
+        )
+        .all(db)
+        .await?;
+
+    Ok(pagination.page_of(
+        accounts,
+        total,
+        |model| match pagination.sort_by {
+            SortBy::SlotUpdated => Cursor::SlotUpdated(model.slot_updated, model.id.clone()),
+            _ => Cursor::Id(model.id.clone()),
+        },
+        |model| PolicyAccountDetails::new(model, &engine.asset_mint),
+    ))
+}
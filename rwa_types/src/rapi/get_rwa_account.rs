@@ -0,0 +1,127 @@
+use crate::dao::{
+    asset_controller, data_account, data_registry, identity_account, identity_registry,
+    policy_account, policy_engine, tracker_account, transaction_approval_account,
+};
+use schemars::JsonSchema;
+use sea_orm::{DatabaseConnection, DbErr, EntityTrait};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    AssetControllerAccount, DataAccount, DataRegistryAccount, IdentityAccount,
+    IdentityRegistryAccount, PolicyAccount, PolicyEngine, TrackerAccount, TransactionApproval,
+};
+
+/// Any account of the RWA programs, tagged with its type.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RwaAccount {
+    AssetController(AssetControllerAccount),
+    TrackerAccount(TrackerAccount),
+    TransactionApproval(TransactionApproval),
+    DataRegistry(DataRegistryAccount),
+    DataAccount(DataAccount),
+    IdentityRegistry(IdentityRegistryAccount),
+    IdentityAccount(IdentityAccount),
+    PolicyEngine(PolicyEngine),
+    PolicyAccount(PolicyAccount),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RwaAccountWithMint {
+    /// The asset the account belongs to. Missing when the parent registry of a child account
+    /// hasn't been indexed.
+    pub mint: Option<String>,
+    pub account: RwaAccount,
+}
+
+/// Looks `address` up in every RWA account table and resolves the mint it belongs to, following
+/// the parent registry of data, identity and policy accounts.
+pub async fn get_rwa_account(
+    db: &DatabaseConnection,
+    address: Vec<u8>,
+) -> Result<RwaAccountWithMint, DbErr> {
+    let (
+        asset_controller,
+        tracker,
+        approval,
+        data_registry,
+        data_account,
+        identity_registry,
+        identity_account,
+        policy_engine,
+        policy_account,
+    ) = tokio::join!(
+        asset_controller::Entity::find_by_id(address.clone()).one(db),
+        tracker_account::Entity::find_by_id(address.clone()).one(db),
+        transaction_approval_account::Entity::find_by_id(address.clone()).one(db),
+        data_registry::Entity::find_by_id(address.clone()).one(db),
+        data_account::Entity::find_by_id(address.clone()).one(db),
+        identity_registry::Entity::find_by_id(address.clone()).one(db),
+        identity_account::Entity::find_by_id(address.clone()).one(db),
+        policy_engine::Entity::find_by_id(address.clone()).one(db),
+        policy_account::Entity::find_by_id(address.clone()).one(db)
+    );
+
+    let (mint, account) = if let Some(model) = asset_controller? {
+        (
+            Some(model.asset_mint.clone()),
+            RwaAccount::AssetController(model.into()),
+        )
+    } else if let Some(model) = tracker? {
+        (
+            Some(model.asset_mint.clone()),
+            RwaAccount::TrackerAccount(model.into()),
+        )
+    } else if let Some(model) = approval? {
+        (
+            Some(model.asset_mint.clone()),
+            RwaAccount::TransactionApproval(TransactionApproval::new(
+                model,
+                chrono::Utc::now().timestamp(),
+            )),
+        )
+    } else if let Some(model) = data_registry? {
+        (
+            Some(model.asset_mint.clone()),
+            RwaAccount::DataRegistry(model.into()),
+        )
+    } else if let Some(model) = identity_registry? {
+        (
+            Some(model.asset_mint.clone()),
+            RwaAccount::IdentityRegistry(model.into()),
+        )
+    } else if let Some(model) = policy_engine? {
+        (
+            Some(model.asset_mint.clone()),
+            RwaAccount::PolicyEngine(model.into()),
+        )
+    } else if let Some(model) = data_account? {
+        let registry = data_registry::Entity::find_by_id(model.data_registry.clone())
+            .one(db)
+            .await?;
+        let mint = registry.map(|registry| registry.asset_mint);
+        (mint, RwaAccount::DataAccount(model.into()))
+    } else if let Some(model) = identity_account? {
+        let registry = identity_registry::Entity::find_by_id(model.identity_registry.clone())
+            .one(db)
+            .await?;
+        let mint = registry.map(|registry| registry.asset_mint);
+        (mint, RwaAccount::IdentityAccount(model.into()))
+    } else if let Some(model) = policy_account? {
+        let engine = policy_engine::Entity::find_by_id(model.policy_engine.clone())
+            .one(db)
+            .await?;
+        let mint = engine.map(|engine| engine.asset_mint);
+        (mint, RwaAccount::PolicyAccount(model.into()))
+    } else {
+        return Err(DbErr::RecordNotFound(format!(
+            "No RWA account found for {}",
+            bs58::encode(address).into_string()
+        )));
+    };
+
+    Ok(RwaAccountWithMint {
+        mint: mint.map(|mint| bs58::encode(mint).into_string()),
+        account,
+    })
+}
@@ -0,0 +1,258 @@
+use crate::dao::{
+    account_history, asset_controller, data_account, data_registry, identity_account,
+    identity_registry, policy_account, policy_engine, tracker_account,
+    transaction_approval_account,
+};
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbBackend, DbErr, EntityName, EntityTrait, Statement,
+    Value,
+};
+use serde::de::DeserializeOwned;
+
+use super::{
+    AccountInclude, AssetControllerAccount, ChildAccountOptions, DataAccount, DataRegistryAccount,
+    FullAccount, IdentityAccount, IdentityRegistryAccount, Page, PolicyAccount, PolicyEngine,
+    TrackerAccount, TransactionApproval,
+};
+
+/// How a history row is tied back to the asset being reconstructed.
+enum HistoryKey {
+    AssetMint(Vec<u8>),
+    Parent(Vec<u8>),
+}
+
+/// `FROM` and `WHERE` clauses selecting, as `h`, the latest snapshot at or before `slot` of every
+/// `entity` account under `key` that is visible per `show_closed`. Binds `$1` to `$4`.
+fn snapshots_at_slot<E: EntityTrait>(
+    entity: E,
+    key: HistoryKey,
+    slot: i64,
+    show_closed: bool,
+) -> (String, Vec<Value>) {
+    let (column, key) = match key {
+        HistoryKey::AssetMint(mint) => ("asset_mint", mint),
+        HistoryKey::Parent(parent) => ("parent", parent),
+    };
+    let clauses = format!(
+        "FROM ( \
+             SELECT DISTINCT ON (pubkey) * FROM account_history \
+             WHERE account_type = $1 AND {column} = $2 AND slot <= $3 \
+             ORDER BY pubkey, slot DESC, id DESC \
+         ) h \
+         WHERE ($4 OR NOT h.closed)"
+    );
+    let values = vec![
+        entity.table_name().into(),
+        key.into(),
+        slot.into(),
+        show_closed.into(),
+    ];
+    (clauses, values)
+}
+
+/// Decodes a history snapshot back into the entity's model.
+fn decode_snapshot<E>(row: account_history::Model) -> Result<E::Model, DbErr>
+where
+    E: EntityTrait,
+    E::Model: DeserializeOwned,
+{
+    serde_json::from_value(row.data).map_err(|e| DbErr::Json(e.to_string()))
+}
+
+async fn registry_at_slot<E>(
+    db: &DatabaseConnection,
+    entity: E,
+    mint: Vec<u8>,
+    slot: i64,
+    options: &ChildAccountOptions,
+) -> Result<Option<E::Model>, DbErr>
+where
+    E: EntityTrait,
+    E::Model: DeserializeOwned,
+{
+    let (snapshots, values) = snapshots_at_slot(
+        entity,
+        HistoryKey::AssetMint(mint),
+        slot,
+        options.show_closed,
+    );
+    let statement = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        &format!("SELECT h.* {snapshots} ORDER BY h.pubkey LIMIT 1"),
+        values,
+    );
+    account_history::Entity::find()
+        .from_raw_sql(statement)
+        .one(db)
+        .await?
+        .map(decode_snapshot::<E>)
+        .transpose()
+}
+
+async fn children_at_slot<E, T>(
+    db: &DatabaseConnection,
+    entity: E,
+    key: Option<HistoryKey>,
+    include: AccountInclude,
+    slot: i64,
+    options: &ChildAccountOptions,
+    convert: impl Fn(E::Model) -> T,
+) -> Result<Option<Page<T>>, DbErr>
+where
+    E: EntityTrait,
+    E::Model: DeserializeOwned,
+{
+    let key = match key {
+        Some(key) if options.includes(include) => key,
+        _ => return Ok(None),
+    };
+    let (snapshots, mut values) = snapshots_at_slot(entity, key, slot, options.show_closed);
+    let count_statement = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        &format!("SELECT COUNT(*) AS total {snapshots}"),
+        values.clone(),
+    );
+    let (cursor_condition, tail) =
+        options
+            .pagination
+            .sql_clauses("h.pubkey", Some("h.slot"), &mut values);
+    let statement = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        &format!("SELECT h.* {snapshots} AND {cursor_condition} {tail}"),
+        values,
+    );
+
+    let (rows, count) = tokio::join!(
+        account_history::Entity::find()
+            .from_raw_sql(statement)
+            .all(db),
+        db.query_one(count_statement)
+    );
+    let total = count?
+        .map(|row| row.try_get::<i64>("", "total"))
+        .transpose()?
+        .unwrap_or_default();
+    let accounts = rows?
+        .into_iter()
+        .map(|row| {
+            let cursor = options.cursor(row.slot, &row.pubkey);
+            decode_snapshot::<E>(row).map(|model| (cursor, model))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Some(options.pagination.page_of(
+        accounts,
+        total as u64,
+        |(cursor, _)| cursor.clone(),
+        |(_, model)| convert(model),
+    )))
+}
+
+/// Rebuilds the accounts of a mint as they were at `slot` from `account_history`. History is
+/// only recorded from the point the indexer started writing it. Approvals are checked for expiry
+/// against `now`.
+pub async fn get_rwa_accounts_at_slot(
+    db: &DatabaseConnection,
+    id: Vec<u8>,
+    slot: i64,
+    options: &ChildAccountOptions,
+    now: i64,
+) -> Result<FullAccount, DbErr> {
+    let (asset_controller, data_registry, identity_registry, policy_engine) = tokio::join!(
+        registry_at_slot(db, asset_controller::Entity, id.clone(), slot, options),
+        registry_at_slot(db, data_registry::Entity, id.clone(), slot, options),
+        registry_at_slot(db, identity_registry::Entity, id.clone(), slot, options),
+        registry_at_slot(db, policy_engine::Entity, id.clone(), slot, options)
+    );
+    let (asset_controller, data_registry, identity_registry, policy_engine) = (
+        asset_controller?,
+        data_registry?,
+        identity_registry?,
+        policy_engine?,
+    );
+
+    let by_mint = || {
+        asset_controller
+            .as_ref()
+            .map(|_| HistoryKey::AssetMint(id.clone()))
+    };
+    let (trackers, approvals, data_accounts, identities, policy_accounts) = tokio::join!(
+        children_at_slot(
+            db,
+            tracker_account::Entity,
+            by_mint(),
+            AccountInclude::Trackers,
+            slot,
+            options,
+            TrackerAccount::from
+        ),
+        children_at_slot(
+            db,
+            transaction_approval_account::Entity,
+            by_mint(),
+            AccountInclude::Approvals,
+            slot,
+            options,
+            |model| TransactionApproval::new(model, now)
+        ),
+        children_at_slot(
+            db,
+            data_account::Entity,
+            data_registry
+                .as_ref()
+                .map(|model| HistoryKey::Parent(model.id.clone())),
+            AccountInclude::DataAccounts,
+            slot,
+            options,
+            DataAccount::from
+        ),
+        children_at_slot(
+            db,
+            identity_account::Entity,
+            identity_registry
+                .as_ref()
+                .map(|model| HistoryKey::Parent(model.id.clone())),
+            AccountInclude::Identities,
+            slot,
+            options,
+            IdentityAccount::from
+        ),
+        children_at_slot(
+            db,
+            policy_account::Entity,
+            policy_engine
+                .as_ref()
+                .map(|model| HistoryKey::Parent(model.id.clone())),
+            AccountInclude::Policies,
+            slot,
+            options,
+            PolicyAccount::from
+        )
+    );
+    let (trackers, approvals, data_accounts, identities, policy_accounts) = (
+        trackers?,
+        approvals?,
+        data_accounts?,
+        identities?,
+        policy_accounts?,
+    );
+
+    Ok(FullAccount {
+        asset_controller: asset_controller.map(|model| AssetControllerAccount {
+            trackers,
+            approvals,
+            ..AssetControllerAccount::from(model)
+        }),
+        data_registry: data_registry.map(|model| DataRegistryAccount {
+            data_accounts,
+            ..DataRegistryAccount::from(model)
+        }),
+        identity_registry: identity_registry.map(|model| IdentityRegistryAccount {
+            identities,
+            ..IdentityRegistryAccount::from(model)
+        }),
+        policy_engine: policy_engine.map(|model| PolicyEngine {
+            policy_accounts,
+            ..PolicyEngine::from(model)
+        }),
+    })
+}
@@ -1,10 +1,95 @@
-use crate::dao::{asset_controller, data_registry, identity_registry, policy_engine};
-use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use crate::dao::{
+    asset_controller, data_account, data_registry, identity_account, identity_registry,
+    policy_account, policy_engine, tracker_account, transaction_approval_account,
+};
+use schemars::JsonSchema;
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+    Select,
+};
+use serde::{Deserialize, Serialize};
 
 use super::{
-    AssetControllerAccount, DataRegistryAccount, FullAccount, IdentityRegistryAccount, PolicyEngine,
+    get_rwa_accounts_at_slot, AssetControllerAccount, Cursor, DataAccount, DataRegistryAccount,
+    FullAccount, IdentityAccount, IdentityRegistryAccount, Page, Pagination, PolicyAccount,
+    PolicyEngine, SortBy, TrackerAccount, TransactionApproval,
 };
 
+/// Child account collections that can be nested under their parent registries.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum AccountInclude {
+    Trackers,
+    Approvals,
+    DataAccounts,
+    Identities,
+    Policies,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChildAccountOptions {
+    pub include: Vec<AccountInclude>,
+    /// Applied to every included collection, each of which comes back as its own page. A
+    /// cursor bounds all of them alike, so keyset paging is meant for one collection at a time.
+    pub pagination: Pagination,
+    /// Closed accounts are left out unless this is set.
+    pub show_closed: bool,
+    /// Reconstructs the accounts from history as of this slot instead of reading current state.
+    pub at_slot: Option<i64>,
+}
+
+impl ChildAccountOptions {
+    pub fn includes(&self, include: AccountInclude) -> bool {
+        self.include.contains(&include)
+    }
+
+    fn closed_condition<C: ColumnTrait>(&self, closed: C) -> Condition {
+        Condition::all().add_option((!self.show_closed).then(|| closed.eq(false)))
+    }
+
+    /// Cursor of a child account in the requested sort.
+    pub(crate) fn cursor(&self, slot_updated: i64, id: &[u8]) -> Cursor {
+        match self.pagination.sort_by {
+            SortBy::SlotUpdated => Cursor::SlotUpdated(slot_updated, id.to_vec()),
+            _ => Cursor::Id(id.to_vec()),
+        }
+    }
+}
+
+impl Default for ChildAccountOptions {
+    fn default() -> Self {
+        ChildAccountOptions {
+            include: Vec::new(),
+            pagination: Pagination::default(),
+            show_closed: false,
+            at_slot: None,
+        }
+    }
+}
+
+/// One page of the child accounts `select` finds, along with their total.
+async fn child_page<E, T>(
+    db: &DatabaseConnection,
+    select: Select<E>,
+    options: &ChildAccountOptions,
+    id: E::Column,
+    slot_updated: E::Column,
+    cursor: impl Fn(&E::Model) -> Cursor,
+    item: impl FnMut(E::Model) -> T,
+) -> Result<Page<T>, DbErr>
+where
+    E: EntityTrait,
+    E::Model: Sync,
+{
+    let total = select.clone().count(db).await?;
+    let accounts = options
+        .pagination
+        .apply(select, id, Some(slot_updated))
+        .all(db)
+        .await?;
+    Ok(options.pagination.page_of(accounts, total, cursor, item))
+}
+
 pub async fn get_rwa_accounts_by_mint_controller(
     db: &DatabaseConnection,
     id: Vec<u8>,
@@ -49,10 +134,151 @@ pub async fn get_policy_engine(
     Ok(account)
 }
 
+pub async fn get_tracker_accounts(
+    db: &DatabaseConnection,
+    controller: Option<&asset_controller::Model>,
+    options: &ChildAccountOptions,
+) -> Result<Option<Page<TrackerAccount>>, DbErr> {
+    match controller {
+        Some(controller) if options.includes(AccountInclude::Trackers) => {
+            let select = tracker_account::Entity::find()
+                .filter(tracker_account::Column::AssetMint.eq(controller.asset_mint.clone()))
+                .filter(options.closed_condition(tracker_account::Column::Closed));
+            let page = child_page(
+                db,
+                select,
+                options,
+                tracker_account::Column::Id,
+                tracker_account::Column::SlotUpdated,
+                |model| options.cursor(model.slot_updated, &model.id),
+                TrackerAccount::from,
+            )
+            .await?;
+            Ok(Some(page))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// `now` is the unix timestamp approvals are checked for expiry against.
+pub async fn get_transaction_approvals(
+    db: &DatabaseConnection,
+    controller: Option<&asset_controller::Model>,
+    options: &ChildAccountOptions,
+    now: i64,
+) -> Result<Option<Page<TransactionApproval>>, DbErr> {
+    match controller {
+        Some(controller) if options.includes(AccountInclude::Approvals) => {
+            let select = transaction_approval_account::Entity::find()
+                .filter(
+                    transaction_approval_account::Column::AssetMint
+                        .eq(controller.asset_mint.clone()),
+                )
+                .filter(options.closed_condition(transaction_approval_account::Column::Closed));
+            let page = child_page(
+                db,
+                select,
+                options,
+                transaction_approval_account::Column::Id,
+                transaction_approval_account::Column::SlotUpdated,
+                |model| options.cursor(model.slot_updated, &model.id),
+                |model| TransactionApproval::new(model, now),
+            )
+            .await?;
+            Ok(Some(page))
+        }
+        _ => Ok(None),
+    }
+}
+
+pub async fn get_data_accounts(
+    db: &DatabaseConnection,
+    registry: Option<&data_registry::Model>,
+    options: &ChildAccountOptions,
+) -> Result<Option<Page<DataAccount>>, DbErr> {
+    match registry {
+        Some(registry) if options.includes(AccountInclude::DataAccounts) => {
+            let select = data_account::Entity::find()
+                .filter(data_account::Column::DataRegistry.eq(registry.id.clone()))
+                .filter(options.closed_condition(data_account::Column::Closed));
+            let page = child_page(
+                db,
+                select,
+                options,
+                data_account::Column::Id,
+                data_account::Column::SlotUpdated,
+                |model| options.cursor(model.slot_updated, &model.id),
+                DataAccount::from,
+            )
+            .await?;
+            Ok(Some(page))
+        }
+        _ => Ok(None),
+    }
+}
+
+pub async fn get_identity_accounts(
+    db: &DatabaseConnection,
+    registry: Option<&identity_registry::Model>,
+    options: &ChildAccountOptions,
+) -> Result<Option<Page<IdentityAccount>>, DbErr> {
+    match registry {
+        Some(registry) if options.includes(AccountInclude::Identities) => {
+            let select = identity_account::Entity::find()
+                .filter(identity_account::Column::IdentityRegistry.eq(registry.id.clone()))
+                .filter(options.closed_condition(identity_account::Column::Closed));
+            let page = child_page(
+                db,
+                select,
+                options,
+                identity_account::Column::Id,
+                identity_account::Column::SlotUpdated,
+                |model| options.cursor(model.slot_updated, &model.id),
+                IdentityAccount::from,
+            )
+            .await?;
+            Ok(Some(page))
+        }
+        _ => Ok(None),
+    }
+}
+
+pub async fn get_policy_accounts(
+    db: &DatabaseConnection,
+    engine: Option<&policy_engine::Model>,
+    options: &ChildAccountOptions,
+) -> Result<Option<Page<PolicyAccount>>, DbErr> {
+    match engine {
+        Some(engine) if options.includes(AccountInclude::Policies) => {
+            let select = policy_account::Entity::find()
+                .filter(policy_account::Column::PolicyEngine.eq(engine.id.clone()))
+                .filter(options.closed_condition(policy_account::Column::Closed));
+            let page = child_page(
+                db,
+                select,
+                options,
+                policy_account::Column::Id,
+                policy_account::Column::SlotUpdated,
+                |model| options.cursor(model.slot_updated, &model.id),
+                PolicyAccount::from,
+            )
+            .await?;
+            Ok(Some(page))
+        }
+        _ => Ok(None),
+    }
+}
+
 pub async fn get_rwa_accounts_by_mint(
     db: &DatabaseConnection,
     id: Vec<u8>,
+    options: &ChildAccountOptions,
 ) -> Result<FullAccount, DbErr> {
+    let now = chrono::Utc::now().timestamp();
+    if let Some(slot) = options.at_slot {
+        return get_rwa_accounts_at_slot(db, id, slot, options, now).await;
+    }
+
     let get_accounts_controller_future = get_rwa_accounts_by_mint_controller(db, id.clone());
     let get_data_registry_future = get_data_registry(db, id.clone());
     let get_identity_registry_future = get_identity_registry(db, id.clone());
@@ -65,18 +291,56 @@ pub async fn get_rwa_accounts_by_mint(
         get_policy_engine_future
     );
 
+    let visible = |closed: bool| options.show_closed || !closed;
+    let asset_controller = asset_controller
+        .ok()
+        .flatten()
+        .filter(|model| visible(model.closed));
+    let data_registry = data_registry
+        .ok()
+        .flatten()
+        .filter(|model| visible(model.closed));
+    let identity_registry = identity_registry
+        .ok()
+        .flatten()
+        .filter(|model| visible(model.closed));
+    let policy_engine = policy_engine
+        .ok()
+        .flatten()
+        .filter(|model| visible(model.closed));
+
+    let (trackers, approvals, data_accounts, identities, policy_accounts) = tokio::join!(
+        get_tracker_accounts(db, asset_controller.as_ref(), options),
+        get_transaction_approvals(db, asset_controller.as_ref(), options, now),
+        get_data_accounts(db, data_registry.as_ref(), options),
+        get_identity_accounts(db, identity_registry.as_ref(), options),
+        get_policy_accounts(db, policy_engine.as_ref(), options)
+    );
+    let (trackers, approvals, data_accounts, identities, policy_accounts) = (
+        trackers?,
+        approvals?,
+        data_accounts?,
+        identities?,
+        policy_accounts?,
+    );
+
     Ok(FullAccount {
-        asset_controller: asset_controller
-            .ok()
-            .and_then(|opt| opt.map(AssetControllerAccount::from)),
-        data_registry: data_registry
-            .ok()
-            .and_then(|opt| opt.map(DataRegistryAccount::from)),
-        identity_registry: identity_registry
-            .ok()
-            .and_then(|opt| opt.map(IdentityRegistryAccount::from)),
-        policy_engine: policy_engine
-            .ok()
-            .and_then(|opt| opt.map(PolicyEngine::from)),
+        asset_controller: asset_controller.map(|model| AssetControllerAccount {
+            trackers,
+            approvals,
+            ..AssetControllerAccount::from(model)
+        }),
+        data_registry: data_registry.map(|model| DataRegistryAccount {
+            data_accounts,
+            ..DataRegistryAccount::from(model)
+        }),
+        identity_registry: identity_registry.map(|model| IdentityRegistryAccount {
+            identities,
+            ..IdentityRegistryAccount::from(model)
+        }),
+        policy_engine: policy_engine.map(|model| PolicyEngine {
+            policy_accounts,
+            ..PolicyEngine::from(model)
+        }),
     })
 }
@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+
+use crate::dao::{asset_controller, data_registry, identity_registry, policy_engine};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+
+use super::{
+    AssetControllerAccount, DataRegistryAccount, FullAccount, IdentityRegistryAccount, PolicyEngine,
+};
+
+/// Registries of many mints in one query per registry table, keyed by base58 mint. Every
+/// requested mint has an entry, with empty registries when nothing is indexed for it.
+pub async fn get_rwa_accounts_by_mints(
+    db: &DatabaseConnection,
+    ids: Vec<Vec<u8>>,
+    show_closed: bool,
+) -> Result<BTreeMap<String, FullAccount>, DbErr> {
+    let (asset_controllers, data_registries, identity_registries, policy_engines) = tokio::join!(
+        asset_controller::Entity::find()
+            .filter(asset_controller::Column::AssetMint.is_in(ids.clone()))
+            .all(db),
+        data_registry::Entity::find()
+            .filter(data_registry::Column::AssetMint.is_in(ids.clone()))
+            .all(db),
+        identity_registry::Entity::find()
+            .filter(identity_registry::Column::AssetMint.is_in(ids.clone()))
+            .all(db),
+        policy_engine::Entity::find()
+            .filter(policy_engine::Column::AssetMint.is_in(ids.clone()))
+            .all(db)
+    );
+
+    let mut accounts: BTreeMap<Vec<u8>, FullAccount> = ids
+        .into_iter()
+        .map(|id| {
+            (
+                id,
+                FullAccount {
+                    asset_controller: None,
+                    data_registry: None,
+                    identity_registry: None,
+                    policy_engine: None,
+                },
+            )
+        })
+        .collect();
+    let visible = |closed: bool| show_closed || !closed;
+
+    for model in asset_controllers?.into_iter().filter(|m| visible(m.closed)) {
+        if let Some(account) = accounts.get_mut(&model.asset_mint) {
+            account.asset_controller = Some(AssetControllerAccount::from(model));
+        }
+    }
+    for model in data_registries?.into_iter().filter(|m| visible(m.closed)) {
+        if let Some(account) = accounts.get_mut(&model.asset_mint) {
+            account.data_registry = Some(DataRegistryAccount::from(model));
+        }
+    }
+    for model in identity_registries?
+        .into_iter()
+        .filter(|m| visible(m.closed))
+    {
+        if let Some(account) = accounts.get_mut(&model.asset_mint) {
+            account.identity_registry = Some(IdentityRegistryAccount::from(model));
+        }
+    }
+    for model in policy_engines?.into_iter().filter(|m| visible(m.closed)) {
+        if let Some(account) = accounts.get_mut(&model.asset_mint) {
+            account.policy_engine = Some(PolicyEngine::from(model));
+        }
+    }
+
+    Ok(accounts
+        .into_iter()
+        .map(|(id, account)| (bs58::encode(id).into_string(), account))
+        .collect())
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use sea_orm::{DatabaseConnection, DbBackend, DbErr, FromQueryResult, Statement};
+use serde::{Deserialize, Serialize};
+
+use super::{Cursor, Page, Pagination};
+
+// Every registry the pubkey controls, one row per registry. The data registry has no delegate.
+const ROLES_QUERY: &str = "SELECT asset_mint, 'asset_controller' AS program, id, \
+         authority = $1 AS is_authority, delegate = $1 AS is_delegate \
+     FROM asset_controller WHERE (authority = $1 OR delegate = $1) AND ($2 OR NOT closed) \
+     UNION ALL \
+     SELECT asset_mint, 'data_registry' AS program, id, TRUE AS is_authority, FALSE AS is_delegate \
+     FROM data_registry WHERE authority = $1 AND ($2 OR NOT closed) \
+     UNION ALL \
+     SELECT asset_mint, 'identity_registry' AS program, id, \
+         authority = $1 AS is_authority, delegate = $1 AS is_delegate \
+     FROM identity_registry WHERE (authority = $1 OR delegate = $1) AND ($2 OR NOT closed) \
+     UNION ALL \
+     SELECT asset_mint, 'policy_engine' AS program, id, \
+         authority = $1 AS is_authority, delegate = $1 AS is_delegate \
+     FROM policy_engine WHERE (authority = $1 OR delegate = $1) AND ($2 OR NOT closed)";
+
+/// The roles a pubkey holds on one program's registry account.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProgramRole {
+    pub address: String,
+    pub authority: bool,
+    pub delegate: bool,
+}
+
+/// A mint the pubkey controls, broken down by program.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct RwaAssetRoles {
+    pub mint: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_controller: Option<ProgramRole>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_registry: Option<ProgramRole>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity_registry: Option<ProgramRole>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy_engine: Option<ProgramRole>,
+}
+
+/// One role of the page, or a single row of NULLs when the page is empty, each carrying the
+/// total number of mints.
+#[derive(Debug, FromQueryResult)]
+struct RoleRow {
+    total: i64,
+    asset_mint: Option<Vec<u8>>,
+    program: Option<String>,
+    id: Option<Vec<u8>>,
+    is_authority: Option<bool>,
+    is_delegate: Option<bool>,
+}
+
+/// Mints where `authority` is the authority or delegate of any of the four registries, ordered
+/// by mint. Assets are keyed by mint, so the pagination must sort by id.
+pub async fn get_rwa_assets_by_authority(
+    db: &DatabaseConnection,
+    authority: Vec<u8>,
+    pagination: &Pagination,
+    show_closed: bool,
+) -> Result<Page<RwaAssetRoles>, DbErr> {
+    let mut values = vec![authority.into(), show_closed.into()];
+    let (cursor_condition, order) = pagination.sql_clauses("asset_mint", None, &mut values);
+    // The roles are materialized once and shared by the total and the page.
+    let statement = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        &format!(
+            "WITH roles AS MATERIALIZED ({ROLES_QUERY}), \
+             mints AS (SELECT DISTINCT asset_mint FROM roles), \
+             page AS (SELECT asset_mint FROM mints WHERE {cursor_condition} {order}) \
+             SELECT (SELECT COUNT(*) FROM mints) AS total, roles.asset_mint, roles.program, \
+                 roles.id, roles.is_authority, roles.is_delegate \
+             FROM (SELECT 1) AS one \
+             LEFT JOIN (roles INNER JOIN page ON page.asset_mint = roles.asset_mint) ON TRUE"
+        ),
+        values,
+    );
+    let rows = RoleRow::find_by_statement(statement).all(db).await?;
+    let total = rows.first().map_or(0, |row| row.total as u64);
+
+    let mut assets: BTreeMap<Vec<u8>, RwaAssetRoles> = BTreeMap::new();
+    for row in rows {
+        let (Some(asset_mint), Some(program), Some(id)) = (row.asset_mint, row.program, row.id)
+        else {
+            continue;
+        };
+        let asset = assets
+            .entry(asset_mint.clone())
+            .or_insert_with(|| RwaAssetRoles {
+                mint: bs58::encode(&asset_mint).into_string(),
+                ..Default::default()
+            });
+        let role = Some(ProgramRole {
+            address: bs58::encode(id).into_string(),
+            authority: row.is_authority.unwrap_or_default(),
+            delegate: row.is_delegate.unwrap_or_default(),
+        });
+        match program.as_str() {
+            "asset_controller" => asset.asset_controller = role,
+            "data_registry" => asset.data_registry = role,
+            "identity_registry" => asset.identity_registry = role,
+            _ => asset.policy_engine = role,
+        }
+    }
+    // Back in the order the query returned the mints in.
+    let mut assets: Vec<(Vec<u8>, RwaAssetRoles)> = assets.into_iter().collect();
+    if !pagination.ascending() {
+        assets.reverse();
+    }
+
+    Ok(pagination.page_of(
+        assets,
+        total,
+        |(mint, _)| Cursor::Id(mint.clone()),
+        |(_, asset)| asset,
+    ))
+}
@@ -0,0 +1,106 @@
+use crate::dao::token_mint;
+use schemars::JsonSchema;
+use sea_orm::{
+    prelude::Decimal, DatabaseConnection, DbBackend, DbErr, EntityTrait, FromQueryResult, Statement,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{Cursor, Page, Pagination, SortBy};
+
+/// Combined balance of every token account an owner holds for a mint.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RwaHolder {
+    pub owner: String,
+    pub amount: String,
+    pub token_accounts: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RwaHolders {
+    pub mint: String,
+    /// Missing until the Token-2022 mint account has been indexed.
+    pub supply: Option<String>,
+    pub decimals: Option<u8>,
+    #[serde(flatten)]
+    pub holders: Page<RwaHolder>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct RwaHolderRow {
+    owner: Vec<u8>,
+    amount: Decimal,
+    token_accounts: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct HolderCountRow {
+    total: i64,
+}
+
+impl From<RwaHolderRow> for RwaHolder {
+    fn from(row: RwaHolderRow) -> Self {
+        RwaHolder {
+            owner: bs58::encode(row.owner).into_string(),
+            amount: row.amount.to_string(),
+            token_accounts: row.token_accounts as u64,
+        }
+    }
+}
+
+/// Owners with a non-zero balance of `mint`. Holders are keyed by owner, so the pagination
+/// must sort by id, which is the owner, or by amount.
+pub async fn get_rwa_holders(
+    db: &DatabaseConnection,
+    mint: Vec<u8>,
+    pagination: &Pagination,
+) -> Result<RwaHolders, DbErr> {
+    let mut values = vec![mint.clone().into()];
+    let (cursor_condition, order) = pagination.sql_clauses("owner", Some("amount"), &mut values);
+    let holders_statement = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        &format!(
+            "SELECT owner, amount, token_accounts \
+             FROM ( \
+                 SELECT owner, SUM(amount) AS amount, COUNT(*) AS token_accounts \
+                 FROM token_account \
+                 WHERE mint = $1 AND NOT closed AND amount > 0 \
+                 GROUP BY owner \
+             ) holders \
+             WHERE {cursor_condition} \
+             {order}"
+        ),
+        values,
+    );
+    let count_statement = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT COUNT(DISTINCT owner) AS total \
+         FROM token_account \
+         WHERE mint = $1 AND NOT closed AND amount > 0",
+        vec![mint.clone().into()],
+    );
+
+    let (token_mint, holders, count) = tokio::join!(
+        token_mint::Entity::find_by_id(mint.clone()).one(db),
+        RwaHolderRow::find_by_statement(holders_statement).all(db),
+        HolderCountRow::find_by_statement(count_statement).one(db)
+    );
+    let token_mint = token_mint?.filter(|model| !model.closed);
+
+    Ok(RwaHolders {
+        mint: bs58::encode(mint).into_string(),
+        supply: token_mint.as_ref().map(|model| model.supply.to_string()),
+        decimals: token_mint.as_ref().map(|model| model.decimals as u8),
+        holders: pagination.page_of(
+            holders?,
+            count?.map_or(0, |row| row.total as u64),
+            |row| match pagination.sort_by {
+                SortBy::Amount => Cursor::Amount(
+                    u64::try_from(row.amount).unwrap_or(u64::MAX),
+                    row.owner.clone(),
+                ),
+                _ => Cursor::Id(row.owner.clone()),
+            },
+            RwaHolder::from,
+        ),
+    })
+}
@@ -0,0 +1,160 @@
+use schemars::JsonSchema;
+use sea_orm::{DatabaseConnection, DbBackend, DbErr, FromQueryResult, Statement, Value};
+use serde::{Deserialize, Serialize};
+
+use super::{Cursor, Page, Pagination, SortBy};
+
+// One row per mint with at least one registry. Binds: $1 show_closed, $2 has_policy_engine,
+// $3 authority, $4 updated_since.
+const MINTS_QUERY: &str = "SELECT * FROM ( \
+         SELECT m.asset_mint, \
+             ac.id AS asset_controller, dr.id AS data_registry, \
+             ir.id AS identity_registry, pe.id AS policy_engine, \
+             GREATEST(ac.slot_updated, dr.slot_updated, ir.slot_updated, pe.slot_updated) \
+                 AS slot_updated, \
+             ARRAY_REMOVE(ARRAY[ac.authority, ac.delegate, dr.authority, ir.authority, \
+                 ir.delegate, pe.authority, pe.delegate], NULL) AS authorities \
+         FROM ( \
+             SELECT asset_mint FROM asset_controller WHERE $1 OR NOT closed \
+             UNION SELECT asset_mint FROM data_registry WHERE $1 OR NOT closed \
+             UNION SELECT asset_mint FROM identity_registry WHERE $1 OR NOT closed \
+             UNION SELECT asset_mint FROM policy_engine WHERE $1 OR NOT closed \
+         ) m \
+         LEFT JOIN asset_controller ac ON ac.asset_mint = m.asset_mint AND ($1 OR NOT ac.closed) \
+         LEFT JOIN data_registry dr ON dr.asset_mint = m.asset_mint AND ($1 OR NOT dr.closed) \
+         LEFT JOIN identity_registry ir ON ir.asset_mint = m.asset_mint AND ($1 OR NOT ir.closed) \
+         LEFT JOIN policy_engine pe ON pe.asset_mint = m.asset_mint AND ($1 OR NOT pe.closed) \
+     ) assets \
+     WHERE ($2::boolean IS NULL OR (policy_engine IS NOT NULL) = $2) \
+         AND ($3::bytea IS NULL OR $3 = ANY(authorities)) \
+         AND ($4::bigint IS NULL OR slot_updated >= $4)";
+
+// Child account counts of the mints on the page, using the registry indices of each table.
+const COUNTS_JOINS: &str = "LEFT JOIN LATERAL ( \
+         SELECT COUNT(*) AS identities FROM identity_account ia \
+         WHERE ia.identity_registry = page.identity_registry AND ($1 OR NOT ia.closed) \
+     ) identities ON TRUE \
+     LEFT JOIN LATERAL ( \
+         SELECT COUNT(*) AS data_accounts FROM data_account da \
+         WHERE da.data_registry = page.data_registry AND ($1 OR NOT da.closed) \
+     ) data_accounts ON TRUE \
+     LEFT JOIN LATERAL ( \
+         SELECT COUNT(*) AS policies FROM policy_account pa \
+         WHERE pa.policy_engine = page.policy_engine AND ($1 OR NOT pa.closed) \
+     ) policies ON TRUE";
+
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct RwaMintFilter {
+    pub has_policy_engine: Option<bool>,
+    /// Authority or delegate of any of the mint's registries.
+    pub authority: Option<Vec<u8>>,
+    pub updated_since: Option<i64>,
+    pub show_closed: bool,
+}
+
+impl RwaMintFilter {
+    fn values(&self) -> Vec<Value> {
+        vec![
+            self.show_closed.into(),
+            self.has_policy_engine.into(),
+            self.authority.clone().into(),
+            self.updated_since.into(),
+        ]
+    }
+}
+
+/// An indexed asset with the registries configured for it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RwaMint {
+    pub mint: String,
+    pub asset_controller: Option<String>,
+    pub data_registry: Option<String>,
+    pub identity_registry: Option<String>,
+    pub policy_engine: Option<String>,
+    pub identities: u64,
+    pub data_accounts: u64,
+    pub policies: u64,
+    /// Latest slot at which any of the registries changed.
+    pub slot_updated: i64,
+}
+
+/// One mint of the page, or a single row of NULLs when the page is empty, each carrying the
+/// total number of mints.
+#[derive(Debug, FromQueryResult)]
+struct RwaMintRow {
+    total: i64,
+    asset_mint: Option<Vec<u8>>,
+    asset_controller: Option<Vec<u8>>,
+    data_registry: Option<Vec<u8>>,
+    identity_registry: Option<Vec<u8>>,
+    policy_engine: Option<Vec<u8>>,
+    identities: i64,
+    data_accounts: i64,
+    policies: i64,
+    slot_updated: Option<i64>,
+}
+
+impl RwaMint {
+    fn new(row: RwaMintRow, asset_mint: Vec<u8>) -> Self {
+        let encode = |id: Option<Vec<u8>>| id.map(|id| bs58::encode(id).into_string());
+        RwaMint {
+            mint: bs58::encode(asset_mint).into_string(),
+            asset_controller: encode(row.asset_controller),
+            data_registry: encode(row.data_registry),
+            identity_registry: encode(row.identity_registry),
+            policy_engine: encode(row.policy_engine),
+            identities: row.identities as u64,
+            data_accounts: row.data_accounts as u64,
+            policies: row.policies as u64,
+            slot_updated: row.slot_updated.unwrap_or_default(),
+        }
+    }
+}
+
+/// Every mint with at least one registry, sortable by mint or by the last slot it changed at.
+pub async fn get_rwa_mints(
+    db: &DatabaseConnection,
+    filter: &RwaMintFilter,
+    pagination: &Pagination,
+) -> Result<Page<RwaMint>, DbErr> {
+    let mut values = filter.values();
+    let (cursor_condition, order) =
+        pagination.sql_clauses("asset_mint", Some("slot_updated"), &mut values);
+    // The filtered mints are materialized once and shared by the total and the page, and the
+    // child accounts are only counted for the mints on the page.
+    let statement = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        &format!(
+            "WITH mints AS MATERIALIZED ({MINTS_QUERY}), \
+             page AS (SELECT * FROM mints WHERE {cursor_condition} {order}) \
+             SELECT (SELECT COUNT(*) FROM mints) AS total, page.asset_mint, \
+                 page.asset_controller, page.data_registry, page.identity_registry, \
+                 page.policy_engine, page.slot_updated, \
+                 identities.identities, data_accounts.data_accounts, policies.policies \
+             FROM (SELECT 1) AS one \
+             LEFT JOIN page ON TRUE \
+             {COUNTS_JOINS} \
+             {}",
+            pagination.order_by("page.asset_mint", Some("page.slot_updated"))
+        ),
+        values,
+    );
+    let rows = RwaMintRow::find_by_statement(statement).all(db).await?;
+    let total = rows.first().map_or(0, |row| row.total as u64);
+    let rows: Vec<(Vec<u8>, RwaMintRow)> = rows
+        .into_iter()
+        .filter_map(|row| row.asset_mint.clone().map(|asset_mint| (asset_mint, row)))
+        .collect();
+
+    Ok(pagination.page_of(
+        rows,
+        total,
+        |(asset_mint, row)| match pagination.sort_by {
+            SortBy::SlotUpdated => {
+                Cursor::SlotUpdated(row.slot_updated.unwrap_or_default(), asset_mint.clone())
+            }
+            _ => Cursor::Id(asset_mint.clone()),
+        },
+        |(asset_mint, row)| RwaMint::new(row, asset_mint),
+    ))
+}
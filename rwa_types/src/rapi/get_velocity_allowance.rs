@@ -0,0 +1,266 @@
+use chrono::Utc;
+use num_traits::ToPrimitive;
+use schemars::JsonSchema;
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+
+use crate::dao::{policy_account, sea_orm_active_enums::PolicyAccountType as DaoPolicyAccountType};
+
+use super::{
+    accounts::json_array, get_identity_levels, get_identity_registry, get_policy_engine,
+    get_tracker_account, identity_filter_matches, transfers_within, PolicyAccountType,
+    TrackerAccount,
+};
+
+/// Usage of a single velocity policy in the window ending at the requested timestamp.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VelocityPolicyAllowance {
+    pub policy_account: String,
+    pub policy_type: PolicyAccountType,
+    /// False when the owner's identity levels don't match the policy's identity filter.
+    pub applies: bool,
+    /// None when the policy has no limit set.
+    pub limit: Option<String>,
+    pub timeframe: i64,
+    /// Amount transferred, or number of transfers, within the window.
+    pub used: String,
+    /// None when the policy has no limit set.
+    pub remaining: Option<String>,
+    /// When the oldest transfer in the window drops out and frees some allowance.
+    pub next_release_at: Option<i64>,
+    /// When every transfer in the window has dropped out and the full limit is available again.
+    pub resets_at: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VelocityAllowance {
+    pub mint: String,
+    pub owner: String,
+    pub timestamp: i64,
+    /// Smallest remaining amount across the limited amount velocity policies that apply, if any.
+    pub remaining_amount: Option<String>,
+    /// Smallest remaining transfer count across the limited count velocity policies that apply,
+    /// if any.
+    pub remaining_transfers: Option<String>,
+    pub policies: Vec<VelocityPolicyAllowance>,
+}
+
+fn policy_allowance(
+    policy: policy_account::Model,
+    levels: Option<&[u8]>,
+    tracker: Option<&TrackerAccount>,
+    timestamp: i64,
+) -> (VelocityPolicyAllowance, Option<u128>) {
+    let filter_levels: Vec<u8> = json_array(policy.identity_levels.clone(), "identity_levels");
+    let applies = levels.map_or(false, |levels| {
+        identity_filter_matches(&filter_levels, policy.comparsion_type, levels)
+    });
+    let limit = policy
+        .total_limit
+        .and_then(|limit| limit.to_u64())
+        .map(u128::from);
+    let timeframe = policy.timeframe.unwrap_or_default();
+
+    let transfers = transfers_within(tracker, timestamp, timeframe);
+    let used = match policy.policy_type {
+        DaoPolicyAccountType::TransactionCountVelocity => transfers.len() as u128,
+        _ => transfers.iter().map(|(amount, _)| *amount as u128).sum(),
+    };
+    let remaining = limit.map(|limit| limit.saturating_sub(used));
+    let first = transfers.iter().map(|(_, ts)| *ts).min();
+    let last = transfers.iter().map(|(_, ts)| *ts).max();
+
+    let allowance = VelocityPolicyAllowance {
+        policy_account: bs58::encode(policy.id).into_string(),
+        policy_type: PolicyAccountType::from(policy.policy_type),
+        applies,
+        limit: limit.map(|limit| limit.to_string()),
+        timeframe,
+        used: used.to_string(),
+        remaining: remaining.map(|remaining| remaining.to_string()),
+        next_release_at: first.map(|ts| ts.saturating_add(timeframe)),
+        resets_at: last.map(|ts| ts.saturating_add(timeframe)),
+    };
+    (allowance, remaining)
+}
+
+/// Remaining velocity allowance of `owner` for `mint`, based on the owner's tracker account and
+/// the mint's open velocity policies.
+pub async fn get_velocity_allowance(
+    db: &DatabaseConnection,
+    mint: Vec<u8>,
+    owner: Vec<u8>,
+    timestamp: Option<i64>,
+) -> Result<VelocityAllowance, DbErr> {
+    let timestamp = timestamp.unwrap_or_else(|| Utc::now().timestamp());
+    let (policy_engine, identity_registry, tracker) = tokio::join!(
+        get_policy_engine(db, mint.clone()),
+        get_identity_registry(db, mint.clone()),
+        get_tracker_account(db, mint.clone(), owner.clone())
+    );
+    let policy_engine = policy_engine?.filter(|engine| !engine.closed);
+    let identity_registry = identity_registry?.filter(|registry| !registry.closed);
+    let tracker = tracker?;
+
+    let policies = match policy_engine {
+        Some(engine) => {
+            policy_account::Entity::find()
+                .filter(policy_account::Column::PolicyEngine.eq(engine.id))
+                .filter(policy_account::Column::Closed.eq(false))
+                .filter(
+                    Condition::any()
+                        .add(
+                            policy_account::Column::PolicyType
+                                .eq(DaoPolicyAccountType::TransactionAmountVelocity),
+                        )
+                        .add(
+                            policy_account::Column::PolicyType
+                                .eq(DaoPolicyAccountType::TransactionCountVelocity),
+                        ),
+                )
+                .all(db)
+                .await?
+        }
+        None => Vec::new(),
+    };
+    let levels = match identity_registry {
+        Some(registry) => get_identity_levels(db, registry.id, owner.clone()).await?,
+        None => None,
+    };
+
+    let mut remaining_amount: Option<u128> = None;
+    let mut remaining_transfers: Option<u128> = None;
+    let mut allowances = Vec::with_capacity(policies.len());
+    for policy in policies {
+        let is_count = policy.policy_type == DaoPolicyAccountType::TransactionCountVelocity;
+        let (allowance, remaining) =
+            policy_allowance(policy, levels.as_deref(), tracker.as_ref(), timestamp);
+        if let (true, Some(remaining)) = (allowance.applies, remaining) {
+            let current = if is_count {
+                &mut remaining_transfers
+            } else {
+                &mut remaining_amount
+            };
+            *current = Some(current.map_or(remaining, |current| current.min(remaining)));
+        }
+        allowances.push(allowance);
+    }
+
+    Ok(VelocityAllowance {
+        mint: bs58::encode(mint).into_string(),
+        owner: bs58::encode(owner).into_string(),
+        timestamp,
+        remaining_amount: remaining_amount.map(|remaining| remaining.to_string()),
+        remaining_transfers: remaining_transfers.map(|remaining| remaining.to_string()),
+        policies: allowances,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::prelude::Decimal;
+    use serde_json::json;
+
+    use super::*;
+
+    fn policy(
+        policy_type: DaoPolicyAccountType,
+        total_limit: Option<u64>,
+        timeframe: i64,
+    ) -> policy_account::Model {
+        policy_account::Model {
+            id: vec![1; 32],
+            policy_engine: vec![2; 32],
+            comparsion_type: 0,
+            identity_levels: Some(json!({ "identity_levels": [1, 0, 0] })),
+            timeframe: Some(timeframe),
+            policy_type,
+            closed: false,
+            closed_at_slot: None,
+            slot_updated: 1,
+            created_at: Default::default(),
+            last_updated_at: Default::default(),
+            total_limit: total_limit.map(Decimal::from),
+        }
+    }
+
+    #[test]
+    fn test_policy_allowance() {
+        use DaoPolicyAccountType::*;
+
+        // The transfer at 100 is outside every window below, and zero timestamps are unused slots.
+        let tracker = TrackerAccount {
+            address: String::new(),
+            mint: String::new(),
+            owner: String::new(),
+            transfer_amounts: vec![5, 10, 20, 40],
+            transfer_timestamps: vec![100, 900, 950, 0],
+            closed: false,
+        };
+        let cases = [
+            // (policy, levels, applies, used, remaining, next_release_at, resets_at)
+            (
+                policy(TransactionAmountVelocity, Some(100), 200),
+                Some(&[1][..]),
+                true,
+                "30",
+                Some("70"),
+                Some(1100),
+                Some(1150),
+            ),
+            (
+                policy(TransactionAmountVelocity, Some(25), 200),
+                Some(&[1][..]),
+                true,
+                "30",
+                Some("0"),
+                Some(1100),
+                Some(1150),
+            ),
+            (
+                policy(TransactionCountVelocity, Some(5), 60),
+                Some(&[1][..]),
+                true,
+                "1",
+                Some("4"),
+                Some(1010),
+                Some(1010),
+            ),
+            (
+                policy(TransactionAmountVelocity, None, 200),
+                Some(&[2][..]),
+                false,
+                "30",
+                None,
+                Some(1100),
+                Some(1150),
+            ),
+            (
+                policy(TransactionCountVelocity, Some(5), 10),
+                None,
+                false,
+                "0",
+                Some("5"),
+                None,
+                None,
+            ),
+        ];
+        for (policy, levels, applies, used, remaining, next_release_at, resets_at) in cases {
+            let policy_type = policy.policy_type.clone();
+            let (allowance, remaining_value) =
+                policy_allowance(policy, levels, Some(&tracker), 1000);
+            assert_eq!(allowance.applies, applies, "{policy_type:?}");
+            assert_eq!(allowance.used, used, "{policy_type:?}");
+            assert_eq!(allowance.remaining.as_deref(), remaining, "{policy_type:?}");
+            assert_eq!(
+                remaining_value.map(|value| value.to_string()).as_deref(),
+                remaining
+            );
+            assert_eq!(
+                allowance.next_release_at, next_release_at,
+                "{policy_type:?}"
+            );
+            assert_eq!(allowance.resets_at, resets_at, "{policy_type:?}");
+        }
+    }
+}
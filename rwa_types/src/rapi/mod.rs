@@ -1,4 +1,30 @@
 mod accounts;
+mod check_transfer_compliance;
+mod get_data_accounts_by_mint;
+mod get_identity_accounts_by_owner;
+mod get_policy_accounts;
+mod get_rwa_account;
+mod get_rwa_accounts_at_slot;
 mod get_rwa_accounts_by_mint;
+mod get_rwa_accounts_by_mints;
+mod get_rwa_assets_by_authority;
+mod get_rwa_holders;
+mod get_rwa_mints;
+mod get_velocity_allowance;
+mod pagination;
+mod rwa_mint_update;
 pub use accounts::*;
+pub use check_transfer_compliance::*;
+pub use get_data_accounts_by_mint::*;
+pub use get_identity_accounts_by_owner::*;
+pub use get_policy_accounts::*;
+pub use get_rwa_account::*;
+pub use get_rwa_accounts_at_slot::*;
 pub use get_rwa_accounts_by_mint::*;
+pub use get_rwa_accounts_by_mints::*;
+pub use get_rwa_assets_by_authority::*;
+pub use get_rwa_holders::*;
+pub use get_rwa_mints::*;
+pub use get_velocity_allowance::*;
+pub use pagination::*;
+pub use rwa_mint_update::*;
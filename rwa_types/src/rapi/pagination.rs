@@ -0,0 +1,364 @@
+use schemars::JsonSchema;
+use sea_orm::{
+    prelude::Decimal, ColumnTrait, Condition, EntityTrait, Order, QueryFilter, QueryOrder,
+    QuerySelect, Select, Value,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Upper bound for `limit` on every list method.
+pub const MAX_LIMIT: u64 = 1000;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum SortBy {
+    #[default]
+    Id,
+    SlotUpdated,
+    /// Token balance, only for holder listings.
+    Amount,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PaginationValidationError {
+    #[error("Limit should not be greater than {MAX_LIMIT}")]
+    LimitExceeded,
+    #[error("Page and before/after cursors can't be combined")]
+    ConflictingMethods,
+    #[error("Sorting by {0:?} is not supported")]
+    UnsupportedSort(SortBy),
+    #[error("Page should be greater than 0")]
+    InvalidPage,
+    #[error("Invalid cursor: {0}")]
+    InvalidCursor(String),
+}
+
+/// Position of an item in a listing. Encoded as base58; slot and amount cursors are the
+/// big-endian sort value followed by the id so items with equal values keep a stable order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Cursor {
+    Id(Vec<u8>),
+    SlotUpdated(i64, Vec<u8>),
+    Amount(u64, Vec<u8>),
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        match self {
+            Cursor::Id(id) => bs58::encode(id).into_string(),
+            Cursor::SlotUpdated(slot, id) => {
+                let mut bytes = slot.to_be_bytes().to_vec();
+                bytes.extend_from_slice(id);
+                bs58::encode(bytes).into_string()
+            }
+            Cursor::Amount(amount, id) => {
+                let mut bytes = amount.to_be_bytes().to_vec();
+                bytes.extend_from_slice(id);
+                bs58::encode(bytes).into_string()
+            }
+        }
+    }
+
+    pub fn decode(cursor: &str, sort_by: SortBy) -> Result<Self, PaginationValidationError> {
+        let bytes = bs58::decode(cursor)
+            .into_vec()
+            .map_err(|_| PaginationValidationError::InvalidCursor(cursor.to_string()))?;
+        match sort_by {
+            SortBy::Id if bytes.len() == 32 => Ok(Cursor::Id(bytes)),
+            SortBy::SlotUpdated if bytes.len() == 40 => {
+                let (slot, id) = bytes.split_at(8);
+                let slot = i64::from_be_bytes(slot.try_into().unwrap_or_default());
+                Ok(Cursor::SlotUpdated(slot, id.to_vec()))
+            }
+            SortBy::Amount if bytes.len() == 40 => {
+                let (amount, id) = bytes.split_at(8);
+                let amount = u64::from_be_bytes(amount.try_into().unwrap_or_default());
+                Ok(Cursor::Amount(amount, id.to_vec()))
+            }
+            _ => Err(PaginationValidationError::InvalidCursor(cursor.to_string())),
+        }
+    }
+}
+
+/// Validated pagination of a list method. Either page based, or keyset based with `before`
+/// and/or `after`, which bound the results on the sort key in the requested direction. With
+/// only `before` the page is the `limit` items just ahead of the cursor, still returned in the
+/// requested direction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pagination {
+    pub limit: u64,
+    pub page: Option<u64>,
+    pub before: Option<Cursor>,
+    pub after: Option<Cursor>,
+    pub sort_by: SortBy,
+    pub sort_direction: SortDirection,
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Pagination {
+            limit: MAX_LIMIT,
+            page: Some(1),
+            before: None,
+            after: None,
+            sort_by: SortBy::Id,
+            sort_direction: SortDirection::Asc,
+        }
+    }
+}
+
+impl Pagination {
+    pub fn new(
+        limit: Option<u64>,
+        page: Option<u64>,
+        before: Option<String>,
+        after: Option<String>,
+        sort_by: Option<SortBy>,
+        sort_direction: Option<SortDirection>,
+    ) -> Result<Self, PaginationValidationError> {
+        let limit = limit.unwrap_or(MAX_LIMIT);
+        if limit > MAX_LIMIT {
+            return Err(PaginationValidationError::LimitExceeded);
+        }
+        if page.is_some() && (before.is_some() || after.is_some()) {
+            return Err(PaginationValidationError::ConflictingMethods);
+        }
+        if page == Some(0) {
+            return Err(PaginationValidationError::InvalidPage);
+        }
+        let sort_by = sort_by.unwrap_or_default();
+        let decode = |cursor: Option<String>| {
+            cursor
+                .map(|cursor| Cursor::decode(&cursor, sort_by))
+                .transpose()
+        };
+        let (before, after) = (decode(before)?, decode(after)?);
+
+        Ok(Pagination {
+            limit,
+            page: page.or_else(|| (before.is_none() && after.is_none()).then_some(1)),
+            before,
+            after,
+            sort_by,
+            sort_direction: sort_direction.unwrap_or_default(),
+        })
+    }
+
+    /// Rejects sorts the listing has no column for.
+    pub fn require_sort(self, supported: &[SortBy]) -> Result<Self, PaginationValidationError> {
+        if supported.contains(&self.sort_by) {
+            Ok(self)
+        } else {
+            Err(PaginationValidationError::UnsupportedSort(self.sort_by))
+        }
+    }
+
+    /// For listings whose items have no `slot_updated` to sort on.
+    pub fn require_id_sort(self) -> Result<Self, PaginationValidationError> {
+        self.require_sort(&[SortBy::Id])
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.page
+            .map_or(0, |page| page.saturating_sub(1) * self.limit)
+    }
+
+    /// Pages requested with only `before` are fetched against the sort direction, so the limit
+    /// keeps the items nearest the cursor, and flipped back in [`Pagination::page_of`].
+    fn backwards(&self) -> bool {
+        self.before.is_some() && self.after.is_none()
+    }
+
+    /// Direction rows come back from the query in, which is the requested one unless paging
+    /// backwards.
+    pub fn ascending(&self) -> bool {
+        (self.sort_direction == SortDirection::Asc) != self.backwards()
+    }
+
+    fn order(&self) -> Order {
+        if self.ascending() {
+            Order::Asc
+        } else {
+            Order::Desc
+        }
+    }
+
+    /// `after` moves along the sort direction, `before` against it.
+    fn cursors(&self) -> impl Iterator<Item = (&Cursor, bool)> {
+        let ascending = self.sort_direction == SortDirection::Asc;
+        self.after
+            .iter()
+            .map(move |cursor| (cursor, ascending))
+            .chain(self.before.iter().map(move |cursor| (cursor, !ascending)))
+    }
+
+    /// Applies the cursors, ordering, limit and offset to a query over an entity. `sort_column`
+    /// is the column of the listing's non-id sort, as in [`Pagination::sql_clauses`]; a slot or
+    /// amount cursor compares `(sort_column, id)` so items with equal values aren't skipped.
+    pub fn apply<E: EntityTrait>(
+        &self,
+        mut select: Select<E>,
+        id: E::Column,
+        sort_column: Option<E::Column>,
+    ) -> Select<E> {
+        for (cursor, greater) in self.cursors() {
+            let past = |column: E::Column, value: Value| {
+                if greater {
+                    column.gt(value)
+                } else {
+                    column.lt(value)
+                }
+            };
+            let (sort_value, cursor_id): (Option<Value>, _) = match cursor {
+                Cursor::Id(cursor_id) => (None, cursor_id),
+                Cursor::SlotUpdated(slot, cursor_id) => (Some((*slot).into()), cursor_id),
+                Cursor::Amount(amount, cursor_id) => {
+                    (Some(Decimal::from(*amount).into()), cursor_id)
+                }
+            };
+            let condition = match (sort_value, sort_column) {
+                (Some(sort_value), Some(sort_column)) => Condition::any()
+                    .add(past(sort_column, sort_value.clone()))
+                    .add(
+                        Condition::all()
+                            .add(sort_column.eq(sort_value))
+                            .add(past(id, cursor_id.clone().into())),
+                    ),
+                _ => Condition::all().add(past(id, cursor_id.clone().into())),
+            };
+            select = select.filter(condition);
+        }
+        if let (SortBy::SlotUpdated | SortBy::Amount, Some(sort_column)) =
+            (self.sort_by, sort_column)
+        {
+            select = select.order_by(sort_column, self.order());
+        }
+        select
+            .order_by(id, self.order())
+            .limit(self.limit)
+            .offset(self.offset())
+    }
+
+    /// Cursor condition and the `ORDER BY ... LIMIT ... OFFSET ...` tail for a raw query.
+    /// Bind values are appended to `values` and the placeholders continue its numbering.
+    /// `sort_column` is the column of the listing's non-id sort, `slot_updated` or `amount`;
+    /// without one the listing must have been checked with [`Pagination::require_id_sort`].
+    pub fn sql_clauses(
+        &self,
+        id: &str,
+        sort_column: Option<&str>,
+        values: &mut Vec<Value>,
+    ) -> (String, String) {
+        let mut conditions = Vec::new();
+        for (cursor, greater) in self.cursors() {
+            let op = if greater { ">" } else { "<" };
+            let (sort_value, cursor_id): (Option<Value>, _) = match cursor {
+                Cursor::Id(cursor_id) => (None, cursor_id),
+                Cursor::SlotUpdated(slot, cursor_id) => (Some((*slot).into()), cursor_id),
+                Cursor::Amount(amount, cursor_id) => {
+                    (Some(Decimal::from(*amount).into()), cursor_id)
+                }
+            };
+            match (sort_value, sort_column) {
+                (Some(sort_value), Some(sort_column)) => {
+                    values.push(sort_value);
+                    values.push(cursor_id.clone().into());
+                    conditions.push(format!(
+                        "({sort_column}, {id}) {op} (${}, ${})",
+                        values.len() - 1,
+                        values.len()
+                    ));
+                }
+                _ => {
+                    values.push(cursor_id.clone().into());
+                    conditions.push(format!("{id} {op} ${}", values.len()));
+                }
+            }
+        }
+        let condition = if conditions.is_empty() {
+            "TRUE".to_string()
+        } else {
+            conditions.join(" AND ")
+        };
+
+        values.push((self.limit as i64).into());
+        values.push((self.offset() as i64).into());
+        let tail = format!(
+            "{} LIMIT ${} OFFSET ${}",
+            self.order_by(id, sort_column),
+            values.len() - 1,
+            values.len()
+        );
+        (condition, tail)
+    }
+
+    /// The `ORDER BY` clause of [`Pagination::sql_clauses`] on its own, to restore the order of
+    /// a page after joining onto it.
+    pub fn order_by(&self, id: &str, sort_column: Option<&str>) -> String {
+        let direction = if self.ascending() { "ASC" } else { "DESC" };
+        match (self.sort_by, sort_column) {
+            (SortBy::SlotUpdated | SortBy::Amount, Some(sort_column)) => {
+                format!("ORDER BY {sort_column} {direction}, {id} {direction}")
+            }
+            _ => format!("ORDER BY {id} {direction}"),
+        }
+    }
+
+    pub fn empty_page<T>(&self) -> Page<T> {
+        Page {
+            total: 0,
+            limit: self.limit,
+            page: self.page,
+            cursor: None,
+            items: Vec::new(),
+        }
+    }
+
+    /// Wraps one page of rows in query order. The cursor points at the row furthest along the
+    /// paging direction: the last one, or the first one when paging backwards.
+    pub fn page_of<R, T>(
+        &self,
+        mut rows: Vec<R>,
+        total: u64,
+        cursor: impl Fn(&R) -> Cursor,
+        item: impl FnMut(R) -> T,
+    ) -> Page<T> {
+        if self.backwards() {
+            rows.reverse();
+        }
+        let end = if self.backwards() {
+            rows.first()
+        } else {
+            rows.last()
+        };
+        Page {
+            total,
+            limit: self.limit,
+            page: self.page,
+            cursor: end.map(|row| cursor(row).encode()),
+            items: rows.into_iter().map(item).collect(),
+        }
+    }
+}
+
+/// A page of a list method.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Page<T> {
+    /// Number of items across all pages.
+    pub total: u64,
+    pub limit: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u64>,
+    /// Pass as `after` to continue past the last item. On a page requested with only `before`
+    /// it points at the first item instead, to pass as `before` to keep paging backwards.
+    pub cursor: Option<String>,
+    pub items: Vec<T>,
+}
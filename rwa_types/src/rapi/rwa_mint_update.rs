@@ -0,0 +1,19 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{FullAccount, RwaAccountWithMint};
+
+/// Notification of a `subscribeRwaMint` subscription. Registry changes carry the mint's
+/// registries, child account changes only the account that changed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum RwaMintUpdate {
+    Registries {
+        slot: i64,
+        accounts: FullAccount,
+    },
+    Account {
+        slot: i64,
+        account: RwaAccountWithMint,
+    },
+}
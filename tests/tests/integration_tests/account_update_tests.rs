@@ -1,9 +1,18 @@
+use std::str::FromStr;
+
 use function_name::named;
 
 use itertools::Itertools;
+use plerkle_serialization::root_as_account_info;
 use rwa_api::api::{self, ApiContract};
+use rwa_types::{
+    dao::policy_account,
+    rapi::{AccountInclude, PolicyAccount},
+};
+use sea_orm::EntityTrait;
 
 use serial_test::serial;
+use solana_sdk::{account::Account, pubkey::Pubkey};
 
 use super::common::*;
 
@@ -11,14 +20,7 @@ use super::common::*;
 #[serial]
 #[named]
 async fn test_get_rwa_accounts_by_mint() {
-    let name = trim_test_name(function_name!());
-    let setup = TestSetup::new(name.clone()).await;
-
-    let seeds: Vec<SeedEvent> = vec![seed_token_mint(
-        "Ea1yrC1xRXd6tWcHL4yhGRB31j6jTwdeqd3e9LHaYUwj",
-    )];
-    apply_migrations_and_delete_data(setup.db.clone()).await;
-    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+    let setup = setup_with_seeds(function_name!(), vec![seed_token_mint(RWA_MINT)]).await;
     let request: api::GetRwaAccountsByMint = serde_json::from_str(
         r#"{
         "id": "Ea1yrC1xRXd6tWcHL4yhGRB31j6jTwdeqd3e9LHaYUwj"
@@ -32,3 +34,290 @@ async fn test_get_rwa_accounts_by_mint() {
         .unwrap();
     insta::assert_json_snapshot!(setup.name.clone(), response);
 }
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_get_rwa_accounts_by_mint_with_children() {
+    let setup = setup_with_seeds(function_name!(), vec![seed_mint_with_children(RWA_MINT)]).await;
+    let request: api::GetRwaAccountsByMint = serde_json::from_str(
+        r#"{
+        "id": "Ea1yrC1xRXd6tWcHL4yhGRB31j6jTwdeqd3e9LHaYUwj",
+        "include": ["trackers", "dataAccounts", "identities", "policies"],
+        "limit": 10
+    }"#,
+    )
+    .unwrap();
+    let response = setup
+        .rwa_api
+        .get_rwa_accounts_by_mint(request)
+        .await
+        .unwrap();
+
+    // Each collection holds the first page of the program's children, sorted by id.
+    let program_ids = setup.config.get_program_ids();
+    let children = cached_fetch_child_accounts(&setup, Pubkey::from_str(RWA_MINT).unwrap()).await;
+    let expected = |program: Pubkey| {
+        children
+            .iter()
+            .filter(|child| child.program == program)
+            .map(|child| child.address.to_string())
+            .take(10)
+            .collect_vec()
+    };
+
+    let data_registry = response.data_registry.unwrap();
+    let data_accounts = data_registry.data_accounts.unwrap().items;
+    assert!(!data_accounts.is_empty());
+    assert!(data_accounts
+        .iter()
+        .all(|account| account.data_registry == data_registry.address));
+    assert_eq!(
+        data_accounts
+            .iter()
+            .map(|account| account.address.clone())
+            .collect_vec(),
+        expected(program_ids.data_registry)
+    );
+
+    let identity_registry = response.identity_registry.unwrap();
+    let identities = identity_registry.identities.unwrap().items;
+    assert!(!identities.is_empty());
+    assert!(identities
+        .iter()
+        .all(|identity| identity.identity_registry == identity_registry.address));
+    assert_eq!(
+        identities
+            .iter()
+            .map(|identity| identity.address.clone())
+            .collect_vec(),
+        expected(program_ids.identity_registry)
+    );
+
+    let policy_engine = response.policy_engine.unwrap();
+    let policy_page = policy_engine.policy_accounts.unwrap();
+    assert_eq!(policy_page.total, POLICY_ACCOUNTS.len() as u64);
+    let policy_accounts = policy_page.items;
+    assert!(policy_accounts
+        .iter()
+        .all(|policy| policy.policy_engine == policy_engine.address));
+    let policy_addresses = policy_accounts
+        .iter()
+        .map(|policy| policy.address.clone())
+        .collect_vec();
+    assert_eq!(policy_addresses, expected(program_ids.policy_engine));
+    assert_eq!(
+        policy_addresses.iter().sorted().collect_vec(),
+        POLICY_ACCOUNTS.iter().sorted().collect_vec()
+    );
+
+    let trackers = response.asset_controller.unwrap().trackers.unwrap().items;
+    assert!(trackers.iter().all(|tracker| tracker.mint == RWA_MINT));
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_closed_account_is_marked_closed() {
+    let setup = setup_with_seeds(function_name!(), vec![seed_mint_with_children(RWA_MINT)]).await;
+
+    // A closed account is drained and emptied, but keeps its owner in the update.
+    let policy = Pubkey::from_str(POLICY_ACCOUNTS[0]).unwrap();
+    let closed = Account {
+        lamports: 0,
+        data: vec![],
+        owner: setup.config.get_program_ids().policy_engine,
+        executable: false,
+        rent_epoch: 0,
+    };
+    let close_slot = DEFAULT_SLOT + 1;
+    index_account_bytes(&setup, serialize_account_bytes(policy, &closed, close_slot)).await;
+
+    let stored = policy_account::Entity::find_by_id(policy.to_bytes().to_vec())
+        .one(setup.db.as_ref())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(stored.closed);
+    assert_eq!(stored.closed_at_slot, Some(close_slot as i64));
+    assert_eq!(stored.slot_updated, close_slot as i64);
+
+    let get_policies = |show_closed| api::GetPolicyAccounts {
+        mint: Some(RWA_MINT.to_string()),
+        show_closed: Some(show_closed),
+        ..Default::default()
+    };
+    let open = setup
+        .rwa_api
+        .get_policy_accounts(get_policies(false))
+        .await
+        .unwrap();
+    assert_eq!(open.total, POLICY_ACCOUNTS.len() as u64 - 1);
+    assert!(open
+        .items
+        .iter()
+        .all(|item| item.address != policy.to_string()));
+
+    let all = setup
+        .rwa_api
+        .get_policy_accounts(get_policies(true))
+        .await
+        .unwrap();
+    assert_eq!(all.total, POLICY_ACCOUNTS.len() as u64);
+    let closed_item = all
+        .items
+        .iter()
+        .find(|item| item.address == policy.to_string())
+        .unwrap();
+    assert!(closed_item.closed);
+
+    // A replayed update from before the close doesn't reopen the account.
+    index_account(&setup, policy).await;
+    let stored = policy_account::Entity::find_by_id(policy.to_bytes().to_vec())
+        .one(setup.db.as_ref())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(stored.closed);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_get_rwa_accounts_by_mints() {
+    let setup = setup_with_seeds(function_name!(), vec![seed_token_mint(RWA_MINT)]).await;
+
+    let unindexed = Pubkey::new_unique().to_string();
+    let request = api::GetRwaAccountsByMints {
+        ids: vec![RWA_MINT.to_string(), unindexed.clone()],
+        ..Default::default()
+    };
+    let response = setup
+        .rwa_api
+        .get_rwa_accounts_by_mints(request)
+        .await
+        .unwrap();
+    assert_eq!(response.len(), 2);
+
+    // Each mint gets the same registries as a single mint lookup.
+    let single = get_full_account(&setup, RWA_MINT, vec![]).await;
+    assert!(single.asset_controller.is_some());
+    assert_eq!(response[RWA_MINT], single);
+
+    let empty = &response[&unindexed];
+    assert!(empty.asset_controller.is_none());
+    assert!(empty.data_registry.is_none());
+    assert!(empty.identity_registry.is_none());
+    assert!(empty.policy_engine.is_none());
+}
+
+/// Re-serializes the cached `account` at `slot` with its data changed by `update`.
+async fn updated_account_bytes(
+    setup: &TestSetup,
+    account: Pubkey,
+    slot: u64,
+    update: impl FnOnce(&mut Vec<u8>),
+) -> Vec<u8> {
+    let account_bytes = cached_fetch_account(setup, account, Some(DEFAULT_SLOT)).await;
+    let info = root_as_account_info(&account_bytes).unwrap();
+    let mut data = info
+        .data()
+        .map(|data| data.iter().collect_vec())
+        .unwrap_or_default();
+    update(&mut data);
+    let updated = Account {
+        lamports: info.lamports(),
+        data,
+        owner: Pubkey::try_from(info.owner().unwrap().0.as_slice()).unwrap(),
+        executable: info.executable(),
+        rent_epoch: info.rent_epoch(),
+    };
+    serialize_account_bytes(account, &updated, slot)
+}
+
+async fn get_policies_at_slot(
+    setup: &TestSetup,
+    at_slot: Option<u64>,
+    show_closed: bool,
+) -> Vec<PolicyAccount> {
+    let request = api::GetRwaAccountsByMint {
+        id: RWA_MINT.to_string(),
+        include: Some(vec![AccountInclude::Policies]),
+        show_closed: Some(show_closed),
+        at_slot,
+        ..Default::default()
+    };
+    setup
+        .rwa_api
+        .get_rwa_accounts_by_mint(request)
+        .await
+        .unwrap()
+        .policy_engine
+        .unwrap()
+        .policy_accounts
+        .unwrap()
+        .items
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_get_rwa_accounts_at_slot_returns_older_state() {
+    let setup = setup_with_seeds(function_name!(), vec![seed_mint_with_children(RWA_MINT)]).await;
+
+    // The amount limit policy applies to level 1 until its filter is widened to level 2.
+    let policy = Pubkey::from_str(POLICY_ACCOUNTS[1]).unwrap();
+    let update_slot = DEFAULT_SLOT + 2;
+    let account_bytes = updated_account_bytes(&setup, policy, update_slot, |data| {
+        // Discriminator, version and policy engine precede the identity levels.
+        data[41..43].copy_from_slice(&[1, 2]);
+    })
+    .await;
+    index_account_bytes(&setup, account_bytes).await;
+
+    let levels = |policies: Vec<PolicyAccount>| {
+        policies
+            .into_iter()
+            .find(|item| item.address == policy.to_string())
+            .unwrap()
+            .identity_levels
+    };
+    let current = get_policies_at_slot(&setup, None, false).await;
+    assert_eq!(levels(current), vec![1, 2]);
+    let before = get_policies_at_slot(&setup, Some(update_slot - 1), false).await;
+    assert_eq!(before.len(), POLICY_ACCOUNTS.len());
+    assert_eq!(levels(before), vec![1]);
+    let after = get_policies_at_slot(&setup, Some(update_slot), false).await;
+    assert_eq!(levels(after), vec![1, 2]);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_get_rwa_accounts_at_slot_after_close() {
+    let setup = setup_with_seeds(function_name!(), vec![seed_mint_with_children(RWA_MINT)]).await;
+
+    let policy = Pubkey::from_str(POLICY_ACCOUNTS[0]).unwrap();
+    let close_slot = DEFAULT_SLOT + 2;
+    let account_bytes = updated_account_bytes(&setup, policy, close_slot, Vec::clear).await;
+    index_account_bytes(&setup, account_bytes).await;
+
+    let find = |policies: &[PolicyAccount]| {
+        policies
+            .iter()
+            .find(|item| item.address == policy.to_string())
+            .cloned()
+    };
+
+    // Before the close the account is still open.
+    let before = get_policies_at_slot(&setup, Some(close_slot - 1), false).await;
+    assert!(!find(&before).unwrap().closed);
+
+    // After it, the account is hidden unless closed accounts are asked for.
+    let after = get_policies_at_slot(&setup, Some(close_slot + 1), false).await;
+    assert_eq!(after.len(), POLICY_ACCOUNTS.len() - 1);
+    assert!(find(&after).is_none());
+    let after = get_policies_at_slot(&setup, Some(close_slot + 1), true).await;
+    assert_eq!(after.len(), POLICY_ACCOUNTS.len());
+    assert!(find(&after).unwrap().closed);
+}
@@ -0,0 +1,229 @@
+use std::str::FromStr;
+
+use function_name::named;
+
+use rwa_api::api::{self, ApiContract};
+use rwa_types::rapi::{ProgramRole, RwaAccount};
+
+use serial_test::serial;
+use solana_sdk::pubkey::Pubkey;
+
+use super::common::*;
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_get_rwa_assets_by_authority() {
+    let setup = setup_with_seeds(function_name!(), vec![seed_token_mint(RWA_MINT)]).await;
+
+    let accounts = get_full_account(&setup, RWA_MINT, vec![]).await;
+    let asset_controller = accounts.asset_controller.unwrap();
+    let data_registry = accounts.data_registry.unwrap();
+    let identity_registry = accounts.identity_registry.unwrap();
+    let policy_engine = accounts.policy_engine.unwrap();
+    let authority = asset_controller.authority.clone();
+
+    let role = |address: &str, authorities: [&str; 2]| {
+        let role = ProgramRole {
+            address: address.to_string(),
+            authority: authorities[0] == authority,
+            delegate: authorities[1] == authority,
+        };
+        (role.authority || role.delegate).then_some(role)
+    };
+
+    let request = api::GetRwaAssetsByAuthority {
+        authority: authority.clone(),
+        ..Default::default()
+    };
+    let response = setup
+        .rwa_api
+        .get_rwa_assets_by_authority(request)
+        .await
+        .unwrap();
+    assert_eq!(response.total, 1);
+    let assets = &response.items[0];
+    assert_eq!(assets.mint, RWA_MINT);
+    assert_eq!(
+        assets.asset_controller,
+        role(
+            &asset_controller.address,
+            [&asset_controller.authority, &asset_controller.delegate]
+        )
+    );
+    // The fixtures give the data registry the same authority.
+    assert_eq!(
+        assets.data_registry,
+        Some(ProgramRole {
+            address: data_registry.address,
+            authority: true,
+            delegate: false,
+        })
+    );
+    assert_eq!(
+        assets.identity_registry,
+        role(
+            &identity_registry.address,
+            [&identity_registry.authority, &identity_registry.delegate]
+        )
+    );
+    assert_eq!(
+        assets.policy_engine,
+        role(
+            &policy_engine.address,
+            [&policy_engine.authority, &policy_engine.delegate]
+        )
+    );
+
+    // A pubkey that controls nothing gets an empty page.
+    let request = api::GetRwaAssetsByAuthority {
+        authority: Pubkey::new_unique().to_string(),
+        ..Default::default()
+    };
+    let response = setup
+        .rwa_api
+        .get_rwa_assets_by_authority(request)
+        .await
+        .unwrap();
+    assert_eq!(response.total, 0);
+    assert!(response.items.is_empty());
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_get_rwa_account_detects_type() {
+    let mut seeds = vec![seed_token_mint(RWA_MINT)];
+    seeds.extend(seed_accounts(POLICY_ACCOUNTS));
+    let setup = setup_with_seeds(function_name!(), seeds).await;
+
+    let get_account = |address: String| {
+        setup
+            .rwa_api
+            .get_rwa_account(api::GetRwaAccount { address })
+    };
+    let program_ids = setup.config.get_program_ids();
+    let mint_key = Pubkey::from_str(RWA_MINT).unwrap();
+
+    let asset_controller = program_ids
+        .find_asset_controller_pda(&mint_key)
+        .0
+        .to_string();
+    let response = get_account(asset_controller.clone()).await.unwrap();
+    assert_eq!(response.mint.as_deref(), Some(RWA_MINT));
+    assert!(matches!(
+        response.account,
+        RwaAccount::AssetController(account) if account.address == asset_controller
+    ));
+
+    let policy_engine = program_ids.find_policy_engine_pda(&mint_key).0.to_string();
+    let response = get_account(policy_engine.clone()).await.unwrap();
+    assert_eq!(response.mint.as_deref(), Some(RWA_MINT));
+    assert!(matches!(
+        response.account,
+        RwaAccount::PolicyEngine(account) if account.address == policy_engine
+    ));
+
+    // Child accounts resolve their mint through the parent registry.
+    let response = get_account(POLICY_ACCOUNTS[0].to_string()).await.unwrap();
+    assert_eq!(response.mint.as_deref(), Some(RWA_MINT));
+    assert!(matches!(
+        response.account,
+        RwaAccount::PolicyAccount(account)
+            if account.address == POLICY_ACCOUNTS[0] && account.policy_engine == policy_engine
+    ));
+
+    assert!(get_account(Pubkey::new_unique().to_string()).await.is_err());
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_get_rwa_mints() {
+    let mut seeds = vec![seed_token_mint(RWA_MINT)];
+    seeds.extend(seed_accounts(POLICY_ACCOUNTS));
+    let setup = setup_with_seeds(function_name!(), seeds).await;
+
+    let accounts = get_full_account(&setup, RWA_MINT, vec![]).await;
+    let asset_controller = accounts.asset_controller.unwrap();
+
+    let response = setup
+        .rwa_api
+        .get_rwa_mints(api::GetRwaMints::default())
+        .await
+        .unwrap();
+    assert_eq!(response.total, 1);
+    let rwa_mint = &response.items[0];
+    assert_eq!(rwa_mint.mint, RWA_MINT);
+    assert_eq!(
+        rwa_mint.asset_controller.as_ref(),
+        Some(&asset_controller.address)
+    );
+    assert_eq!(
+        rwa_mint.data_registry,
+        accounts.data_registry.map(|registry| registry.address)
+    );
+    assert_eq!(
+        rwa_mint.identity_registry,
+        accounts.identity_registry.map(|registry| registry.address)
+    );
+    assert_eq!(
+        rwa_mint.policy_engine,
+        accounts.policy_engine.map(|engine| engine.address)
+    );
+    assert_eq!(rwa_mint.policies, POLICY_ACCOUNTS.len() as u64);
+    let slot_updated = rwa_mint.slot_updated;
+
+    let rwa_api = &setup.rwa_api;
+    let count = |request: api::GetRwaMints| async move {
+        rwa_api.get_rwa_mints(request).await.unwrap().total
+    };
+    assert_eq!(
+        count(api::GetRwaMints {
+            has_policy_engine: Some(true),
+            ..Default::default()
+        })
+        .await,
+        1
+    );
+    assert_eq!(
+        count(api::GetRwaMints {
+            has_policy_engine: Some(false),
+            ..Default::default()
+        })
+        .await,
+        0
+    );
+    assert_eq!(
+        count(api::GetRwaMints {
+            authority: Some(asset_controller.authority.clone()),
+            ..Default::default()
+        })
+        .await,
+        1
+    );
+    assert_eq!(
+        count(api::GetRwaMints {
+            authority: Some(Pubkey::new_unique().to_string()),
+            ..Default::default()
+        })
+        .await,
+        0
+    );
+    assert_eq!(
+        count(api::GetRwaMints {
+            updated_since: Some(slot_updated as u64),
+            ..Default::default()
+        })
+        .await,
+        1
+    );
+    assert_eq!(
+        count(api::GetRwaMints {
+            updated_since: Some(slot_updated as u64 + 1),
+            ..Default::default()
+        })
+        .await,
+        0
+    );
+}
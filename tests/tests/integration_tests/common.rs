@@ -4,7 +4,8 @@ use std::path::Path;
 use std::str::FromStr;
 
 use common::utils;
-use rwa_api::api::RwaApi;
+use rwa_api::api::{self, ApiContract, RwaApi};
+use rwa_types::rapi::{AccountInclude, FullAccount};
 
 use rwa_api::config::Config;
 
@@ -28,15 +29,20 @@ use tokio::sync::Mutex;
 
 use futures_util::StreamExt as FuturesStreamExt;
 use futures_util::TryStreamExt;
+use itertools::Itertools;
 use tokio_stream::{self as stream};
 
 use log::error;
 // use rand::seq::SliceRandom;
 use serde::de::DeserializeOwned;
+use solana_account_decoder::UiDataSliceConfig;
 use solana_account_decoder::{UiAccount, UiAccountEncoding};
 use solana_client::{
-    client_error::Result as RpcClientResult, rpc_config::RpcAccountInfoConfig,
-    rpc_request::RpcRequest, rpc_response::Response as RpcResponse,
+    client_error::Result as RpcClientResult,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+    rpc_request::RpcRequest,
+    rpc_response::Response as RpcResponse,
 };
 use solana_sdk::{
     account::Account,
@@ -50,6 +56,21 @@ use tokio::time::sleep;
 
 pub const DEFAULT_SLOT: u64 = 1;
 
+/// The asset most tests index. Its fixtures are cached in each test's account directory.
+pub const RWA_MINT: &str = "Ea1yrC1xRXd6tWcHL4yhGRB31j6jTwdeqd3e9LHaYUwj";
+
+/// The policy accounts of the [`RWA_MINT`] asset.
+pub const POLICY_ACCOUNTS: [&str; 4] = [
+    "9ouBMZVbQeVLjpi6oewvM4aEc16cuQCB1mmwgrkVjCbL",
+    "DMX8iMbWnw82gSDjMQ9fVYNNYFj4f3ceKd7vHi3x3eMG",
+    "Cg5wADuxpSVQdAxhMwdCzDQzPwVLa6aTu3uG3eNTD2CY",
+    "5ynb2SyJSxF17DRq8vvDSQVV1xKx6g8B5LsnzUwEnKRe",
+];
+
+// Child accounts store their registry, and trackers and approvals their mint, right after the
+// discriminator and version.
+const REGISTRY_OFFSET: usize = 9;
+
 pub struct TestSetup {
     pub name: String,
     pub client: RpcClient,
@@ -167,6 +188,33 @@ pub async fn apply_migrations_and_delete_data(db: Arc<DatabaseConnection>) {
         .unwrap();
 }
 
+/// Creates the setup of the test called `function_name` and indexes `seeds` into a cleared
+/// database.
+pub async fn setup_with_seeds(function_name: &str, seeds: Vec<SeedEvent>) -> TestSetup {
+    let setup = TestSetup::new(trim_test_name(function_name)).await;
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+    setup
+}
+
+/// The registries of `mint`, with the `include`d child accounts.
+pub async fn get_full_account(
+    setup: &TestSetup,
+    mint: &str,
+    include: Vec<AccountInclude>,
+) -> FullAccount {
+    let request = api::GetRwaAccountsByMint {
+        id: mint.to_string(),
+        include: Some(include),
+        ..Default::default()
+    };
+    setup
+        .rwa_api
+        .get_rwa_accounts_by_mint(request)
+        .await
+        .unwrap()
+}
+
 // Util functions for accounts
 pub async fn rpc_tx_with_retries<T, E>(
     client: &RpcClient,
@@ -246,6 +294,18 @@ pub async fn fetch_and_serialize_account(
         }
     };
 
+    Ok(serialize_account_bytes(
+        pubkey,
+        &account,
+        match slot {
+            Some(slot) => slot,
+            None => actual_slot,
+        },
+    ))
+}
+
+/// Serializes `account` the way the geyser plugin sends it, for accounts built in a test.
+pub fn serialize_account_bytes(pubkey: Pubkey, account: &Account, slot: u64) -> Vec<u8> {
     let fbb = flatbuffers::FlatBufferBuilder::new();
     let account_info = ReplicaAccountInfoV2 {
         pubkey: &pubkey.to_bytes(),
@@ -259,16 +319,8 @@ pub async fn fetch_and_serialize_account(
     };
     let is_startup = false;
 
-    let fbb = serialize_account(
-        fbb,
-        &account_info,
-        match slot {
-            Some(slot) => slot,
-            None => actual_slot,
-        },
-        is_startup,
-    );
-    Ok(fbb.finished_data().to_vec())
+    let fbb = serialize_account(fbb, &account_info, slot, is_startup);
+    fbb.finished_data().to_vec()
 }
 
 pub async fn index_account_bytes(setup: &TestSetup, account_bytes: Vec<u8>) {
@@ -320,6 +372,8 @@ async fn cached_fetch_account_with_error_handling(
 pub enum SeedEvent {
     Account(Pubkey),
     TokenMint(Pubkey),
+    /// The token mint and the child accounts of its asset controller and registries.
+    MintWithChildren(Pubkey),
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -344,6 +398,12 @@ pub async fn index_seed_events(setup: &TestSetup, events: Vec<&SeedEvent>) {
             SeedEvent::TokenMint(mint) => {
                 index_token_mint(setup, *mint).await;
             }
+            SeedEvent::MintWithChildren(mint) => {
+                index_token_mint(setup, *mint).await;
+                for child in cached_fetch_child_accounts(setup, *mint).await {
+                    index_account(setup, child.address).await;
+                }
+            }
         }
     }
 }
@@ -356,6 +416,10 @@ pub fn seed_token_mint(str: &str) -> SeedEvent {
     SeedEvent::TokenMint(Pubkey::from_str(str).unwrap())
 }
 
+pub fn seed_mint_with_children(str: &str) -> SeedEvent {
+    SeedEvent::MintWithChildren(Pubkey::from_str(str).unwrap())
+}
+
 pub fn seed_accounts<I>(strs: I) -> Vec<SeedEvent>
 where
     I: IntoIterator,
@@ -426,6 +490,95 @@ async fn index_token_mint(setup: &TestSetup, mint: Pubkey) {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChildAccount {
+    pub program: Pubkey,
+    pub address: Pubkey,
+}
+
+/// The accounts pointing at `mint`'s asset controller or registries: trackers, approvals and
+/// the data, identity and policy accounts. The list is cached next to the test's accounts, the
+/// accounts themselves are fetched with [`cached_fetch_account`].
+pub async fn cached_fetch_child_accounts(setup: &TestSetup, mint: Pubkey) -> Vec<ChildAccount> {
+    let dir = get_relative_project_path(&format!("tests/data/accounts/{}", setup.name));
+    if !Path::new(&dir).exists() {
+        std::fs::create_dir(&dir).unwrap();
+    }
+    let file_path = dir.join(format!("{}.children", mint));
+
+    if file_path.exists() {
+        return std::fs::read_to_string(file_path)
+            .unwrap()
+            .lines()
+            .map(|line| {
+                let (program, address) = line.split_once(' ').unwrap();
+                ChildAccount {
+                    program: Pubkey::from_str(program).unwrap(),
+                    address: Pubkey::from_str(address).unwrap(),
+                }
+            })
+            .collect();
+    }
+
+    let program_ids = setup.config.get_program_ids();
+    let parents = [
+        (program_ids.asset_controller, mint),
+        (
+            program_ids.data_registry,
+            program_ids.find_data_registry_pda(&mint).0,
+        ),
+        (
+            program_ids.identity_registry,
+            program_ids.find_identifier_registry_pda(&mint).0,
+        ),
+        (
+            program_ids.policy_engine,
+            program_ids.find_policy_engine_pda(&mint).0,
+        ),
+    ];
+    let mut children = Vec::new();
+    for (program, parent) in parents {
+        let accounts = setup
+            .client
+            .get_program_accounts_with_config(
+                &program,
+                RpcProgramAccountsConfig {
+                    filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                        REGISTRY_OFFSET,
+                        parent.to_bytes().to_vec(),
+                    ))]),
+                    account_config: RpcAccountInfoConfig {
+                        encoding: Some(UiAccountEncoding::Base64),
+                        // Only the addresses are needed.
+                        data_slice: Some(UiDataSliceConfig {
+                            offset: 0,
+                            length: 0,
+                        }),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        children.extend(
+            accounts
+                .into_iter()
+                .map(|(address, _)| ChildAccount { program, address })
+                // The asset controller stores the mint at the same offset.
+                .filter(|child| child.address != program_ids.find_asset_controller_pda(&mint).0)
+                .sorted_by_key(|child| child.address),
+        );
+    }
+
+    let lines = children
+        .iter()
+        .map(|child| format!("{} {}\n", child.program, child.address))
+        .collect::<String>();
+    std::fs::write(file_path, lines).unwrap();
+    children
+}
+
 pub fn trim_test_name(name: &str) -> String {
     name.replace("test_", "")
 }
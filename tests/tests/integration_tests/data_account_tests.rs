@@ -0,0 +1,49 @@
+use function_name::named;
+
+use itertools::Itertools;
+use rwa_api::api::{self, ApiContract};
+
+use serial_test::serial;
+
+use super::common::*;
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_get_data_accounts_by_mint_filtered_by_type() {
+    let setup = setup_with_seeds(function_name!(), vec![seed_mint_with_children(RWA_MINT)]).await;
+
+    let request = api::GetDataAccountsByMint {
+        mint: Some(RWA_MINT.to_string()),
+        limit: Some(100),
+        ..Default::default()
+    };
+    let all = setup
+        .rwa_api
+        .get_data_accounts_by_mint(request)
+        .await
+        .unwrap();
+    assert!(!all.items.is_empty());
+    assert_eq!(all.total, all.items.len() as u64);
+
+    let data_type = all.items[0].data_type;
+    let request = api::GetDataAccountsByMint {
+        mint: Some(RWA_MINT.to_string()),
+        data_types: Some(vec![data_type]),
+        limit: Some(100),
+        ..Default::default()
+    };
+    let filtered = setup
+        .rwa_api
+        .get_data_accounts_by_mint(request)
+        .await
+        .unwrap();
+
+    let expected = all
+        .items
+        .into_iter()
+        .filter(|account| account.data_type == data_type)
+        .collect_vec();
+    assert_eq!(filtered.total, expected.len() as u64);
+    assert_eq!(filtered.items, expected);
+}
@@ -0,0 +1,145 @@
+use function_name::named;
+
+use indexer::{
+    dead_letter::store_failed_account_update,
+    metrics::{capture_result, CapturedMessage, MessageOutcome},
+};
+use plerkle_messenger::ACCOUNT_STREAM;
+use plerkle_serialization::root_as_account_info;
+use rwa_types::dao::failed_account_update;
+use sea_orm::EntityTrait;
+use serial_test::serial;
+use solana_sdk::{account::Account, hash::hash, pubkey::Pubkey};
+use tokio::time::Instant;
+use transformer::{
+    program_handler::ProgramParser, programs::token_extensions::TokenExtensionsParser,
+};
+
+use super::common::*;
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_failing_account_update_is_dead_lettered() {
+    // Every account is built here, so there are no fixtures to seed.
+    let setup = setup_with_seeds(function_name!(), vec![]).await;
+
+    // A policy account cut short after its discriminator can't be deserialized.
+    let pubkey = Pubkey::new_unique();
+    let owner = setup.config.get_program_ids().policy_engine;
+    let mut data = hash(b"account:PolicyAccount").to_bytes()[..8].to_vec();
+    data.extend_from_slice(&[1, 2, 3]);
+    let account = Account {
+        lamports: 1_000_000,
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    };
+    let payload = serialize_account_bytes(pubkey, &account, DEFAULT_SLOT);
+    let account_update = root_as_account_info(&payload).unwrap();
+
+    let result = setup
+        .transformer
+        .handle_account_update(account_update, &setup.config)
+        .await;
+    assert!(result.is_err());
+
+    // The last allowed try gives up on the message instead of asking for a redelivery.
+    let max_retries = setup.config.get_max_retries();
+    let outcome = capture_result(
+        CapturedMessage {
+            stream: ACCOUNT_STREAM,
+            label: ("owner", &owner.to_string()),
+            tries: max_retries,
+            max_retries,
+            proc: Instant::now(),
+            txn_sig: None,
+            account: Some(pubkey.to_string()),
+        },
+        result,
+    );
+    let error = match outcome {
+        MessageOutcome::DeadLetter(error) => error,
+        outcome => panic!("Expected the update to be dead-lettered, got {:?}", outcome),
+    };
+    assert!(!error.is_empty());
+
+    store_failed_account_update(
+        setup.db.as_ref(),
+        ACCOUNT_STREAM,
+        &payload,
+        Some(&account_update),
+        &error,
+        max_retries,
+    )
+    .await
+    .unwrap();
+
+    let stored = failed_account_update::Entity::find()
+        .all(setup.db.as_ref())
+        .await
+        .unwrap();
+    assert_eq!(stored.len(), 1);
+    let stored = &stored[0];
+    assert_eq!(stored.pubkey, Some(pubkey.to_bytes().to_vec()));
+    assert_eq!(stored.owner, Some(owner.to_bytes().to_vec()));
+    assert_eq!(stored.slot, Some(DEFAULT_SLOT as i64));
+    assert_eq!(stored.stream, ACCOUNT_STREAM);
+    assert_eq!(stored.payload, payload);
+    assert_eq!(stored.error, error);
+    assert_eq!(stored.tries, max_retries as i32);
+    assert_eq!(stored.replayed_at, None);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_unrelated_account_updates_are_acked() {
+    let setup = setup_with_seeds(function_name!(), vec![]).await;
+
+    // An account of a type the policy engine parser doesn't know, and a Token-2022 account
+    // that fails to unpack and belongs to a mint no asset uses.
+    let policy_engine = setup.config.get_program_ids().policy_engine;
+    let unknown_type = hash(b"account:Unknown").to_bytes()[..8].to_vec();
+    // The base Token-2022 account length, left uninitialized past its mint.
+    let mut uninitialized = Pubkey::new_unique().to_bytes().to_vec();
+    uninitialized.resize(165, 0);
+    let updates = [
+        (policy_engine, unknown_type),
+        (TokenExtensionsParser {}.key(), uninitialized),
+    ];
+
+    let max_retries = setup.config.get_max_retries();
+    for (owner, data) in updates {
+        let pubkey = Pubkey::new_unique();
+        let account = Account {
+            lamports: 1_000_000,
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        };
+        let payload = serialize_account_bytes(pubkey, &account, DEFAULT_SLOT);
+        let result = setup
+            .transformer
+            .handle_account_update(root_as_account_info(&payload).unwrap(), &setup.config)
+            .await;
+        assert!(result.is_err());
+
+        // Even on the last allowed try, these are acked instead of dead-lettered.
+        let outcome = capture_result(
+            CapturedMessage {
+                stream: ACCOUNT_STREAM,
+                label: ("owner", &owner.to_string()),
+                tries: max_retries,
+                max_retries,
+                proc: Instant::now(),
+                txn_sig: None,
+                account: Some(pubkey.to_string()),
+            },
+            result,
+        );
+        assert_eq!(outcome, MessageOutcome::Ack);
+    }
+}
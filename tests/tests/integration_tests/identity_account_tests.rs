@@ -0,0 +1,46 @@
+use function_name::named;
+
+use rwa_api::api::{self, ApiContract};
+use rwa_types::rapi::AccountInclude;
+
+use serial_test::serial;
+
+use super::common::*;
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_get_identity_accounts_by_owner() {
+    let setup = setup_with_seeds(function_name!(), vec![seed_mint_with_children(RWA_MINT)]).await;
+
+    let identity_registry = get_full_account(&setup, RWA_MINT, vec![AccountInclude::Identities])
+        .await
+        .identity_registry
+        .unwrap();
+    let identity = identity_registry.identities.unwrap().items.remove(0);
+
+    let request = api::GetIdentityAccountsByOwner {
+        owner: identity.owner.clone(),
+        ..Default::default()
+    };
+    let response = setup
+        .rwa_api
+        .get_identity_accounts_by_owner(request)
+        .await
+        .unwrap();
+
+    assert!(response
+        .items
+        .iter()
+        .any(|account| account.address == identity.address));
+    for account in response.items {
+        assert_eq!(account.owner, identity.owner);
+        assert!(!account.closed);
+        if account.address == identity.address {
+            assert_eq!(account.mint, RWA_MINT);
+            assert_eq!(account.identity_registry, identity_registry.address);
+            assert_eq!(account.registry_authority, identity_registry.authority);
+            assert_eq!(account.levels, identity.levels);
+        }
+    }
+}
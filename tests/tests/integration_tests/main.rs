@@ -1,2 +1,9 @@
 mod account_update_tests;
+mod asset_tests;
 mod common;
+mod data_account_tests;
+mod dead_letter_tests;
+mod identity_account_tests;
+mod pagination_tests;
+mod policy_account_tests;
+mod raw_account_tests;
@@ -0,0 +1,186 @@
+use std::str::FromStr;
+
+use function_name::named;
+
+use itertools::Itertools;
+use rwa_api::api::{self, ApiContract};
+use rwa_types::{
+    dao::token_account,
+    rapi::{AccountInclude, SortBy, SortDirection},
+};
+use sea_orm::{prelude::Decimal, ActiveValue::Set, EntityTrait};
+
+use serial_test::serial;
+use solana_sdk::pubkey::Pubkey;
+
+use super::common::*;
+
+async fn get_policy_page(
+    setup: &TestSetup,
+    before: Option<String>,
+    after: Option<String>,
+) -> (Vec<String>, Option<String>) {
+    let request = api::GetPolicyAccounts {
+        mint: Some(RWA_MINT.to_string()),
+        limit: Some(2),
+        before,
+        after,
+        ..Default::default()
+    };
+    let response = setup.rwa_api.get_policy_accounts(request).await.unwrap();
+    assert_eq!(response.total, 4);
+    let addresses = response
+        .items
+        .into_iter()
+        .map(|policy| policy.address)
+        .collect();
+    (addresses, response.cursor)
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_get_policy_accounts_forward_and_back() {
+    let mut seeds = vec![seed_token_mint(RWA_MINT)];
+    seeds.extend(seed_accounts(POLICY_ACCOUNTS));
+    let setup = setup_with_seeds(function_name!(), seeds).await;
+
+    // Ids sort by their bytes, not their base58 text.
+    let by_id = POLICY_ACCOUNTS
+        .iter()
+        .map(|address| Pubkey::from_str(address).unwrap())
+        .sorted_by_key(|pubkey| pubkey.to_bytes())
+        .map(|pubkey| pubkey.to_string())
+        .collect_vec();
+
+    let (first, cursor) = get_policy_page(&setup, None, None).await;
+    assert_eq!(first, by_id[0..2]);
+
+    let (second, cursor) = get_policy_page(&setup, None, cursor).await;
+    assert_eq!(second, by_id[2..4]);
+
+    // Walking back from the last item returns the two before it, still in ascending order, with
+    // the cursor on the first of them.
+    let (back, cursor) = get_policy_page(&setup, cursor, None).await;
+    assert_eq!(back, by_id[1..3]);
+
+    let (start, cursor) = get_policy_page(&setup, cursor, None).await;
+    assert_eq!(start, by_id[0..1]);
+
+    let (empty, cursor) = get_policy_page(&setup, cursor, None).await;
+    assert!(empty.is_empty());
+    assert_eq!(cursor, None);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_get_nested_policy_accounts_by_cursor() {
+    let mut seeds = vec![seed_token_mint(RWA_MINT)];
+    seeds.extend(seed_accounts(POLICY_ACCOUNTS));
+    let setup = setup_with_seeds(function_name!(), seeds).await;
+
+    // The nested collection pages like getPolicyAccounts, newest first here.
+    let get_page = |after: Option<String>| api::GetRwaAccountsByMint {
+        id: RWA_MINT.to_string(),
+        include: Some(vec![AccountInclude::Policies]),
+        limit: Some(3),
+        after,
+        sort_by: Some(SortBy::SlotUpdated),
+        sort_direction: Some(SortDirection::Desc),
+        ..Default::default()
+    };
+    let mut addresses = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = setup
+            .rwa_api
+            .get_rwa_accounts_by_mint(get_page(cursor))
+            .await
+            .unwrap()
+            .policy_engine
+            .unwrap()
+            .policy_accounts
+            .unwrap();
+        assert_eq!(page.total, POLICY_ACCOUNTS.len() as u64);
+        if page.items.is_empty() {
+            break;
+        }
+        addresses.extend(page.items.into_iter().map(|policy| policy.address));
+        cursor = page.cursor;
+    }
+
+    // The policy fixtures share a slot, so the id decides their order, across pages too.
+    let by_id = POLICY_ACCOUNTS
+        .iter()
+        .map(|address| Pubkey::from_str(address).unwrap())
+        .sorted_by_key(|pubkey| std::cmp::Reverse(pubkey.to_bytes()))
+        .map(|pubkey| pubkey.to_string())
+        .collect_vec();
+    assert_eq!(addresses, by_id);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_get_rwa_holders_with_equal_amounts() {
+    let setup = setup_with_seeds(function_name!(), vec![]).await;
+
+    // Stored directly: only the balances matter for the holder listing.
+    let mint = Pubkey::from_str(RWA_MINT).unwrap();
+    let balances: Vec<(Pubkey, u64)> = vec![
+        (Pubkey::new_unique(), 1_000),
+        (Pubkey::new_unique(), 500),
+        (Pubkey::new_unique(), 500),
+    ];
+    for (owner, amount) in &balances {
+        let token_account = token_account::ActiveModel {
+            id: Set(Pubkey::new_unique().to_bytes().to_vec()),
+            mint: Set(mint.to_bytes().to_vec()),
+            owner: Set(owner.to_bytes().to_vec()),
+            delegate: Set(None),
+            frozen: Set(false),
+            closed: Set(false),
+            closed_at_slot: Set(None),
+            slot_updated: Set(DEFAULT_SLOT as i64),
+            amount: Set(Decimal::from(*amount)),
+            ..Default::default()
+        };
+        token_account::Entity::insert(token_account)
+            .exec(setup.db.as_ref())
+            .await
+            .unwrap();
+    }
+
+    // Largest first, ties broken by owner in the same direction. One holder per page puts the
+    // two equal balances on either side of a page boundary.
+    let expected = balances
+        .iter()
+        .sorted_by_key(|(owner, amount)| std::cmp::Reverse((*amount, owner.to_bytes())))
+        .map(|(owner, amount)| (owner.to_string(), amount.to_string()))
+        .collect_vec();
+    let mut holders = Vec::new();
+    let mut cursor = None;
+    loop {
+        let request = api::GetRwaHolders {
+            mint: RWA_MINT.to_string(),
+            limit: Some(1),
+            after: cursor,
+            ..Default::default()
+        };
+        let response = setup.rwa_api.get_rwa_holders(request).await.unwrap();
+        assert_eq!(response.holders.total, balances.len() as u64);
+        if response.holders.items.is_empty() {
+            break;
+        }
+        holders.extend(
+            response
+                .holders
+                .items
+                .into_iter()
+                .map(|holder| (holder.owner, holder.amount)),
+        );
+        cursor = response.holders.cursor;
+    }
+    assert_eq!(holders, expected);
+}
@@ -0,0 +1,191 @@
+use std::str::FromStr;
+
+use function_name::named;
+
+use rwa_api::api::{self, ApiContract};
+use rwa_types::{
+    dao::tracker_account,
+    rapi::{AccountInclude, ComparisonType, PolicyAccountType},
+};
+use sea_orm::{ActiveValue::Set, EntityTrait};
+use serde_json::json;
+
+use serial_test::serial;
+use solana_sdk::pubkey::Pubkey;
+
+use super::common::*;
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_get_policy_accounts_decoded() {
+    let mut seeds = vec![seed_token_mint(RWA_MINT)];
+    seeds.extend(seed_accounts(POLICY_ACCOUNTS));
+    let setup = setup_with_seeds(function_name!(), seeds).await;
+
+    let request = api::GetPolicyAccounts {
+        mint: Some(RWA_MINT.to_string()),
+        ..Default::default()
+    };
+    let response = setup.rwa_api.get_policy_accounts(request).await.unwrap();
+    assert_eq!(response.total, POLICY_ACCOUNTS.len() as u64);
+
+    let policy_engine = setup
+        .config
+        .get_program_ids()
+        .find_policy_engine_pda(&Pubkey::from_str(RWA_MINT).unwrap())
+        .0
+        .to_string();
+    // (address, type, comparison, identity levels, limit, timeframe) of the fixtures.
+    let expected = [
+        (
+            POLICY_ACCOUNTS[0],
+            PolicyAccountType::IdentityApproval,
+            ComparisonType::Or,
+            vec![1],
+            None,
+            None,
+        ),
+        (
+            POLICY_ACCOUNTS[1],
+            PolicyAccountType::TransactionAmountLimit,
+            ComparisonType::Or,
+            vec![1],
+            Some("1000000"),
+            None,
+        ),
+        (
+            POLICY_ACCOUNTS[2],
+            PolicyAccountType::TransactionAmountVelocity,
+            ComparisonType::And,
+            vec![1, 2],
+            Some("5000000"),
+            Some(86_400),
+        ),
+        (
+            POLICY_ACCOUNTS[3],
+            PolicyAccountType::TransactionCountVelocity,
+            ComparisonType::Or,
+            vec![2],
+            Some("10"),
+            Some(3_600),
+        ),
+    ];
+    for (address, policy_type, comparison_type, identity_levels, limit, timeframe) in expected {
+        let policy = response
+            .items
+            .iter()
+            .find(|policy| policy.address == address)
+            .unwrap();
+        assert_eq!(policy.mint, RWA_MINT);
+        assert_eq!(policy.policy_engine, policy_engine);
+        assert_eq!(policy.policy_type, policy_type, "{address}");
+        assert_eq!(policy.comparison_type, comparison_type, "{address}");
+        // Unused identity level slots are left out.
+        assert_eq!(policy.identity_levels, identity_levels, "{address}");
+        assert_eq!(policy.limit.as_deref(), limit, "{address}");
+        assert_eq!(policy.timeframe, timeframe, "{address}");
+        assert!(!policy.closed);
+    }
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_get_velocity_allowance() {
+    let setup = setup_with_seeds(function_name!(), vec![seed_mint_with_children(RWA_MINT)]).await;
+
+    // The authority holds identity levels 1 and 2, so both velocity policies apply to it.
+    let identities = get_full_account(&setup, RWA_MINT, vec![AccountInclude::Identities])
+        .await
+        .identity_registry
+        .unwrap()
+        .identities
+        .unwrap()
+        .items;
+    let owner = identities
+        .iter()
+        .find(|identity| identity.levels == vec![1, 2])
+        .unwrap()
+        .owner
+        .clone();
+
+    // Stored directly: the transfers are what matters here, not how the tracker was indexed.
+    let timestamp = 1_700_000_000;
+    let tracker = tracker_account::ActiveModel {
+        id: Set(Pubkey::new_unique().to_bytes().to_vec()),
+        asset_mint: Set(Pubkey::from_str(RWA_MINT).unwrap().to_bytes().to_vec()),
+        owner: Set(Pubkey::from_str(&owner).unwrap().to_bytes().to_vec()),
+        transfer_amounts: Set(Some(
+            json!({ "transfer_amounts": [1_000_000, 250_000, 40, 0] }),
+        )),
+        transfer_timestamps: Set(Some(json!({
+            "transfer_timestamps": [timestamp - 100_000, timestamp - 5_000, timestamp - 10, 0]
+        }))),
+        closed: Set(false),
+        closed_at_slot: Set(None),
+        slot_updated: Set(DEFAULT_SLOT as i64),
+        ..Default::default()
+    };
+    tracker_account::Entity::insert(tracker)
+        .exec(setup.db.as_ref())
+        .await
+        .unwrap();
+
+    let request = api::GetVelocityAllowance {
+        mint: RWA_MINT.to_string(),
+        owner: owner.clone(),
+        timestamp: Some(timestamp),
+    };
+    let response = setup.rwa_api.get_velocity_allowance(request).await.unwrap();
+    assert_eq!(response.mint, RWA_MINT);
+    assert_eq!(response.owner, owner);
+    assert_eq!(response.timestamp, timestamp);
+
+    // The day long amount window holds the last two transfers, the hour long count window only
+    // the last one.
+    let amount = response
+        .policies
+        .iter()
+        .find(|allowance| allowance.policy_account == POLICY_ACCOUNTS[2])
+        .unwrap();
+    assert!(amount.applies);
+    assert_eq!(amount.used, "250040");
+    assert_eq!(amount.remaining.as_deref(), Some("4749960"));
+    assert_eq!(amount.next_release_at, Some(timestamp - 5_000 + 86_400));
+    assert_eq!(amount.resets_at, Some(timestamp - 10 + 86_400));
+
+    let count = response
+        .policies
+        .iter()
+        .find(|allowance| allowance.policy_account == POLICY_ACCOUNTS[3])
+        .unwrap();
+    assert!(count.applies);
+    assert_eq!(count.used, "1");
+    assert_eq!(count.remaining.as_deref(), Some("9"));
+
+    assert_eq!(response.policies.len(), 2);
+    assert_eq!(response.remaining_amount.as_deref(), Some("4749960"));
+    assert_eq!(response.remaining_transfers.as_deref(), Some("9"));
+
+    // Level 1 alone matches neither policy, and without a tracker nothing is used.
+    let holder = identities
+        .iter()
+        .find(|identity| identity.levels == vec![1])
+        .unwrap()
+        .owner
+        .clone();
+    let request = api::GetVelocityAllowance {
+        mint: RWA_MINT.to_string(),
+        owner: holder,
+        timestamp: Some(timestamp),
+    };
+    let response = setup.rwa_api.get_velocity_allowance(request).await.unwrap();
+    assert_eq!(response.policies.len(), 2);
+    assert!(response
+        .policies
+        .iter()
+        .all(|allowance| !allowance.applies && allowance.used == "0"));
+    assert_eq!(response.remaining_amount, None);
+    assert_eq!(response.remaining_transfers, None);
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use function_name::named;
+
+use indexer::program_handlers::ProgramRegistry;
+use rwa_types::dao::raw_account;
+use sea_orm::EntityTrait;
+use serde_json::json;
+use serial_test::serial;
+use solana_sdk::{account::Account, hash::hash, pubkey::Pubkey};
+use transformer::programs::idl::IdlDecoder;
+
+use super::common::*;
+
+// An account type the asset controller parser doesn't know.
+const ESCROW_IDL: &str = r#"{
+    "version": "0.1.0",
+    "name": "asset_controller",
+    "accounts": [
+        {
+            "name": "Escrow",
+            "type": {
+                "kind": "struct",
+                "fields": [
+                    { "name": "owner", "type": "publicKey" },
+                    { "name": "amount", "type": "u64" }
+                ]
+            }
+        }
+    ]
+}"#;
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_unknown_account_is_stored_raw_with_idl() {
+    // The escrow is built here, so there are no fixtures to seed.
+    let mut setup = setup_with_seeds(function_name!(), vec![]).await;
+    let program_ids = setup.config.get_program_ids();
+    let mut programs = ProgramRegistry::rwa_programs(&program_ids);
+    programs
+        .set_idl(
+            &program_ids.asset_controller,
+            IdlDecoder::from_json(ESCROW_IDL).unwrap(),
+        )
+        .unwrap();
+    setup.transformer = setup.transformer.with_programs(Arc::new(programs));
+
+    let escrow = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let mut data = hash(b"account:Escrow").to_bytes()[..8].to_vec();
+    data.extend_from_slice(&owner.to_bytes());
+    data.extend_from_slice(&500u64.to_le_bytes());
+    let account = Account {
+        lamports: 1_000_000,
+        data,
+        owner: program_ids.asset_controller,
+        executable: false,
+        rent_epoch: 0,
+    };
+    index_account_bytes(
+        &setup,
+        serialize_account_bytes(escrow, &account, DEFAULT_SLOT),
+    )
+    .await;
+
+    let stored = raw_account::Entity::find_by_id(escrow.to_bytes().to_vec())
+        .one(setup.db.as_ref())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        stored.program_id,
+        program_ids.asset_controller.to_bytes().to_vec()
+    );
+    assert_eq!(stored.account_type, "Escrow");
+    assert_eq!(
+        stored.raw_decoded,
+        json!({ "owner": owner.to_string(), "amount": "500" })
+    );
+    assert!(!stored.closed);
+    assert_eq!(stored.slot_updated, DEFAULT_SLOT as i64);
+}
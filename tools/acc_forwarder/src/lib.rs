@@ -1,7 +1,5 @@
-use common::utils::{
-    ASSET_CONTROLLER_PROGRAM_ID, DATA_REGISTRY_PROGRAM_ID, IDENTIFIER_REGISTRY_PROGRAM_ID,
-    POLICY_ENGINE_PROGRAM_ID,
-};
+use common::utils::{ProgramIds, APPROVAL_ACCOUNT_LEN, TOKEN_2022_PROGRAM_ID};
+use solana_account_decoder::UiDataSliceConfig;
 use solana_client::{
     rpc_config::RpcProgramAccountsConfig,
     rpc_filter::{Memcmp, RpcFilterType},
@@ -10,7 +8,7 @@ use {
     anyhow::Context,
     futures::stream::{BoxStream, StreamExt},
     log::{error, info},
-    plerkle_messenger::ACCOUNT_STREAM,
+    plerkle_messenger::{Messenger, MessengerConfig},
     plerkle_serialization::{
         serializer::serialize_account, solana_geyser_plugin_interface_shims::ReplicaAccountInfoV2,
     },
@@ -25,7 +23,9 @@ use {
     solana_sdk::{
         account::Account,
         commitment_config::{CommitmentConfig, CommitmentLevel},
+        hash::hash,
     },
+    sqlx::PgPool,
     std::{fmt, io::Result as IoResult, sync::Arc},
     tokio::sync::Mutex,
     tokio::{
@@ -38,12 +38,55 @@ use {
 
 const REGISTRY_OFFSET: usize = 9;
 
+/// A messenger connection and the stream accounts are sent to, shared by everything sending
+/// through it.
+#[derive(Clone)]
+pub struct AccountStream {
+    messenger: Arc<Mutex<Box<dyn Messenger>>>,
+    stream: &'static str,
+}
+
+impl AccountStream {
+    pub async fn connect(
+        config: MessengerConfig,
+        stream: &'static str,
+        buffer_size: usize,
+    ) -> anyhow::Result<Self> {
+        let mut messenger = plerkle_messenger::select_messenger(config).await?;
+        messenger.add_stream(stream).await?;
+        messenger.set_buffer_size(stream, buffer_size).await;
+        Ok(AccountStream {
+            messenger: Arc::new(Mutex::new(messenger)),
+            stream,
+        })
+    }
+
+    pub async fn send(&self, bytes: &[u8]) -> anyhow::Result<()> {
+        self.messenger.lock().await.send(self.stream, bytes).await?;
+        Ok(())
+    }
+}
+
+/// Anchor's discriminator of the account type `name`.
+fn account_discriminator(name: &str) -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(format!("account:{name}").as_bytes()).to_bytes()[..8]);
+    discriminator
+}
+
+/// Sends every account of `program` matching `filters` and returns how many were sent.
 pub async fn fetch_and_send_program_accounts(
     program: Pubkey,
     client: &RpcClient,
-    messenger: &Arc<Mutex<Box<dyn plerkle_messenger::Messenger>>>,
+    messenger: &AccountStream,
     filters: Vec<RpcFilterType>,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<usize> {
+    // Taken before the snapshot so stream updates for the same accounts always win the slot guard.
+    let current_slot = client
+        .get_slot()
+        .await
+        .context("Failed to get current slot")?;
+
     let accounts = client
         .get_program_accounts_with_config(
             &program,
@@ -58,28 +101,88 @@ pub async fn fetch_and_send_program_accounts(
         )
         .await?;
 
-    let current_slot = client
-        .get_slot()
-        .await
-        .context("Failed to get current slot")?;
-
+    let sent = accounts.len();
     for (account_pubkey, account_info) in accounts {
         send_account(account_pubkey, account_info, current_slot, messenger)
             .await
             .context(format!("Failed to send account {}", account_pubkey))?;
     }
-    Ok(())
+    Ok(sent)
+}
+
+/// The mints of every asset controller deployed under `program_ids`.
+pub async fn fetch_asset_mints(
+    program_ids: &ProgramIds,
+    client: &RpcClient,
+) -> anyhow::Result<Vec<Pubkey>> {
+    let accounts = client
+        .get_program_accounts_with_config(
+            &program_ids.asset_controller,
+            RpcProgramAccountsConfig {
+                filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                    0,
+                    account_discriminator("AssetControllerAccount").to_vec(),
+                ))]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    // Only the asset mint, which follows the discriminator and version.
+                    data_slice: Some(UiDataSliceConfig {
+                        offset: REGISTRY_OFFSET,
+                        length: 32,
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .await?;
+    accounts
+        .into_iter()
+        .map(|(pubkey, account)| {
+            Pubkey::try_from(account.data.as_slice())
+                .map_err(|_| anyhow::anyhow!("invalid asset controller {pubkey}"))
+        })
+        .collect()
+}
+
+/// Sends `mint`, its registries, their child accounts and the mint's token accounts, like
+/// `acc_forwarder mint` does. Returns how many accounts were sent.
+pub async fn fetch_and_send_mint(
+    mint: Pubkey,
+    program_ids: &ProgramIds,
+    client: &RpcClient,
+    messenger: &AccountStream,
+) -> anyhow::Result<usize> {
+    let asset_controller_pda = program_ids.find_asset_controller_pda(&mint).0;
+    let data_pda = program_ids.find_data_registry_pda(&mint).0;
+    let identifier_pda = program_ids.find_identifier_registry_pda(&mint).0;
+    let policy_pda = program_ids.find_policy_engine_pda(&mint).0;
+
+    let mut sent = usize::from(fetch_and_send_account(mint, client, messenger, false).await?);
+    for pubkey in &[asset_controller_pda, data_pda, identifier_pda, policy_pda] {
+        sent += usize::from(fetch_and_send_account(*pubkey, client, messenger, true).await?);
+    }
+    sent += fetch_and_send_tracker_account(mint, program_ids, client, messenger).await?;
+    sent += fetch_and_send_approval_accounts(mint, program_ids, client, messenger).await?;
+    sent += fetch_and_send_data_accounts(data_pda, program_ids, client, messenger).await?;
+    sent +=
+        fetch_and_send_identity_accounts(identifier_pda, program_ids, client, messenger).await?;
+    sent += fetch_and_send_policy_accounts(policy_pda, program_ids, client, messenger).await?;
+    // Sent last so the asset controller is usually indexed before the holders arrive.
+    sent += fetch_and_send_token_accounts(mint, client, messenger).await?;
+    Ok(sent)
 }
 
 pub async fn fetch_and_send_identity_accounts(
     registry: Pubkey,
+    program_ids: &ProgramIds,
     client: &RpcClient,
-    messenger: &Arc<Mutex<Box<dyn plerkle_messenger::Messenger>>>,
-) -> anyhow::Result<()> {
+    messenger: &AccountStream,
+) -> anyhow::Result<usize> {
     const IDENTITY_ACCOUNT_LEN: u64 = 83;
 
     fetch_and_send_program_accounts(
-        IDENTIFIER_REGISTRY_PROGRAM_ID,
+        program_ids.identity_registry,
         client,
         messenger,
         vec![
@@ -90,19 +193,19 @@ pub async fn fetch_and_send_identity_accounts(
             )),
         ],
     )
-    .await?;
-    Ok(())
+    .await
 }
 
 pub async fn fetch_and_send_tracker_account(
     registry: Pubkey,
+    program_ids: &ProgramIds,
     client: &RpcClient,
-    messenger: &Arc<Mutex<Box<dyn plerkle_messenger::Messenger>>>,
-) -> anyhow::Result<()> {
+    messenger: &AccountStream,
+) -> anyhow::Result<usize> {
     const TRACKER_ACCOUNT_LEN: u64 = 473;
 
     fetch_and_send_program_accounts(
-        ASSET_CONTROLLER_PROGRAM_ID,
+        program_ids.asset_controller,
         client,
         messenger,
         vec![
@@ -113,19 +216,40 @@ pub async fn fetch_and_send_tracker_account(
             )),
         ],
     )
-    .await?;
-    Ok(())
+    .await
+}
+
+pub async fn fetch_and_send_approval_accounts(
+    mint: Pubkey,
+    program_ids: &ProgramIds,
+    client: &RpcClient,
+    messenger: &AccountStream,
+) -> anyhow::Result<usize> {
+    fetch_and_send_program_accounts(
+        program_ids.asset_controller,
+        client,
+        messenger,
+        vec![
+            RpcFilterType::DataSize(APPROVAL_ACCOUNT_LEN),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                REGISTRY_OFFSET,
+                mint.to_bytes().to_vec(),
+            )),
+        ],
+    )
+    .await
 }
 
 pub async fn fetch_and_send_data_accounts(
     registry: Pubkey,
+    program_ids: &ProgramIds,
     client: &RpcClient,
-    messenger: &Arc<Mutex<Box<dyn plerkle_messenger::Messenger>>>,
-) -> anyhow::Result<()> {
+    messenger: &AccountStream,
+) -> anyhow::Result<usize> {
     const DATA_ACCOUNT_LEN: u64 = 337;
 
     fetch_and_send_program_accounts(
-        DATA_REGISTRY_PROGRAM_ID,
+        program_ids.data_registry,
         client,
         messenger,
         vec![
@@ -136,19 +260,19 @@ pub async fn fetch_and_send_data_accounts(
             )),
         ],
     )
-    .await?;
-    Ok(())
+    .await
 }
 
 pub async fn fetch_and_send_policy_accounts(
     registry: Pubkey,
+    program_ids: &ProgramIds,
     client: &RpcClient,
-    messenger: &Arc<Mutex<Box<dyn plerkle_messenger::Messenger>>>,
-) -> anyhow::Result<()> {
+    messenger: &AccountStream,
+) -> anyhow::Result<usize> {
     const POLICY_ACCOUNT_LEN: u64 = 69;
 
     fetch_and_send_program_accounts(
-        POLICY_ENGINE_PROGRAM_ID,
+        program_ids.policy_engine,
         client,
         messenger,
         vec![
@@ -159,29 +283,49 @@ pub async fn fetch_and_send_policy_accounts(
             )),
         ],
     )
-    .await?;
-    Ok(())
+    .await
 }
 
-/// fetch account from node and send it to redis
+pub async fn fetch_and_send_token_accounts(
+    mint: Pubkey,
+    client: &RpcClient,
+    messenger: &AccountStream,
+) -> anyhow::Result<usize> {
+    // Token accounts start with their mint; their length depends on the extensions.
+    const TOKEN_ACCOUNT_MINT_OFFSET: usize = 0;
+
+    fetch_and_send_program_accounts(
+        TOKEN_2022_PROGRAM_ID,
+        client,
+        messenger,
+        vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            TOKEN_ACCOUNT_MINT_OFFSET,
+            mint.to_bytes().to_vec(),
+        ))],
+    )
+    .await
+}
+
+/// fetch account from node and send it to redis, false when it failed to fetch and `ok_to_fail`
 pub async fn fetch_and_send_account(
     pubkey: Pubkey,
     client: &RpcClient,
-    messenger: &Arc<Mutex<Box<dyn plerkle_messenger::Messenger>>>,
+    messenger: &AccountStream,
     ok_to_fail: bool,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<bool> {
     let fetch_result = fetch_account(pubkey, client).await;
     let (account, slot) = match fetch_result {
         Ok((account, slot)) => (account, slot),
         Err(e) => {
             if ok_to_fail {
-                return Ok(());
+                return Ok(false);
             } else {
                 return Err(anyhow::anyhow!("Failed to fetch account: {:?}", e));
             }
         }
     };
-    send_account(pubkey, account, slot, messenger).await
+    send_account(pubkey, account, slot, messenger).await?;
+    Ok(true)
 }
 
 /// fetch account and slot with retries
@@ -219,7 +363,7 @@ pub async fn send_account(
     pubkey: Pubkey,
     account: Account,
     slot: u64,
-    messenger: &Arc<Mutex<Box<dyn plerkle_messenger::Messenger>>>,
+    messenger: &AccountStream,
 ) -> anyhow::Result<()> {
     let fbb = flatbuffers::FlatBufferBuilder::new();
 
@@ -238,13 +382,77 @@ pub async fn send_account(
     let fbb = serialize_account(fbb, &account_info, slot, is_startup);
     let bytes = fbb.finished_data();
 
-    messenger.lock().await.send(ACCOUNT_STREAM, bytes).await?;
+    messenger.send(bytes).await?;
     sleep(Duration::from_millis(10)).await;
     info!("sent account {} to stream", pubkey);
 
     Ok(())
 }
 
+/// resend dead-lettered account updates that haven't been replayed yet and mark them replayed
+pub async fn replay_failed_account_updates(
+    pool: &PgPool,
+    messenger: &AccountStream,
+    pubkey: Option<Pubkey>,
+    limit: i64,
+) -> anyhow::Result<usize> {
+    let rows: Vec<(i64, Vec<u8>)> = sqlx::query_as(
+        "SELECT id, payload FROM failed_account_update \
+         WHERE replayed_at IS NULL AND ($1::bytea IS NULL OR pubkey = $1) \
+         ORDER BY id \
+         LIMIT $2",
+    )
+    .bind(pubkey.map(|pubkey| pubkey.to_bytes().to_vec()))
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("failed to load failed account updates")?;
+
+    let replayed = rows.len();
+    for (id, payload) in rows {
+        messenger.send(&payload).await?;
+        sqlx::query("UPDATE failed_account_update SET replayed_at = now() WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await
+            .with_context(|| format!("failed to mark update {id} replayed"))?;
+        info!("replayed failed account update {}", id);
+    }
+
+    Ok(replayed)
+}
+
+/// resend dead-lettered transactions that haven't been replayed yet and mark them replayed
+pub async fn replay_failed_transaction_updates(
+    pool: &PgPool,
+    messenger: &AccountStream,
+    limit: i64,
+) -> anyhow::Result<usize> {
+    let rows: Vec<(i64, Vec<u8>)> = sqlx::query_as(
+        "SELECT id, payload FROM failed_transaction_update \
+         WHERE replayed_at IS NULL \
+         ORDER BY id \
+         LIMIT $1",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("failed to load failed transaction updates")?;
+
+    let replayed = rows.len();
+    for (id, payload) in rows {
+        messenger.send(&payload).await?;
+        sqlx::query("UPDATE failed_transaction_update SET replayed_at = now() WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await
+            .with_context(|| format!("failed to mark transaction {id} replayed"))?;
+        info!("replayed failed transaction update {}", id);
+    }
+
+    Ok(replayed)
+}
+
 pub async fn rpc_tx_with_retries<T, E>(
     client: &RpcClient,
     request: RpcRequest,
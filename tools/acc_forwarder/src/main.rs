@@ -1,22 +1,19 @@
 use {
     acc_forwarder::{
-        fetch_and_send_account, fetch_and_send_data_accounts, fetch_and_send_identity_accounts,
-        fetch_and_send_policy_accounts, fetch_and_send_tracker_account, read_lines,
+        fetch_and_send_account, fetch_and_send_mint, read_lines, replay_failed_account_updates,
+        replay_failed_transaction_updates, AccountStream,
     },
     anyhow::Context,
     clap::Parser,
-    common::utils::{
-        find_asset_controller_pda, find_data_registry_pda, find_identifier_registry_pda,
-        find_policy_engine_pda,
-    },
+    common::utils::ProgramIds,
     figment::{map, value::Value},
     futures::stream::StreamExt,
-    log::warn,
-    plerkle_messenger::{MessengerConfig, ACCOUNT_STREAM},
+    log::{info, warn},
+    plerkle_messenger::{MessengerConfig, ACCOUNT_STREAM, TRANSACTION_STREAM},
     solana_client::nonblocking::rpc_client::RpcClient,
     solana_sdk::pubkey::Pubkey,
-    std::{env, str::FromStr, sync::Arc},
-    tokio::sync::Mutex,
+    sqlx::PgPool,
+    std::{env, str::FromStr},
     tracing_subscriber::fmt,
 };
 
@@ -27,6 +24,15 @@ struct Args {
     redis_url: String,
     #[arg(long)]
     rpc_url: String,
+    // program ids of a devnet or localnet deployment, the mainnet programs by default
+    #[arg(long)]
+    asset_controller_program: Option<Pubkey>,
+    #[arg(long)]
+    data_registry_program: Option<Pubkey>,
+    #[arg(long)]
+    identity_registry_program: Option<Pubkey>,
+    #[arg(long)]
+    policy_engine_program: Option<Pubkey>,
     #[command(subcommand)]
     action: Action,
 }
@@ -50,6 +56,22 @@ enum Action {
         #[arg(long)]
         mint: String,
     },
+    ReplayFailed {
+        // resends account updates the indexer dead-lettered
+        #[arg(long)]
+        database_url: String,
+        #[arg(long)]
+        account: Option<String>,
+        #[arg(long, default_value_t = 1000)]
+        limit: i64,
+    },
+    ReplayFailedTransactions {
+        // resends transactions the indexer dead-lettered onto the transaction stream
+        #[arg(long)]
+        database_url: String,
+        #[arg(long, default_value_t = 1000)]
+        limit: i64,
+    },
 }
 
 #[tokio::main]
@@ -66,16 +88,23 @@ async fn main() -> anyhow::Result<()> {
         messenger_type: plerkle_messenger::MessengerType::Redis,
         connection_config: config,
     };
-    let mut messenger = plerkle_messenger::select_messenger(messenger_config)
-        .await
-        .unwrap();
-    messenger.add_stream(ACCOUNT_STREAM).await.unwrap();
-    messenger
-        .set_buffer_size(ACCOUNT_STREAM, 10000000000000000)
-        .await;
-    let messenger = Arc::new(Mutex::new(messenger));
+    let messenger =
+        AccountStream::connect(messenger_config.clone(), ACCOUNT_STREAM, 10000000000000000)
+            .await
+            .unwrap();
 
     let client = RpcClient::new(args.rpc_url.clone());
+    let defaults = ProgramIds::default();
+    let program_ids = ProgramIds {
+        asset_controller: args
+            .asset_controller_program
+            .unwrap_or(defaults.asset_controller),
+        data_registry: args.data_registry_program.unwrap_or(defaults.data_registry),
+        identity_registry: args
+            .identity_registry_program
+            .unwrap_or(defaults.identity_registry),
+        policy_engine: args.policy_engine_program.unwrap_or(defaults.policy_engine),
+    };
 
     match args.action {
         Action::Account { account } => {
@@ -125,20 +154,40 @@ async fn main() -> anyhow::Result<()> {
         Action::Mint { mint } => {
             let mint =
                 Pubkey::from_str(&mint).with_context(|| format!("failed to parse mint {mint}"))?;
+            let sent = fetch_and_send_mint(mint, &program_ids, &client, &messenger).await?;
+            info!("sent {} accounts of mint {}", sent, mint);
+        }
+        Action::ReplayFailed {
+            database_url,
+            account,
+            limit,
+        } => {
+            let pubkey = account
+                .map(|account| {
+                    Pubkey::from_str(&account)
+                        .with_context(|| format!("failed to parse account {account}"))
+                })
+                .transpose()?;
+            let pool = PgPool::connect(&database_url)
+                .await
+                .context("failed to connect to database")?;
 
-            let asset_controller_pda = find_asset_controller_pda(&mint).0;
-            let data_pda = find_data_registry_pda(&mint).0;
-            let identifier_pda = find_identifier_registry_pda(&mint).0;
-            let policy_pda = find_policy_engine_pda(&mint).0;
+            let replayed = replay_failed_account_updates(&pool, &messenger, pubkey, limit).await?;
+            info!("replayed {} failed account updates", replayed);
+        }
+        Action::ReplayFailedTransactions {
+            database_url,
+            limit,
+        } => {
+            let pool = PgPool::connect(&database_url)
+                .await
+                .context("failed to connect to database")?;
+            let transactions =
+                AccountStream::connect(messenger_config, TRANSACTION_STREAM, 10000000000000000)
+                    .await?;
 
-            fetch_and_send_account(mint, &client, &messenger, false).await?;
-            for pubkey in &[asset_controller_pda, data_pda, identifier_pda, policy_pda] {
-                fetch_and_send_account(*pubkey, &client, &messenger, true).await?;
-            }
-            fetch_and_send_tracker_account(mint, &client, &messenger).await?;
-            fetch_and_send_data_accounts(data_pda, &client, &messenger).await?;
-            fetch_and_send_identity_accounts(identifier_pda, &client, &messenger).await?;
-            fetch_and_send_policy_accounts(policy_pda, &client, &messenger).await?;
+            let replayed = replay_failed_transaction_updates(&pool, &transactions, limit).await?;
+            info!("replayed {} failed transaction updates", replayed);
         }
     }
 
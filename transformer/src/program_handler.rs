@@ -1,4 +1,7 @@
-use crate::{error::TransformerError, programs::ProgramParseResult};
+use crate::{
+    error::TransformerError,
+    programs::{instruction::ParsedInstruction, ProgramParseResult},
+};
 use plerkle_serialization::AccountInfo;
 use solana_sdk::pubkey::Pubkey;
 
@@ -42,4 +45,8 @@ pub trait ProgramParser: Sync + Send {
         &self,
         account_info: &AccountInfo,
     ) -> Result<Box<dyn ParseResult>, TransformerError>;
+    fn handle_instruction(
+        &self,
+        data: &[u8],
+    ) -> Result<Option<ParsedInstruction>, TransformerError>;
 }
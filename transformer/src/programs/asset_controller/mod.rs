@@ -1,25 +1,76 @@
 use crate::{
     error::TransformerError,
     program_handler::{ParseResult, ProgramParser},
-    programs::ProgramParseResult,
+    programs::{
+        instruction::{
+            instruction_definition, parse_instruction, InstructionDefinition, ParsedInstruction,
+        },
+        ProgramParseResult,
+    },
+};
+use asset_controller::{
+    instruction,
+    state::{AssetControllerAccount, TrackerAccount, TransactionApprovalAccount},
 };
-use asset_controller::state::{AssetControllerAccount, TrackerAccount};
 use borsh::BorshDeserialize;
 use plerkle_serialization::AccountInfo;
-use solana_sdk::{pubkey::Pubkey, pubkeys};
+use solana_sdk::pubkey::Pubkey;
 
-use super::get_discriminator;
+use super::{get_discriminator, is_closed_account};
 
-pubkeys!(
-    asset_controller_program_id,
-    "acpcFrzEYKjVLvZGWueTV8vyDjhu3oKC7sN38QELLan"
-);
+// Instructions recorded in `rwa_instruction`, decoded with the program's instruction structs.
+const ASSET_CONTROLLER_INSTRUCTIONS: &[InstructionDefinition] = &[
+    instruction_definition!("create_asset_controller", instruction::CreateAssetController, |ix| {
+        "decimals" => ix.args.decimals,
+        "name" => ix.args.name,
+        "symbol" => ix.args.symbol,
+        "uri" => ix.args.uri,
+        "delegate" => ix.args.delegate,
+    }),
+    instruction_definition!(
+        "create_token_account",
+        instruction::CreateTokenAccount,
+        |_| {}
+    ),
+    instruction_definition!("issue_tokens", instruction::IssueTokens, |ix| {
+        "amount" => ix.amount,
+    }),
+    instruction_definition!("void_tokens", instruction::VoidTokens, |ix| {
+        "amount" => ix.amount,
+    }),
+    instruction_definition!("revoke_tokens", instruction::RevokeTokens, |ix| {
+        "amount" => ix.amount,
+    }),
+    instruction_definition!(
+        "create_tracker_account",
+        instruction::CreateTrackerAccount,
+        |_| {}
+    ),
+    instruction_definition!(
+        "create_transaction_approval",
+        instruction::CreateTransactionApproval,
+        |ix| {
+            "amount" => ix.amount,
+            "expiry_timestamp" => ix.expiry_timestamp,
+        }
+    ),
+];
 
-pub struct AssetControllerParser;
+/// Parses the asset controller program deployed at `program_id`.
+pub struct AssetControllerParser {
+    program_id: Pubkey,
+}
+
+impl AssetControllerParser {
+    pub fn new(program_id: Pubkey) -> Self {
+        Self { program_id }
+    }
+}
 
 pub enum AssetControllerProgram {
     AssetControllerAccount(AssetControllerAccount),
     TrackerAccount(Box<TrackerAccount>),
+    TransactionApprovalAccount(Box<TransactionApprovalAccount>),
     EmptyAccount,
 }
 
@@ -37,31 +88,31 @@ impl ParseResult for AssetControllerProgram {
 
 impl ProgramParser for AssetControllerParser {
     fn key(&self) -> Pubkey {
-        asset_controller_program_id()
+        self.program_id
     }
     fn key_match(&self, key: &Pubkey) -> bool {
-        key == &asset_controller_program_id()
+        key == &self.program_id
     }
     fn handles_account_updates(&self) -> bool {
         true
     }
 
     fn handles_instructions(&self) -> bool {
-        false
+        true
     }
 
     fn handle_account(
         &self,
         account_info: &AccountInfo,
     ) -> Result<Box<(dyn ParseResult + 'static)>, TransformerError> {
-        let account_data = if let Some(account_info) = account_info.data() {
-            account_info.iter().collect::<Vec<_>>()
-        } else {
-            return Ok(Box::new(AssetControllerProgram::EmptyAccount));
+        let account_data = match account_info.data() {
+            Some(data) if !is_closed_account(account_info) => data.iter().collect::<Vec<_>>(),
+            _ => return Ok(Box::new(AssetControllerProgram::EmptyAccount)),
         };
 
         let asset_controller_discriminator = get_discriminator("AssetControllerAccount");
         let tracker_account_discriminator = get_discriminator("TrackerAccount");
+        let transaction_approval_discriminator = get_discriminator("TransactionApprovalAccount");
         let account_type_discriminator = &account_data[..8];
         let account_info_without_discriminator = &account_data[8..];
         let mut cursor = std::io::Cursor::new(account_info_without_discriminator);
@@ -82,9 +133,24 @@ impl ProgramParser for AssetControllerParser {
                     )
                 })?,
             ))
+        } else if account_type_discriminator == transaction_approval_discriminator {
+            AssetControllerProgram::TransactionApprovalAccount(Box::new(
+                TransactionApprovalAccount::deserialize(cursor.get_mut()).map_err(|_| {
+                    TransformerError::CustomDeserializationError(
+                        "Failed to deserialize TransactionApprovalAccount".to_string(),
+                    )
+                })?,
+            ))
         } else {
             return Err(TransformerError::UnknownAccountDiscriminator);
         };
         Ok(Box::new(account))
     }
+
+    fn handle_instruction(
+        &self,
+        data: &[u8],
+    ) -> Result<Option<ParsedInstruction>, TransformerError> {
+        parse_instruction(ASSET_CONTROLLER_INSTRUCTIONS, data)
+    }
 }
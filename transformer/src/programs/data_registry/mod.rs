@@ -1,21 +1,64 @@
 use crate::{
     error::TransformerError,
     program_handler::{ParseResult, ProgramParser},
-    programs::ProgramParseResult,
+    programs::{
+        instruction::{
+            instruction_definition, parse_instruction, InstructionDefinition, ParsedInstruction,
+        },
+        ProgramParseResult,
+    },
 };
 use borsh::BorshDeserialize;
-use data_registry::{state::DataAccount, DataRegistryAccount};
+use data_registry::{instruction, state::DataAccount, DataRegistryAccount};
 use plerkle_serialization::AccountInfo;
-use solana_sdk::{pubkey::Pubkey, pubkeys};
+use solana_sdk::pubkey::Pubkey;
 
-use super::get_discriminator;
+use super::{get_discriminator, is_closed_account};
 
-pubkeys!(
-    data_registry_program_id,
-    "dataeP5X1e7XsWN1ovDSEDP5cqaEUnKBmHE5iZhXPVw"
-);
+// Instructions recorded in `rwa_instruction`, decoded with the program's instruction structs.
+const DATA_REGISTRY_INSTRUCTIONS: &[InstructionDefinition] = &[
+    instruction_definition!("create_data_registry", instruction::CreateDataRegistry, |ix| {
+        "authority" => ix.authority,
+        "delegate" => ix.delegate,
+    }),
+    instruction_definition!("delegate_data_registry", instruction::DelegateDataRegistry, |ix| {
+        "delegate" => ix.delegate,
+    }),
+    instruction_definition!("create_data_account", instruction::CreateDataAccount, |ix| {
+        "type" => ix.args._type as u8,
+        "name" => ix.args.name,
+        "uri" => ix.args.uri,
+    }),
+    instruction_definition!(
+        "update_data_account_name",
+        instruction::UpdateDataAccountName,
+        |ix| { "name" => ix.name }
+    ),
+    instruction_definition!("update_data_account_uri", instruction::UpdateDataAccountUri, |ix| {
+        "uri" => ix.uri,
+    }),
+    instruction_definition!(
+        "update_data_account_type",
+        instruction::UpdateDataAccountType,
+        |ix| { "type" => ix._type as u8 }
+    ),
+    instruction_definition!(
+        "delete_data_account",
+        instruction::DeleteDataAccount,
+        |_| {}
+    ),
+];
 
-pub struct DataRegistryParser;
+/// Parses the data registry program deployed at `program_id`.
+pub struct DataRegistryParser {
+    program_id: Pubkey,
+}
+
+impl DataRegistryParser {
+    pub fn new(program_id: Pubkey) -> Self {
+        Self { program_id }
+    }
+}
 
 pub enum DataRegistryProgram {
     DataAccount(DataAccount),
@@ -37,26 +80,25 @@ impl ParseResult for DataRegistryProgram {
 
 impl ProgramParser for DataRegistryParser {
     fn key(&self) -> Pubkey {
-        data_registry_program_id()
+        self.program_id
     }
     fn key_match(&self, key: &Pubkey) -> bool {
-        key == &data_registry_program_id()
+        key == &self.program_id
     }
     fn handles_account_updates(&self) -> bool {
         true
     }
 
     fn handles_instructions(&self) -> bool {
-        false
+        true
     }
     fn handle_account(
         &self,
         account_info: &AccountInfo,
     ) -> Result<Box<(dyn ParseResult + 'static)>, TransformerError> {
-        let account_data = if let Some(account_info) = account_info.data() {
-            account_info.iter().collect::<Vec<_>>()
-        } else {
-            return Ok(Box::new(DataRegistryProgram::EmptyAccount));
+        let account_data = match account_info.data() {
+            Some(data) if !is_closed_account(account_info) => data.iter().collect::<Vec<_>>(),
+            _ => return Ok(Box::new(DataRegistryProgram::EmptyAccount)),
         };
 
         let data_registry_discriminator = get_discriminator("DataRegistryAccount");
@@ -86,4 +128,11 @@ impl ProgramParser for DataRegistryParser {
         };
         Ok(Box::new(account))
     }
+
+    fn handle_instruction(
+        &self,
+        data: &[u8],
+    ) -> Result<Option<ParsedInstruction>, TransformerError> {
+        parse_instruction(DATA_REGISTRY_INSTRUCTIONS, data)
+    }
 }
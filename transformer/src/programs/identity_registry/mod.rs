@@ -1,21 +1,66 @@
 use crate::{
     error::TransformerError,
     program_handler::{ParseResult, ProgramParser},
-    programs::ProgramParseResult,
+    programs::{
+        instruction::{
+            instruction_definition, parse_instruction, InstructionDefinition, ParsedInstruction,
+        },
+        ProgramParseResult,
+    },
 };
 use borsh::BorshDeserialize;
-use identity_registry::{state::IdentityAccount, IdentityRegistryAccount};
+use identity_registry::{instruction, state::IdentityAccount, IdentityRegistryAccount};
 use plerkle_serialization::AccountInfo;
-use solana_sdk::{pubkey::Pubkey, pubkeys};
+use solana_sdk::pubkey::Pubkey;
 
-use super::get_discriminator;
+use super::{get_discriminator, is_closed_account};
 
-pubkeys!(
-    identity_registry_program_id,
-    "idtynCMYbdisCTv4FrCWPSQboZb1uM4TV2cPi79yxQf"
-);
+// Instructions recorded in `rwa_instruction`, decoded with the program's instruction structs.
+const IDENTITY_REGISTRY_INSTRUCTIONS: &[InstructionDefinition] = &[
+    instruction_definition!(
+        "create_identity_registry",
+        instruction::CreateIdentityRegistry,
+        |ix| {
+            "authority" => ix.authority,
+            "delegate" => ix.delegate,
+        }
+    ),
+    instruction_definition!(
+        "delegate_identity_registry",
+        instruction::DelegateIdentityRegistry,
+        |ix| { "delegate" => ix.delegate }
+    ),
+    instruction_definition!("create_identity_account", instruction::CreateIdentityAccount, |ix| {
+        "owner" => ix.owner,
+        "level" => ix.level,
+    }),
+    instruction_definition!(
+        "add_level_to_identity_account",
+        instruction::AddLevelToIdentityAccount,
+        |ix| { "level" => ix.level }
+    ),
+    instruction_definition!(
+        "remove_level_from_identity_account",
+        instruction::RemoveLevelFromIdentityAccount,
+        |ix| { "level" => ix.level }
+    ),
+    instruction_definition!(
+        "revoke_identity_account",
+        instruction::RevokeIdentityAccount,
+        |_| {}
+    ),
+];
 
-pub struct IdentityRegistryParser;
+/// Parses the identity registry program deployed at `program_id`.
+pub struct IdentityRegistryParser {
+    program_id: Pubkey,
+}
+
+impl IdentityRegistryParser {
+    pub fn new(program_id: Pubkey) -> Self {
+        Self { program_id }
+    }
+}
 
 pub enum IdentityRegistryProgram {
     IdentityRegistry(IdentityRegistryAccount),
@@ -37,26 +82,25 @@ impl ParseResult for IdentityRegistryProgram {
 
 impl ProgramParser for IdentityRegistryParser {
     fn key(&self) -> Pubkey {
-        identity_registry_program_id()
+        self.program_id
     }
     fn key_match(&self, key: &Pubkey) -> bool {
-        key == &identity_registry_program_id()
+        key == &self.program_id
     }
     fn handles_account_updates(&self) -> bool {
         true
     }
 
     fn handles_instructions(&self) -> bool {
-        false
+        true
     }
     fn handle_account(
         &self,
         account_info: &AccountInfo,
     ) -> Result<Box<(dyn ParseResult + 'static)>, TransformerError> {
-        let account_data = if let Some(account_info) = account_info.data() {
-            account_info.iter().collect::<Vec<_>>()
-        } else {
-            return Ok(Box::new(IdentityRegistryProgram::EmptyAccount));
+        let account_data = match account_info.data() {
+            Some(data) if !is_closed_account(account_info) => data.iter().collect::<Vec<_>>(),
+            _ => return Ok(Box::new(IdentityRegistryProgram::EmptyAccount)),
         };
 
         let identity_registry_descriminator = get_discriminator("IdentityRegistryAccount");
@@ -86,4 +130,11 @@ impl ProgramParser for IdentityRegistryParser {
         };
         Ok(Box::new(account))
     }
+
+    fn handle_instruction(
+        &self,
+        data: &[u8],
+    ) -> Result<Option<ParsedInstruction>, TransformerError> {
+        parse_instruction(IDENTITY_REGISTRY_INSTRUCTIONS, data)
+    }
 }
@@ -0,0 +1,447 @@
+use crate::error::TransformerError;
+use borsh::BorshDeserialize;
+use serde::Deserialize;
+use serde_json::{Map, Number, Value};
+use solana_sdk::pubkey::Pubkey;
+use std::{collections::HashMap, path::Path};
+
+use super::get_discriminator;
+
+/// Decodes the accounts of an Anchor program into JSON from its IDL, without Rust types for
+/// them. Used for accounts the typed parsers don't know, e.g. after a program upgrade.
+pub struct IdlDecoder {
+    accounts: Vec<IdlAccount>,
+    types: HashMap<String, IdlTypeDefTy>,
+}
+
+struct IdlAccount {
+    name: String,
+    discriminator: Vec<u8>,
+    ty: IdlTypeDefTy,
+}
+
+/// An account decoded with an IDL. Integers wider than 32 bits are strings, public keys are
+/// base58 encoded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedAccount {
+    pub account_type: String,
+    pub data: Value,
+}
+
+// The subset of the Anchor IDL, legacy and 0.30 formats, describing account layouts.
+#[derive(Deserialize)]
+struct Idl {
+    #[serde(default)]
+    accounts: Vec<IdlAccountDef>,
+    #[serde(default)]
+    types: Vec<IdlTypeDef>,
+}
+
+#[derive(Deserialize)]
+struct IdlAccountDef {
+    name: String,
+    /// Only in 0.30 IDLs, older ones use the hash of the account name.
+    discriminator: Option<Vec<u8>>,
+    /// Only in legacy IDLs, 0.30 ones describe the account in `types`.
+    #[serde(rename = "type")]
+    ty: Option<IdlTypeDefTy>,
+}
+
+#[derive(Deserialize)]
+struct IdlTypeDef {
+    name: String,
+    #[serde(rename = "type")]
+    ty: IdlTypeDefTy,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum IdlTypeDefTy {
+    Struct {
+        #[serde(default)]
+        fields: IdlFields,
+    },
+    Enum {
+        variants: Vec<IdlEnumVariant>,
+    },
+    Type {
+        alias: IdlType,
+    },
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(untagged)]
+enum IdlFields {
+    Named(Vec<IdlField>),
+    Tuple(Vec<IdlType>),
+}
+
+impl Default for IdlFields {
+    fn default() -> Self {
+        IdlFields::Named(Vec::new())
+    }
+}
+
+#[derive(Deserialize, Clone)]
+struct IdlField {
+    name: String,
+    #[serde(rename = "type")]
+    ty: IdlType,
+}
+
+#[derive(Deserialize, Clone)]
+struct IdlEnumVariant {
+    name: String,
+    fields: Option<IdlFields>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(untagged)]
+enum IdlType {
+    Primitive(String),
+    Option { option: Box<IdlType> },
+    COption { coption: Box<IdlType> },
+    Vec { vec: Box<IdlType> },
+    Array { array: (Box<IdlType>, usize) },
+    Defined { defined: IdlDefined },
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(untagged)]
+enum IdlDefined {
+    Name(String),
+    Generic { name: String },
+}
+
+impl IdlDefined {
+    fn name(&self) -> &str {
+        match self {
+            IdlDefined::Name(name) | IdlDefined::Generic { name } => name,
+        }
+    }
+}
+
+impl IdlDecoder {
+    pub fn from_json(idl: &str) -> Result<Self, TransformerError> {
+        let idl: Idl = serde_json::from_str(idl).map_err(|e| {
+            TransformerError::CustomDeserializationError(format!("Invalid IDL: {e}"))
+        })?;
+        let types = idl
+            .types
+            .into_iter()
+            .map(|def| (def.name, def.ty))
+            .collect::<HashMap<_, _>>();
+        let accounts = idl
+            .accounts
+            .into_iter()
+            .map(|account| {
+                let ty = match account.ty {
+                    Some(ty) => ty,
+                    None => types.get(&account.name).cloned().ok_or_else(|| {
+                        TransformerError::CustomDeserializationError(format!(
+                            "IDL has no layout for account {}",
+                            account.name
+                        ))
+                    })?,
+                };
+                let discriminator = account
+                    .discriminator
+                    .unwrap_or_else(|| get_discriminator(&account.name).to_vec());
+                Ok(IdlAccount {
+                    name: account.name,
+                    discriminator,
+                    ty,
+                })
+            })
+            .collect::<Result<Vec<_>, TransformerError>>()?;
+        Ok(IdlDecoder { accounts, types })
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, TransformerError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Decodes `data` as whichever account of the IDL its discriminator belongs to. Returns
+    /// `None` when the IDL doesn't know the discriminator either. Trailing bytes, such as unused
+    /// space allocated for the account, are ignored.
+    pub fn decode_account(&self, data: &[u8]) -> Result<Option<DecodedAccount>, TransformerError> {
+        let account = match self
+            .accounts
+            .iter()
+            .find(|account| data.starts_with(&account.discriminator))
+        {
+            Some(account) => account,
+            None => return Ok(None),
+        };
+        let mut data = &data[account.discriminator.len()..];
+        let decoded = self.decode_type_def(&account.ty, &mut data).map_err(|e| {
+            TransformerError::CustomDeserializationError(format!(
+                "Failed to decode {} with IDL: {}",
+                account.name, e
+            ))
+        })?;
+        Ok(Some(DecodedAccount {
+            account_type: account.name.clone(),
+            data: decoded,
+        }))
+    }
+
+    fn decode_type_def(
+        &self,
+        ty: &IdlTypeDefTy,
+        data: &mut &[u8],
+    ) -> Result<Value, TransformerError> {
+        match ty {
+            IdlTypeDefTy::Struct { fields } => self.decode_fields(fields, data),
+            IdlTypeDefTy::Enum { variants } => {
+                let index = read::<u8>(data)? as usize;
+                let variant = variants.get(index).ok_or_else(|| {
+                    TransformerError::CustomDeserializationError(format!(
+                        "Unknown enum variant {index}"
+                    ))
+                })?;
+                match &variant.fields {
+                    Some(fields) => {
+                        let mut decoded = Map::new();
+                        decoded.insert(variant.name.clone(), self.decode_fields(fields, data)?);
+                        Ok(Value::Object(decoded))
+                    }
+                    None => Ok(Value::String(variant.name.clone())),
+                }
+            }
+            IdlTypeDefTy::Type { alias } => self.decode_type(alias, data),
+        }
+    }
+
+    fn decode_fields(
+        &self,
+        fields: &IdlFields,
+        data: &mut &[u8],
+    ) -> Result<Value, TransformerError> {
+        match fields {
+            IdlFields::Named(fields) => {
+                let mut decoded = Map::new();
+                for field in fields {
+                    decoded.insert(field.name.clone(), self.decode_type(&field.ty, data)?);
+                }
+                Ok(Value::Object(decoded))
+            }
+            IdlFields::Tuple(types) => types
+                .iter()
+                .map(|ty| self.decode_type(ty, data))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array),
+        }
+    }
+
+    fn decode_type(&self, ty: &IdlType, data: &mut &[u8]) -> Result<Value, TransformerError> {
+        let value = match ty {
+            IdlType::Primitive(primitive) => decode_primitive(primitive, data)?,
+            IdlType::Option { option } => match read::<u8>(data)? {
+                0 => Value::Null,
+                _ => self.decode_type(option, data)?,
+            },
+            // COption tags are four bytes wide.
+            IdlType::COption { coption } => match read::<u32>(data)? {
+                0 => Value::Null,
+                _ => self.decode_type(coption, data)?,
+            },
+            IdlType::Vec { vec: inner } => {
+                let len = read::<u32>(data)?;
+                (0..len)
+                    .map(|_| self.decode_type(inner, data))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Value::Array)?
+            }
+            IdlType::Array {
+                array: (inner, len),
+            } => (0..*len)
+                .map(|_| self.decode_type(inner, data))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array)?,
+            IdlType::Defined { defined } => {
+                let ty = self.types.get(defined.name()).ok_or_else(|| {
+                    TransformerError::CustomDeserializationError(format!(
+                        "IDL has no type {}",
+                        defined.name()
+                    ))
+                })?;
+                self.decode_type_def(ty, data)?
+            }
+        };
+        Ok(value)
+    }
+}
+
+fn decode_primitive(primitive: &str, data: &mut &[u8]) -> Result<Value, TransformerError> {
+    let value = match primitive {
+        "bool" => Value::Bool(read::<bool>(data)?),
+        "u8" => Value::from(read::<u8>(data)?),
+        "i8" => Value::from(read::<i8>(data)?),
+        "u16" => Value::from(read::<u16>(data)?),
+        "i16" => Value::from(read::<i16>(data)?),
+        "u32" => Value::from(read::<u32>(data)?),
+        "i32" => Value::from(read::<i32>(data)?),
+        // Kept as strings so they survive JSON consumers that use doubles.
+        "u64" => Value::String(read::<u64>(data)?.to_string()),
+        "i64" => Value::String(read::<i64>(data)?.to_string()),
+        "u128" => Value::String(read::<u128>(data)?.to_string()),
+        "i128" => Value::String(read::<i128>(data)?.to_string()),
+        "f32" => float(read::<f32>(data)? as f64),
+        "f64" => float(read::<f64>(data)?),
+        "string" => Value::String(read::<String>(data)?),
+        "bytes" => Value::from(read::<Vec<u8>>(data)?),
+        "publicKey" | "pubkey" => Value::String(read::<Pubkey>(data)?.to_string()),
+        _ => {
+            return Err(TransformerError::CustomDeserializationError(format!(
+                "Unsupported IDL type {primitive}"
+            )))
+        }
+    };
+    Ok(value)
+}
+
+fn read<T: BorshDeserialize>(data: &mut &[u8]) -> Result<T, TransformerError> {
+    Ok(T::deserialize(data)?)
+}
+
+fn float(value: f64) -> Value {
+    Number::from_f64(value).map_or(Value::Null, Value::Number)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const LEGACY_IDL: &str = r#"{
+        "version": "0.1.0",
+        "name": "vaults",
+        "accounts": [
+            {
+                "name": "Vault",
+                "type": {
+                    "kind": "struct",
+                    "fields": [
+                        { "name": "authority", "type": "publicKey" },
+                        { "name": "amount", "type": "u64" },
+                        { "name": "label", "type": "string" },
+                        { "name": "delegate", "type": { "option": "publicKey" } },
+                        { "name": "state", "type": { "defined": "State" } }
+                    ]
+                }
+            }
+        ],
+        "types": [
+            {
+                "name": "State",
+                "type": {
+                    "kind": "enum",
+                    "variants": [
+                        { "name": "Active" },
+                        { "name": "Frozen", "fields": [{ "name": "until", "type": "i64" }] }
+                    ]
+                }
+            }
+        ]
+    }"#;
+
+    const IDL_0_30: &str = r#"{
+        "address": "11111111111111111111111111111111",
+        "metadata": { "name": "levels", "version": "0.1.0", "spec": "0.1.0" },
+        "accounts": [{ "name": "Levels", "discriminator": [1, 2, 3, 4, 5, 6, 7, 8] }],
+        "types": [
+            {
+                "name": "Levels",
+                "type": {
+                    "kind": "struct",
+                    "fields": [
+                        { "name": "owner", "type": "pubkey" },
+                        { "name": "levels", "type": { "vec": "u8" } },
+                        { "name": "limits", "type": { "array": ["u16", 2] } }
+                    ]
+                }
+            }
+        ]
+    }"#;
+
+    fn legacy_vault() -> Vec<u8> {
+        let mut data = get_discriminator("Vault").to_vec();
+        data.extend_from_slice(&[1; 32]);
+        data.extend_from_slice(&1_000_000u64.to_le_bytes());
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.extend_from_slice(b"abc");
+        data.push(0);
+        data.push(1);
+        data.extend_from_slice(&42i64.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_decode_legacy_idl_account() {
+        let decoder = IdlDecoder::from_json(LEGACY_IDL).unwrap();
+        // Unused space at the end of the account is ignored.
+        let mut data = legacy_vault();
+        data.extend_from_slice(&[0; 16]);
+
+        let decoded = decoder.decode_account(&data).unwrap().unwrap();
+        assert_eq!(decoded.account_type, "Vault");
+        assert_eq!(
+            decoded.data,
+            json!({
+                "authority": Pubkey::new_from_array([1; 32]).to_string(),
+                "amount": "1000000",
+                "label": "abc",
+                "delegate": null,
+                "state": { "Frozen": { "until": "42" } },
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_0_30_idl_account() {
+        let decoder = IdlDecoder::from_json(IDL_0_30).unwrap();
+        let mut data = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        data.extend_from_slice(&[2; 32]);
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&[3, 4]);
+        data.extend_from_slice(&5u16.to_le_bytes());
+        data.extend_from_slice(&6u16.to_le_bytes());
+
+        let decoded = decoder.decode_account(&data).unwrap().unwrap();
+        assert_eq!(decoded.account_type, "Levels");
+        assert_eq!(
+            decoded.data,
+            json!({
+                "owner": Pubkey::new_from_array([2; 32]).to_string(),
+                "levels": [3, 4],
+                "limits": [5, 6],
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_unknown_discriminator() {
+        let decoder = IdlDecoder::from_json(LEGACY_IDL).unwrap();
+        let mut data = get_discriminator("Unknown").to_vec();
+        data.extend_from_slice(&legacy_vault()[8..]);
+
+        assert_eq!(decoder.decode_account(&data).unwrap(), None);
+        assert_eq!(decoder.decode_account(&[1, 2, 3]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_decode_truncated_account() {
+        let decoder = IdlDecoder::from_json(LEGACY_IDL).unwrap();
+        let data = legacy_vault();
+
+        // Cut inside `amount`, and inside the `Frozen` variant's fields.
+        for len in [8 + 32 + 4, data.len() - 1] {
+            assert!(matches!(
+                decoder.decode_account(&data[..len]),
+                Err(TransformerError::CustomDeserializationError(_))
+            ));
+        }
+    }
+}
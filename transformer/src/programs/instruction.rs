@@ -0,0 +1,113 @@
+use crate::error::TransformerError;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+
+use super::get_instruction_discriminator;
+
+pub type InstructionArgs = BTreeMap<&'static str, InstructionArg>;
+
+/// An Anchor instruction of a program, built with [`instruction_definition!`].
+pub struct InstructionDefinition {
+    pub name: &'static str,
+    /// Deserializes the arguments, the data after the discriminator, into the program's
+    /// `instruction::*` struct and lists its fields. `None` when they don't fit the struct.
+    pub decode: fn(&[u8]) -> Option<InstructionArgs>,
+}
+
+/// Defines an instruction recorded in `rwa_instruction`: its name in the program, the
+/// `instruction::*` struct Anchor generates for it and the arguments stored from that struct.
+macro_rules! instruction_definition {
+    ($name:literal, $instruction:ty, |$decoded:pat_param| { $($arg:literal => $value:expr),* $(,)? }) => {
+        $crate::programs::instruction::InstructionDefinition {
+            name: $name,
+            decode: |data| {
+                let $decoded = <$instruction as borsh::BorshDeserialize>::try_from_slice(data).ok()?;
+                Some(std::collections::BTreeMap::from([
+                    $(($arg, $crate::programs::instruction::InstructionArg::from($value))),*
+                ]))
+            },
+        }
+    };
+}
+pub(crate) use instruction_definition;
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum InstructionArg {
+    Int(i64),
+    /// u64 values are kept as strings so they survive JSON consumers that use doubles.
+    BigInt(String),
+    Text(String),
+    Bytes(Vec<u8>),
+    Null,
+}
+
+impl From<u8> for InstructionArg {
+    fn from(value: u8) -> Self {
+        InstructionArg::Int(value as i64)
+    }
+}
+
+impl From<i64> for InstructionArg {
+    fn from(value: i64) -> Self {
+        InstructionArg::Int(value)
+    }
+}
+
+impl From<u64> for InstructionArg {
+    fn from(value: u64) -> Self {
+        InstructionArg::BigInt(value.to_string())
+    }
+}
+
+impl From<String> for InstructionArg {
+    fn from(value: String) -> Self {
+        InstructionArg::Text(value)
+    }
+}
+
+impl From<Pubkey> for InstructionArg {
+    fn from(value: Pubkey) -> Self {
+        InstructionArg::Text(value.to_string())
+    }
+}
+
+impl From<Vec<u8>> for InstructionArg {
+    fn from(value: Vec<u8>) -> Self {
+        InstructionArg::Bytes(value)
+    }
+}
+
+impl<T: Into<InstructionArg>> From<Option<T>> for InstructionArg {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(InstructionArg::Null, Into::into)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParsedInstruction {
+    pub name: &'static str,
+    /// `None` when the discriminator matched but the arguments did not fit the known layout,
+    /// e.g. after a program upgrade; the raw data is still available to the caller.
+    pub args: Option<InstructionArgs>,
+}
+
+/// Matches the instruction discriminator against `definitions` and decodes the arguments.
+/// Returns `None` for instructions that aren't in `definitions`.
+pub fn parse_instruction(
+    definitions: &[InstructionDefinition],
+    data: &[u8],
+) -> Result<Option<ParsedInstruction>, TransformerError> {
+    if data.len() < 8 {
+        return Err(TransformerError::InstructionParsingError);
+    }
+    let (discriminator, args_data) = data.split_at(8);
+    Ok(definitions
+        .iter()
+        .find(|definition| get_instruction_discriminator(definition.name) == discriminator)
+        .map(|definition| ParsedInstruction {
+            name: definition.name,
+            args: (definition.decode)(args_data),
+        }))
+}
@@ -1,19 +1,28 @@
 use asset_controller::AssetControllerProgram;
 use data_registry::DataRegistryProgram;
 use identity_registry::IdentityRegistryProgram;
+use plerkle_serialization::AccountInfo;
 use policy_engine::PolicyEngineProgram;
 use solana_sdk::hash::hash;
+use std::any::Any;
+use token_extensions::TokenExtensionsProgram;
 
 pub mod asset_controller;
 pub mod data_registry;
 pub mod identity_registry;
+pub mod idl;
+pub mod instruction;
 pub mod policy_engine;
+pub mod token_extensions;
 
 pub enum ProgramParseResult<'a> {
     AssetControllerProgram(&'a AssetControllerProgram),
     DataRegistryProgram(&'a DataRegistryProgram),
     IdentityRegistryProgram(&'a IdentityRegistryProgram),
     PolicyEngineProgram(&'a PolicyEngineProgram),
+    TokenExtensionsProgram(&'a TokenExtensionsProgram),
+    /// Result of a parser defined outside this crate, downcast it to the parser's own type.
+    Custom(&'a dyn Any),
     Unknown,
 }
 
@@ -23,3 +32,15 @@ fn get_discriminator(account_type: &str) -> [u8; 8] {
     discriminator.copy_from_slice(&hash(discriminator_preimage.as_bytes()).to_bytes()[..8]);
     discriminator
 }
+
+fn get_instruction_discriminator(instruction_name: &str) -> [u8; 8] {
+    let discriminator_preimage = format!("global:{}", instruction_name);
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(discriminator_preimage.as_bytes()).to_bytes()[..8]);
+    discriminator
+}
+
+/// Closed accounts are drained of lamports and have their data truncated.
+pub fn is_closed_account(account_info: &AccountInfo) -> bool {
+    account_info.lamports() == 0 || account_info.data().map_or(true, |data| data.is_empty())
+}
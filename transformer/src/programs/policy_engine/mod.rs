@@ -1,21 +1,57 @@
 use crate::{
     error::TransformerError,
     program_handler::{ParseResult, ProgramParser},
-    programs::ProgramParseResult,
+    programs::{
+        instruction::{
+            instruction_definition, parse_instruction, InstructionDefinition, ParsedInstruction,
+        },
+        ProgramParseResult,
+    },
 };
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use plerkle_serialization::AccountInfo;
-use policy_engine::{state::PolicyAccount, PolicyEngineAccount};
-use solana_sdk::{pubkey::Pubkey, pubkeys};
+use policy_engine::{instruction, state::PolicyAccount, PolicyEngineAccount};
+use solana_sdk::pubkey::Pubkey;
 
-use super::get_discriminator;
+use super::{get_discriminator, is_closed_account};
 
-pubkeys!(
-    policy_engine_program_id,
-    "po1cPf1eyUJJPqULw4so3T4JU9pdFn83CDyuLEKFAau"
-);
+// Instructions recorded in `rwa_instruction`, decoded with the program's instruction structs.
+const POLICY_ENGINE_INSTRUCTIONS: &[InstructionDefinition] = &[
+    instruction_definition!("create_policy_engine", instruction::CreatePolicyEngine, |ix| {
+        "authority" => ix.authority,
+        "delegate" => ix.delegate,
+    }),
+    instruction_definition!("create_policy_account", instruction::CreatePolicyAccount, |ix| {
+        "identity_levels" => ix.identity_filter.identity_levels.to_vec(),
+        "comparison_type" => ix.identity_filter.comparision_type as u8,
+        "policy" => ix.policy.try_to_vec().ok(),
+    }),
+    instruction_definition!(
+        "attach_to_policy_account",
+        instruction::AttachToPolicyAccount,
+        |ix| {
+            "identity_levels" => ix.identity_filter.identity_levels.to_vec(),
+            "comparison_type" => ix.identity_filter.comparision_type as u8,
+            "policy" => ix.policy.try_to_vec().ok(),
+        }
+    ),
+    instruction_definition!(
+        "detach_from_policy_account",
+        instruction::DetachFromPolicyAccount,
+        |ix| { "hash" => ix.hash }
+    ),
+];
 
-pub struct PolicyEngineParser;
+/// Parses the policy engine program deployed at `program_id`.
+pub struct PolicyEngineParser {
+    program_id: Pubkey,
+}
+
+impl PolicyEngineParser {
+    pub fn new(program_id: Pubkey) -> Self {
+        Self { program_id }
+    }
+}
 
 pub enum PolicyEngineProgram {
     PolicyEngine(Box<PolicyEngineAccount>),
@@ -37,26 +73,25 @@ impl ParseResult for PolicyEngineProgram {
 
 impl ProgramParser for PolicyEngineParser {
     fn key(&self) -> Pubkey {
-        policy_engine_program_id()
+        self.program_id
     }
     fn key_match(&self, key: &Pubkey) -> bool {
-        key == &policy_engine_program_id()
+        key == &self.program_id
     }
     fn handles_account_updates(&self) -> bool {
         true
     }
 
     fn handles_instructions(&self) -> bool {
-        false
+        true
     }
     fn handle_account(
         &self,
         account_info: &AccountInfo,
     ) -> Result<Box<(dyn ParseResult + 'static)>, TransformerError> {
-        let account_data = if let Some(account_info) = account_info.data() {
-            account_info.iter().collect::<Vec<_>>()
-        } else {
-            return Ok(Box::new(PolicyEngineProgram::EmptyAccount));
+        let account_data = match account_info.data() {
+            Some(data) if !is_closed_account(account_info) => data.iter().collect::<Vec<_>>(),
+            _ => return Ok(Box::new(PolicyEngineProgram::EmptyAccount)),
         };
 
         let policy_engine_descriminator = get_discriminator("PolicyEngineAccount");
@@ -85,4 +120,11 @@ impl ProgramParser for PolicyEngineParser {
 
         Ok(Box::new(account))
     }
+
+    fn handle_instruction(
+        &self,
+        data: &[u8],
+    ) -> Result<Option<ParsedInstruction>, TransformerError> {
+        parse_instruction(POLICY_ENGINE_INSTRUCTIONS, data)
+    }
 }
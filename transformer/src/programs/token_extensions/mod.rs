@@ -0,0 +1,140 @@
+use crate::{
+    error::TransformerError,
+    program_handler::{ParseResult, ProgramParser},
+    programs::{instruction::ParsedInstruction, ProgramParseResult},
+};
+use plerkle_serialization::AccountInfo;
+use solana_sdk::{program_pack::Pack, pubkey::Pubkey};
+use spl_token_2022::{
+    extension::{
+        metadata_pointer::MetadataPointer, transfer_hook::TransferHook, AccountType,
+        BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::{Account, AccountState, Mint, Multisig},
+};
+
+use super::is_closed_account;
+
+pub struct TokenExtensionsParser;
+
+/// Token-2022 mint with the extensions the RWA programs rely on.
+pub struct MintAccount {
+    pub supply: u64,
+    pub decimals: u8,
+    pub mint_authority: Option<Pubkey>,
+    pub freeze_authority: Option<Pubkey>,
+    pub transfer_hook_program_id: Option<Pubkey>,
+    pub transfer_hook_authority: Option<Pubkey>,
+    pub metadata_address: Option<Pubkey>,
+    pub metadata_pointer_authority: Option<Pubkey>,
+}
+
+pub struct TokenAccount {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub delegate: Option<Pubkey>,
+    pub frozen: bool,
+}
+
+pub enum TokenExtensionsProgram {
+    Mint(Box<MintAccount>),
+    TokenAccount(Box<TokenAccount>),
+    /// Multisig accounts, which the indexer doesn't track.
+    Unsupported,
+    EmptyAccount,
+}
+
+impl ParseResult for TokenExtensionsProgram {
+    fn result(&self) -> &Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+    fn result_type(&self) -> ProgramParseResult {
+        ProgramParseResult::TokenExtensionsProgram(self)
+    }
+}
+
+impl ProgramParser for TokenExtensionsParser {
+    fn key(&self) -> Pubkey {
+        spl_token_2022::id()
+    }
+    fn key_match(&self, key: &Pubkey) -> bool {
+        key == &spl_token_2022::id()
+    }
+    fn handles_account_updates(&self) -> bool {
+        true
+    }
+
+    fn handles_instructions(&self) -> bool {
+        false
+    }
+    fn handle_account(
+        &self,
+        account_info: &AccountInfo,
+    ) -> Result<Box<(dyn ParseResult + 'static)>, TransformerError> {
+        let account_data = match account_info.data() {
+            Some(data) if !is_closed_account(account_info) => data.iter().collect::<Vec<_>>(),
+            _ => return Ok(Box::new(TokenExtensionsProgram::EmptyAccount)),
+        };
+
+        // Accounts with extensions are padded past the base account length and carry their
+        // type right after it, so the length alone only identifies plain mints and accounts.
+        let len = account_data.len();
+        let account = if len == Multisig::LEN {
+            TokenExtensionsProgram::Unsupported
+        } else if len == Mint::LEN
+            || (len > Account::LEN && account_data[Account::LEN] == AccountType::Mint as u8)
+        {
+            TokenExtensionsProgram::Mint(Box::new(unpack_mint(&account_data)?))
+        } else if len >= Account::LEN {
+            TokenExtensionsProgram::TokenAccount(Box::new(unpack_token_account(&account_data)?))
+        } else {
+            return Err(TransformerError::InvalidAccountType);
+        };
+
+        Ok(Box::new(account))
+    }
+
+    fn handle_instruction(
+        &self,
+        _data: &[u8],
+    ) -> Result<Option<ParsedInstruction>, TransformerError> {
+        Ok(None)
+    }
+}
+
+fn unpack_mint(data: &[u8]) -> Result<MintAccount, TransformerError> {
+    let mint = StateWithExtensions::<Mint>::unpack(data).map_err(|_| {
+        TransformerError::CustomDeserializationError("Token Mint Unpack Failed".to_string())
+    })?;
+    let transfer_hook = mint.get_extension::<TransferHook>().ok();
+    let metadata_pointer = mint.get_extension::<MetadataPointer>().ok();
+
+    Ok(MintAccount {
+        supply: mint.base.supply,
+        decimals: mint.base.decimals,
+        mint_authority: mint.base.mint_authority.into(),
+        freeze_authority: mint.base.freeze_authority.into(),
+        transfer_hook_program_id: transfer_hook.and_then(|hook| hook.program_id.into()),
+        transfer_hook_authority: transfer_hook.and_then(|hook| hook.authority.into()),
+        metadata_address: metadata_pointer.and_then(|pointer| pointer.metadata_address.into()),
+        metadata_pointer_authority: metadata_pointer.and_then(|pointer| pointer.authority.into()),
+    })
+}
+
+fn unpack_token_account(data: &[u8]) -> Result<TokenAccount, TransformerError> {
+    let account = StateWithExtensions::<Account>::unpack(data).map_err(|_| {
+        TransformerError::CustomDeserializationError("Token Account Unpack Failed".to_string())
+    })?;
+
+    Ok(TokenAccount {
+        mint: account.base.mint,
+        owner: account.base.owner,
+        amount: account.base.amount,
+        delegate: account.base.delegate.into(),
+        frozen: account.base.state == AccountState::Frozen,
+    })
+}